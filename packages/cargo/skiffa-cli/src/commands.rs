@@ -0,0 +1,149 @@
+mod bundle;
+mod convert;
+mod dereference;
+mod diff;
+mod dump_model;
+mod lint;
+mod list_schemas;
+mod merge;
+mod normalize;
+mod slice;
+mod validate;
+
+pub use bundle::*;
+pub use convert::*;
+pub use dereference::*;
+pub use diff::*;
+pub use dump_model::*;
+pub use lint::*;
+pub use list_schemas::*;
+pub use merge::*;
+pub use normalize::*;
+pub use slice::*;
+pub use validate::*;
+
+use crate::error::CliError;
+use skiffa_core::documents::oas30::UpgradeIssue;
+use skiffa_core::documents::DocumentContext;
+use skiffa_core::error::Error;
+use skiffa_core::models::Api;
+use skiffa_core::utils::{sync, NodeLocation};
+use std::io::Write;
+use std::path::PathBuf;
+
+/// The result of a command that ran, findings (like invalid documents or
+/// breaking changes) result in a non zero exit code.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+  Success,
+  Findings,
+}
+
+/// Format of documents that are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum DocumentFormat {
+  Json,
+  Yaml,
+}
+
+/// Format of reports that are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReportFormat {
+  Text,
+  Json,
+}
+
+#[derive(clap::Args)]
+pub struct DocumentOutputArguments {
+  /// File or url of the document
+  location: String,
+  #[arg(long, value_enum, default_value_t = DocumentFormat::Yaml)]
+  format: DocumentFormat,
+  /// Write to this file instead of stdout
+  #[arg(long, short)]
+  output: Option<PathBuf>,
+}
+
+fn new_context() -> sync::Shared<DocumentContext> {
+  let context = sync::Shared::new(DocumentContext::new());
+  context.register_well_known_factories();
+  context
+}
+
+async fn load_document(context: &DocumentContext, location: &NodeLocation) -> Result<(), CliError> {
+  context
+    .load_from_location(location)
+    .await
+    .map_err(|error| CliError::Document(location.clone(), error))
+}
+
+/// The api model of a loaded document, a document that does not describe an
+/// http api is an error.
+///
+fn get_api_model(
+  context: &DocumentContext,
+  location: &NodeLocation,
+) -> Result<sync::Shared<Api>, CliError> {
+  context
+    .get_api_model(location)
+    .map_err(|error| match error {
+      Error::NotFound => CliError::NoApiModel(location.clone()),
+      error => CliError::Document(location.clone(), error),
+    })
+}
+
+/// Report issues of converting or upgrading a document, the outcome has
+/// findings if there are any.
+///
+fn report_issues(issues: &[UpgradeIssue]) -> Outcome {
+  for issue in issues {
+    eprintln!("{}: {}", issue.location, issue.message);
+  }
+
+  if issues.is_empty() {
+    Outcome::Success
+  } else {
+    Outcome::Findings
+  }
+}
+
+fn write_document(
+  node: &serde_json::Value,
+  format: DocumentFormat,
+  output: Option<&PathBuf>,
+) -> Result<(), CliError> {
+  let text = match format {
+    DocumentFormat::Json => {
+      serde_json::to_string_pretty(node).map_err(|_error| CliError::SerializationError)? + "\n"
+    }
+    DocumentFormat::Yaml => {
+      serde_yaml::to_string(node).map_err(|_error| CliError::SerializationError)?
+    }
+  };
+  write_output(&text, output)
+}
+
+/// Write to the output file, or to stdout if there is no output file
+fn write_output(text: &str, output: Option<&PathBuf>) -> Result<(), CliError> {
+  match output {
+    Some(output) => std::fs::write(output, text)?,
+    None => std::io::stdout().write_all(text.as_bytes())?,
+  }
+  Ok(())
+}
+
+/// Write the files to a new directory in the temporary directory, returns
+/// the path of the directory.
+///
+#[cfg(test)]
+fn write_test_files(name: &str, files: &[(&str, &str)]) -> PathBuf {
+  let directory = std::env::temp_dir()
+    .join(format!("skiffa-cli-{}", std::process::id()))
+    .join(name);
+  std::fs::create_dir_all(&directory).unwrap();
+  for (file_name, text) in files {
+    std::fs::write(directory.join(file_name), text).unwrap();
+  }
+  directory
+}
@@ -0,0 +1,72 @@
+use super::{load_document, new_context, write_document, DocumentOutputArguments, Outcome};
+use crate::error::CliError;
+use crate::location::parse_location;
+use skiffa_core::documents::bundle_document;
+
+pub async fn bundle(arguments: DocumentOutputArguments) -> Result<Outcome, CliError> {
+  let location = parse_location(&arguments.location)?;
+  let context = new_context();
+  load_document(&context, &location).await?;
+  context
+    .load_referenced_nodes(&location)
+    .await
+    .map_err(|error| CliError::Document(location.clone(), error))?;
+
+  let node = bundle_document(context.as_ref(), &location)
+    .map_err(|error| CliError::Document(location.clone(), error))?;
+  write_document(&node, arguments.format, arguments.output.as_ref())?;
+
+  Ok(Outcome::Success)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::commands::{write_test_files, DocumentFormat};
+
+  #[tokio::test]
+  async fn test_bundle() {
+    let directory = write_test_files(
+      "bundle",
+      &[
+        (
+          "api.yaml",
+          r#"
+openapi: 3.0.3
+paths:
+  /pets:
+    get:
+      operationId: list-pets
+      responses:
+        "200":
+          description: Ok
+          content:
+            application/json:
+              schema:
+                $ref: "./schemas.yaml#/pet"
+"#,
+        ),
+        ("schemas.yaml", "pet:\n  type: object\n"),
+      ],
+    );
+    let output = directory.join("bundled.json");
+
+    let outcome = bundle(DocumentOutputArguments {
+      location: directory.join("api.yaml").to_str().unwrap().to_owned(),
+      format: DocumentFormat::Json,
+      output: Some(output.clone()),
+    })
+    .await
+    .unwrap();
+    assert_eq!(outcome, Outcome::Success);
+
+    let node: serde_json::Value =
+      serde_json::from_str(&std::fs::read_to_string(output).unwrap()).unwrap();
+    assert_eq!(
+      node
+        .pointer("/paths/~1pets/get/responses/200/content/application~1json/schema")
+        .unwrap(),
+      &serde_json::json!({ "type": "object" })
+    );
+  }
+}
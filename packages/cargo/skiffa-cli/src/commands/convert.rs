@@ -0,0 +1,80 @@
+use super::{report_issues, write_document, DocumentFormat, Outcome};
+use crate::error::CliError;
+use crate::location::parse_location;
+use skiffa_core::documents::{oas30, swagger2, DocumentType};
+use skiffa_core::error::Error;
+use skiffa_core::utils::NodeCache;
+use std::path::PathBuf;
+
+/// Version of the OpenApi document to convert to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum OpenApiVersion {
+  #[value(name = "3.0")]
+  V30,
+  #[value(name = "3.1")]
+  V31,
+}
+
+impl From<OpenApiVersion> for DocumentType {
+  fn from(value: OpenApiVersion) -> Self {
+    match value {
+      OpenApiVersion::V30 => Self::OpenApiV30,
+      OpenApiVersion::V31 => Self::OpenApiV31,
+    }
+  }
+}
+
+#[derive(clap::Args)]
+pub struct ConvertArguments {
+  /// File or url of the Swagger 2.0 or OpenApi 3.0 document
+  location: String,
+  /// Version of the converted document
+  #[arg(long, value_enum, default_value_t = OpenApiVersion::V30)]
+  to: OpenApiVersion,
+  #[arg(long, value_enum, default_value_t = DocumentFormat::Yaml)]
+  format: DocumentFormat,
+  /// Write to this file instead of stdout
+  #[arg(long, short)]
+  output: Option<PathBuf>,
+}
+
+/// Convert a single document, referenced documents are not converted. Things
+/// that could not be converted without losing information are reported, the
+/// outcome has findings if there are any.
+///
+pub async fn convert(arguments: ConvertArguments) -> Result<Outcome, CliError> {
+  let location = parse_location(&arguments.location)?;
+
+  let mut cache = NodeCache::new();
+  cache
+    .load_from_location(&location)
+    .await
+    .map_err(|error| CliError::Document(location.clone(), error.into()))?;
+  let node = cache
+    .get_node(&location)
+    .ok_or_else(|| CliError::Document(location.clone(), Error::NotFound))?;
+
+  let to_document_type = arguments.to.into();
+  let (node, issues) = match (DocumentType::try_from(node), to_document_type) {
+    (Ok(DocumentType::Swagger2), to_document_type) => {
+      let converted_document = swagger2::convert_to_openapi(node, &location, to_document_type)
+        .map_err(|error| CliError::Document(location.clone(), error))?;
+      (converted_document.node, converted_document.issues)
+    }
+    (Ok(DocumentType::OpenApiV30), DocumentType::OpenApiV30) => (node.clone(), Vec::new()),
+    (Ok(DocumentType::OpenApiV30), DocumentType::OpenApiV31) => {
+      let upgraded_document = oas30::upgrade_to_oas31(&cache, &location)
+        .map_err(|error| CliError::Document(location.clone(), error))?;
+      (upgraded_document.node, upgraded_document.issues)
+    }
+    _ => {
+      return Err(CliError::Document(
+        location.clone(),
+        Error::DocumentTypeError,
+      ))
+    }
+  };
+  write_document(&node, arguments.format, arguments.output.as_ref())?;
+
+  Ok(report_issues(&issues))
+}
@@ -0,0 +1,20 @@
+use super::{load_document, new_context, write_document, DocumentOutputArguments, Outcome};
+use crate::error::CliError;
+use crate::location::parse_location;
+use skiffa_core::documents::dereference_document;
+
+pub async fn dereference(arguments: DocumentOutputArguments) -> Result<Outcome, CliError> {
+  let location = parse_location(&arguments.location)?;
+  let context = new_context();
+  load_document(&context, &location).await?;
+  context
+    .load_referenced_nodes(&location)
+    .await
+    .map_err(|error| CliError::Document(location.clone(), error))?;
+
+  let node = dereference_document(context.as_ref(), &location)
+    .map_err(|error| CliError::Document(location.clone(), error))?;
+  write_document(&node, arguments.format, arguments.output.as_ref())?;
+
+  Ok(Outcome::Success)
+}
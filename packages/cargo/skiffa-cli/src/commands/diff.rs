@@ -0,0 +1,138 @@
+use super::{get_api_model, load_document, new_context, Outcome, ReportFormat};
+use crate::error::CliError;
+use crate::location::parse_location;
+use skiffa_core::models::ApiDiff;
+
+#[derive(clap::Args)]
+pub struct DiffArguments {
+  /// File or url of the old version of the document
+  old_location: String,
+  /// File or url of the new version of the document
+  new_location: String,
+  #[arg(long, value_enum, default_value_t = ReportFormat::Text)]
+  format: ReportFormat,
+}
+
+/// Report the differences between the api models, the outcome has findings
+/// when one of the differences is breaking.
+///
+pub async fn diff(arguments: DiffArguments) -> Result<Outcome, CliError> {
+  let old_location = parse_location(&arguments.old_location)?;
+  let new_location = parse_location(&arguments.new_location)?;
+
+  // every version gets it's own context, both versions may be (or reference)
+  // documents at the same location
+  let old_context = new_context();
+  load_document(&old_context, &old_location).await?;
+  let new_context = new_context();
+  load_document(&new_context, &new_location).await?;
+
+  let old_api_model = get_api_model(&old_context, &old_location)?;
+  let new_api_model = get_api_model(&new_context, &new_location)?;
+  let api_diff = ApiDiff::new(
+    &old_api_model,
+    &new_api_model,
+    old_context.as_ref(),
+    new_context.as_ref(),
+  );
+
+  match arguments.format {
+    ReportFormat::Text => {
+      for difference in &api_diff.differences {
+        let kind = serde_json::to_value(difference.kind).unwrap();
+        println!(
+          "{} {}: {}",
+          if difference.breaking {
+            "breaking"
+          } else {
+            "non-breaking"
+          },
+          kind.as_str().unwrap_or_default(),
+          difference.message
+        );
+      }
+    }
+    ReportFormat::Json => {
+      println!("{}", api_diff.to_report());
+    }
+  }
+
+  if api_diff.is_breaking() {
+    Ok(Outcome::Findings)
+  } else {
+    Ok(Outcome::Success)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::commands::write_test_files;
+
+  #[tokio::test]
+  async fn test_diff() {
+    let directory = write_test_files(
+      "diff",
+      &[
+        (
+          "v1.yaml",
+          r#"
+openapi: 3.0.3
+paths:
+  /pets:
+    get:
+      operationId: list-pets
+      responses:
+        "200":
+          description: Ok
+  /pets/{id}:
+    get:
+      operationId: get-pet
+      responses:
+        "200":
+          description: Ok
+"#,
+        ),
+        (
+          "v2.yaml",
+          r#"
+openapi: 3.0.3
+paths:
+  /pets:
+    get:
+      operationId: list-pets
+      responses:
+        "200":
+          description: Ok
+"#,
+        ),
+        ("events.yaml", "asyncapi: 3.0.0\n"),
+      ],
+    );
+    let location = |file_name: &str| directory.join(file_name).to_str().unwrap().to_owned();
+    let arguments = |old: &str, new: &str| DiffArguments {
+      old_location: location(old),
+      new_location: location(new),
+      format: ReportFormat::Json,
+    };
+
+    let outcome = diff(arguments("v1.yaml", "v1.yaml")).await.unwrap();
+    assert_eq!(outcome, Outcome::Success);
+
+    let outcome = diff(arguments("v2.yaml", "v1.yaml")).await.unwrap();
+    assert_eq!(outcome, Outcome::Success);
+
+    let outcome = diff(arguments("v1.yaml", "v2.yaml")).await.unwrap();
+    assert_eq!(outcome, Outcome::Findings);
+
+    // the error is about the new version
+    let error = diff(arguments("v1.yaml", "events.yaml")).await.unwrap_err();
+    let CliError::NoApiModel(error_location) = error else {
+      panic!("unexpected error {}", error);
+    };
+    assert_eq!(
+      error_location,
+      parse_location(&location("events.yaml")).unwrap()
+    );
+  }
+}
@@ -0,0 +1,28 @@
+use super::{get_api_model, load_document, new_context, write_output, Outcome};
+use crate::error::CliError;
+use crate::location::parse_location;
+use skiffa_core::models::ApiIr;
+use std::path::PathBuf;
+
+#[derive(clap::Args)]
+pub struct DumpModelArguments {
+  /// File or url of the document
+  location: String,
+  /// Write to this file instead of stdout
+  #[arg(long, short)]
+  output: Option<PathBuf>,
+}
+
+pub async fn dump_model(arguments: DumpModelArguments) -> Result<Outcome, CliError> {
+  let location = parse_location(&arguments.location)?;
+  let context = new_context();
+  load_document(&context, &location).await?;
+
+  let api_model = get_api_model(&context, &location)?;
+  let text = ApiIr::new(api_model)
+    .to_json()
+    .map_err(|_error| CliError::SerializationError)?;
+  write_output(&(text + "\n"), arguments.output.as_ref())?;
+
+  Ok(Outcome::Success)
+}
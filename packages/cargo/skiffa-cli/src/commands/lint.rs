@@ -0,0 +1,272 @@
+use super::{get_api_model, load_document, new_context, Outcome, ReportFormat};
+use crate::error::CliError;
+use crate::location::parse_location;
+use serde_json::json;
+use skiffa_core::models::{Api, StatusKind};
+use skiffa_core::utils::NodeLocation;
+use std::collections::BTreeMap;
+
+/// Every operation should have a summary or a description
+pub const RULE_OPERATION_DESCRIPTION: &str = "operation-description";
+/// Every operation should have at least one tag
+pub const RULE_OPERATION_TAGS: &str = "operation-tags";
+/// Every operation should have a successful (2XX) result
+pub const RULE_OPERATION_SUCCESS: &str = "operation-success";
+/// Parameters in the path pattern should be declared, and declared path
+/// parameters should be in the pattern
+pub const RULE_PATH_PARAMETERS: &str = "path-parameters";
+/// Operation names should be unique in the api
+pub const RULE_UNIQUE_OPERATION_NAMES: &str = "unique-operation-names";
+
+pub const LINT_RULES: [&str; 5] = [
+  RULE_OPERATION_DESCRIPTION,
+  RULE_OPERATION_TAGS,
+  RULE_OPERATION_SUCCESS,
+  RULE_PATH_PARAMETERS,
+  RULE_UNIQUE_OPERATION_NAMES,
+];
+
+#[derive(clap::Args)]
+pub struct LintArguments {
+  /// File or url of the document
+  location: String,
+  /// Do not report findings of this rule, may be repeated
+  #[arg(long, value_name = "RULE", value_parser = clap::builder::PossibleValuesParser::new(LINT_RULES))]
+  skip: Vec<String>,
+  #[arg(long, value_enum, default_value_t = ReportFormat::Text)]
+  format: ReportFormat,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintFinding {
+  pub rule: &'static str,
+  pub location: NodeLocation,
+  pub message: String,
+}
+
+pub async fn lint(arguments: LintArguments) -> Result<Outcome, CliError> {
+  let location = parse_location(&arguments.location)?;
+  let context = new_context();
+  load_document(&context, &location).await?;
+
+  let api_model = get_api_model(&context, &location)?;
+  let findings: Vec<_> = lint_api(&api_model)
+    .into_iter()
+    .filter(|finding| !arguments.skip.iter().any(|rule| rule == finding.rule))
+    .collect();
+
+  match arguments.format {
+    ReportFormat::Text => {
+      for finding in &findings {
+        println!("{} {}: {}", finding.location, finding.rule, finding.message);
+      }
+    }
+    ReportFormat::Json => {
+      let findings: Vec<_> = findings
+        .iter()
+        .map(|finding| {
+          json!({
+            "rule": finding.rule,
+            "location": finding.location.to_string(),
+            "message": finding.message,
+          })
+        })
+        .collect();
+      println!("{}", serde_json::Value::Array(findings));
+    }
+  }
+
+  if findings.is_empty() {
+    Ok(Outcome::Success)
+  } else {
+    Ok(Outcome::Findings)
+  }
+}
+
+/// Check the api against all lint rules
+pub fn lint_api(api: &Api) -> Vec<LintFinding> {
+  let mut findings = Vec::new();
+  let mut operation_locations = BTreeMap::<_, Vec<_>>::new();
+
+  for path in &api.paths {
+    let pattern_parameters = get_pattern_parameters(&path.pattern);
+
+    for operation in &path.operations {
+      operation_locations
+        .entry(operation.name.as_str())
+        .or_default()
+        .push(&operation.location);
+
+      let mut add_finding = |rule, message| {
+        findings.push(LintFinding {
+          rule,
+          location: operation.location.clone(),
+          message,
+        })
+      };
+
+      if operation.summary.is_none() && operation.description.is_none() {
+        add_finding(
+          RULE_OPERATION_DESCRIPTION,
+          format!("operation {} has no summary or description", operation.name),
+        );
+      }
+
+      if operation.tags.is_empty() {
+        add_finding(
+          RULE_OPERATION_TAGS,
+          format!("operation {} has no tags", operation.name),
+        );
+      }
+
+      let has_success = operation.operation_results.iter().any(|operation_result| {
+        match operation_result.status_kind {
+          StatusKind::Default => false,
+          StatusKind::Class(class) => class == 2,
+          StatusKind::Code(code) => (200..300).contains(&code),
+        }
+      });
+      if !has_success {
+        add_finding(
+          RULE_OPERATION_SUCCESS,
+          format!(
+            "operation {} has no successful (2XX) result",
+            operation.name
+          ),
+        );
+      }
+
+      for name in &pattern_parameters {
+        if !operation
+          .path_parameters
+          .iter()
+          .any(|parameter| &parameter.name == name)
+        {
+          add_finding(
+            RULE_PATH_PARAMETERS,
+            format!(
+              "parameter {} in path {} is not declared by operation {}",
+              name, path.pattern, operation.name
+            ),
+          );
+        }
+      }
+      for parameter in &operation.path_parameters {
+        if !pattern_parameters.contains(&parameter.name) {
+          add_finding(
+            RULE_PATH_PARAMETERS,
+            format!(
+              "path parameter {} of operation {} is not in path {}",
+              parameter.name, operation.name, path.pattern
+            ),
+          );
+        }
+      }
+    }
+  }
+
+  for (name, locations) in operation_locations {
+    if locations.len() < 2 {
+      continue;
+    }
+    for location in locations {
+      findings.push(LintFinding {
+        rule: RULE_UNIQUE_OPERATION_NAMES,
+        location: location.clone(),
+        message: format!("operation name {} is used more than once", name),
+      });
+    }
+  }
+
+  findings
+}
+
+/// The names of the parameters in a path pattern like `/pets/{pet-id}`
+fn get_pattern_parameters(pattern: &str) -> Vec<String> {
+  pattern
+    .split('{')
+    .skip(1)
+    .filter_map(|part| part.split_once('}'))
+    .map(|(name, _rest)| name.to_owned())
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use skiffa_core::utils::MemoryFetcher;
+
+  #[tokio::test]
+  async fn test_lint_api() {
+    let location: NodeLocation = "http://example.com/api.yaml".parse().unwrap();
+    let context = new_context();
+    context.set_fetcher(MemoryFetcher::from_iter([(
+      location.clone(),
+      r#"
+openapi: 3.0.3
+paths:
+  /pets/{pet-id}:
+    get:
+      operationId: get-pet
+      summary: Get a pet
+      tags: [pets]
+      parameters:
+        - name: pet-id
+          in: path
+          required: true
+      responses:
+        "200":
+          description: Ok
+    put:
+      operationId: get-pet
+      tags: [pets]
+      parameters:
+        - name: id
+          in: path
+          required: true
+      responses:
+        default:
+          description: Error
+"#,
+    )]));
+    context.load_from_location(&location).await.unwrap();
+    let api = context.get_api_model(&location).unwrap();
+
+    let mut rules: Vec<_> = lint_api(&api)
+      .into_iter()
+      .map(|finding| (finding.rule, finding.location.get_pointer().unwrap()))
+      .collect();
+    rules.sort();
+
+    let put_pointer = vec![
+      "paths".to_owned(),
+      "/pets/{pet-id}".to_owned(),
+      "put".to_owned(),
+    ];
+    let get_pointer = vec![
+      "paths".to_owned(),
+      "/pets/{pet-id}".to_owned(),
+      "get".to_owned(),
+    ];
+    assert_eq!(
+      rules,
+      vec![
+        (RULE_OPERATION_DESCRIPTION, put_pointer.clone()),
+        (RULE_OPERATION_SUCCESS, put_pointer.clone()),
+        (RULE_PATH_PARAMETERS, put_pointer.clone()),
+        (RULE_PATH_PARAMETERS, put_pointer.clone()),
+        (RULE_UNIQUE_OPERATION_NAMES, get_pointer),
+        (RULE_UNIQUE_OPERATION_NAMES, put_pointer),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_get_pattern_parameters() {
+    assert_eq!(
+      get_pattern_parameters("/pets/{pet-id}/toys/{toy-id}.json"),
+      vec!["pet-id".to_owned(), "toy-id".to_owned()]
+    );
+    assert!(get_pattern_parameters("/pets").is_empty());
+  }
+}
@@ -0,0 +1,53 @@
+use super::{load_document, new_context, Outcome, ReportFormat};
+use crate::error::CliError;
+use crate::location::parse_location;
+use serde_json::json;
+
+#[derive(clap::Args)]
+pub struct ListSchemasArguments {
+  /// File or url of the document
+  location: String,
+  #[arg(long, value_enum, default_value_t = ReportFormat::Text)]
+  format: ReportFormat,
+}
+
+/// List the id and the retrieval location of every schema, one per line, or
+/// as a json array.
+///
+pub async fn list_schemas(arguments: ListSchemasArguments) -> Result<Outcome, CliError> {
+  let location = parse_location(&arguments.location)?;
+  let context = new_context();
+  load_document(&context, &location).await?;
+
+  let schemas = context
+    .get_schemas()
+    .map_err(|error| CliError::Document(location.clone(), error))?;
+
+  match arguments.format {
+    ReportFormat::Text => {
+      for schema in schemas {
+        println!("{}\t{}", schema.schema_id, schema.schema_location);
+      }
+    }
+    ReportFormat::Json => {
+      let schemas: Vec<_> = schemas
+        .into_iter()
+        .map(|schema| {
+          json!({
+            "schemaId": schema.schema_id.to_string(),
+            "schemaLocation": schema.schema_location.to_string(),
+            "anchorLocations": schema
+              .anchor_locations
+              .iter()
+              .map(|location| location.to_string())
+              .collect::<Vec<_>>(),
+            "documentLocation": schema.document_location.to_string(),
+          })
+        })
+        .collect();
+      println!("{}", serde_json::Value::Array(schemas));
+    }
+  }
+
+  Ok(Outcome::Success)
+}
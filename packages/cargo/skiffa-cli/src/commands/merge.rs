@@ -0,0 +1,159 @@
+use super::{
+  load_document, new_context, report_issues, write_document, DocumentFormat, OpenApiVersion,
+  Outcome,
+};
+use crate::error::CliError;
+use crate::location::parse_location;
+use skiffa_core::documents::write_openapi_document;
+use skiffa_core::error::Error;
+use std::path::PathBuf;
+
+#[derive(clap::Args)]
+pub struct MergeArguments {
+  /// Files or urls of the documents, in order of precedence
+  #[arg(required = true)]
+  locations: Vec<String>,
+  /// Prefix for the paths of a document, paired with the documents in order.
+  /// There may be fewer prefixes than documents, not more.
+  #[arg(long)]
+  prefix: Vec<String>,
+  /// Version of the merged document
+  #[arg(long, value_enum, default_value_t = OpenApiVersion::V31)]
+  to: OpenApiVersion,
+  #[arg(long, value_enum, default_value_t = DocumentFormat::Yaml)]
+  format: DocumentFormat,
+  /// Write to this file instead of stdout
+  #[arg(long, short)]
+  output: Option<PathBuf>,
+}
+
+/// Merge the apis of the documents into one document. Conflicts are
+/// reported, the outcome has findings if there are any.
+///
+/// The schemas in the components of the first document are copied into the
+/// merged document. Schemas of the other documents are not copied, they are
+/// referenced via their location. Bundle the merged document to include
+/// them.
+///
+pub async fn merge(arguments: MergeArguments) -> Result<Outcome, CliError> {
+  if arguments.prefix.len() > arguments.locations.len() {
+    return Err(CliError::InvalidArguments(format!(
+      "there are {} prefixes for {} documents",
+      arguments.prefix.len(),
+      arguments.locations.len()
+    )));
+  }
+
+  let context = new_context();
+  let mut sources = Vec::new();
+  for (index, location) in arguments.locations.iter().enumerate() {
+    let location = parse_location(location)?;
+    load_document(&context, &location).await?;
+    let path_prefix = arguments.prefix.get(index).cloned().unwrap_or_default();
+    sources.push((location, path_prefix));
+  }
+
+  let api_merge = context.merge_api_models(&sources).map_err(|error| {
+    // find the document that has no api model
+    let location = sources
+      .iter()
+      .map(|(location, _)| location)
+      .find(|location| context.get_api_model(location).is_err())
+      .unwrap_or(&sources[0].0)
+      .clone();
+    match error {
+      Error::NotFound => CliError::NoApiModel(location),
+      error => CliError::Document(location, error),
+    }
+  })?;
+
+  let written_document =
+    write_openapi_document(&api_merge.api, arguments.to.into(), context.as_ref())
+      .map_err(|error| CliError::Document(sources[0].0.clone(), error))?;
+  write_document(
+    &written_document.node,
+    arguments.format,
+    arguments.output.as_ref(),
+  )?;
+
+  for conflict in &api_merge.conflicts {
+    eprintln!("{}: {}", conflict.location, conflict.message);
+  }
+
+  let outcome = report_issues(&written_document.issues);
+  if api_merge.has_conflicts() {
+    Ok(Outcome::Findings)
+  } else {
+    Ok(outcome)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::commands::write_test_files;
+
+  #[tokio::test]
+  async fn test_merge() {
+    let directory = write_test_files(
+      "merge",
+      &[
+        (
+          "pets.yaml",
+          r#"
+openapi: 3.0.3
+paths:
+  /pets/{id}:
+    get:
+      operationId: get-pet
+      parameters:
+        - { name: id, in: path, required: true, schema: { type: string } }
+      responses:
+        "200":
+          description: Ok
+"#,
+        ),
+        (
+          "owners.yaml",
+          r#"
+openapi: 3.0.3
+paths:
+  /pets/{petId}:
+    put:
+      operationId: update-pet
+      parameters:
+        - { name: petId, in: path, required: true, schema: { type: string } }
+      responses:
+        "204":
+          description: Ok
+"#,
+        ),
+      ],
+    );
+    let location = |file_name: &str| directory.join(file_name).to_str().unwrap().to_owned();
+    let output = directory.join("merged.json");
+    let arguments = |prefix: Vec<String>| MergeArguments {
+      locations: vec![location("pets.yaml"), location("owners.yaml")],
+      prefix,
+      to: OpenApiVersion::V30,
+      format: DocumentFormat::Json,
+      output: Some(output.clone()),
+    };
+
+    let outcome = merge(arguments(Vec::new())).await.unwrap();
+    assert_eq!(outcome, Outcome::Success);
+    let node: serde_json::Value =
+      serde_json::from_str(&std::fs::read_to_string(&output).unwrap()).unwrap();
+    assert_eq!(
+      node
+        .pointer("/paths/~1pets~1{id}/put/parameters/0/name")
+        .unwrap(),
+      "id"
+    );
+
+    let error = merge(arguments(vec![String::new(), String::new(), String::new()]))
+      .await
+      .unwrap_err();
+    assert!(matches!(error, CliError::InvalidArguments(_)));
+  }
+}
@@ -0,0 +1,46 @@
+use super::{
+  load_document, new_context, report_issues, write_document, DocumentFormat, OpenApiVersion,
+  Outcome,
+};
+use crate::error::CliError;
+use crate::location::parse_location;
+use skiffa_core::error::Error;
+use std::path::PathBuf;
+
+#[derive(clap::Args)]
+pub struct NormalizeArguments {
+  /// File or url of the document
+  location: String,
+  /// Version of the written document
+  #[arg(long, value_enum, default_value_t = OpenApiVersion::V31)]
+  to: OpenApiVersion,
+  #[arg(long, value_enum, default_value_t = DocumentFormat::Yaml)]
+  format: DocumentFormat,
+  /// Write to this file instead of stdout
+  #[arg(long, short)]
+  output: Option<PathBuf>,
+}
+
+/// Write the document again from its api model, so documents that describe
+/// the same api are written the same way. Issues of upgrading the schemas
+/// are reported, the outcome has findings if there are any.
+///
+pub async fn normalize(arguments: NormalizeArguments) -> Result<Outcome, CliError> {
+  let location = parse_location(&arguments.location)?;
+  let context = new_context();
+  load_document(&context, &location).await?;
+
+  let written_document = context
+    .write_openapi_document(&location, arguments.to.into())
+    .map_err(|error| match error {
+      Error::NotFound => CliError::NoApiModel(location.clone()),
+      error => CliError::Document(location.clone(), error),
+    })?;
+  write_document(
+    &written_document.node,
+    arguments.format,
+    arguments.output.as_ref(),
+  )?;
+
+  Ok(report_issues(&written_document.issues))
+}
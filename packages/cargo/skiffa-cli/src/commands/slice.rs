@@ -0,0 +1,104 @@
+use super::{
+  load_document, new_context, report_issues, write_document, DocumentFormat, OpenApiVersion,
+  Outcome,
+};
+use crate::error::CliError;
+use crate::location::parse_location;
+use skiffa_core::documents::write_openapi_document;
+use skiffa_core::error::Error;
+use skiffa_core::models::ApiFilter;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+#[derive(clap::Args)]
+pub struct SliceArguments {
+  /// File or url of the document
+  location: String,
+  /// Keep operations with one of these tags
+  #[arg(long)]
+  tag: Vec<String>,
+  /// Leave out operations with one of these tags
+  #[arg(long)]
+  exclude_tag: Vec<String>,
+  /// Keep operations on paths that match one of these globs
+  #[arg(long)]
+  path: Vec<String>,
+  /// Leave out operations on paths that match one of these globs
+  #[arg(long)]
+  exclude_path: Vec<String>,
+  /// Keep operations with one of these operation ids
+  #[arg(long)]
+  operation: Vec<String>,
+  /// Leave out operations with one of these operation ids
+  #[arg(long)]
+  exclude_operation: Vec<String>,
+  /// Leave out operations and paths with `x-internal: true`
+  #[arg(long)]
+  exclude_internal: bool,
+  /// Leave out deprecated operations
+  #[arg(long)]
+  exclude_deprecated: bool,
+  /// Version of the written document
+  #[arg(long, value_enum, default_value_t = OpenApiVersion::V31)]
+  to: OpenApiVersion,
+  #[arg(long, value_enum, default_value_t = DocumentFormat::Yaml)]
+  format: DocumentFormat,
+  /// Write to this file instead of stdout
+  #[arg(long, short)]
+  output: Option<PathBuf>,
+}
+
+/// Write a document with only the operations that pass the filter, schemas
+/// in the components that those operations do not use are left out.
+///
+pub async fn slice(arguments: SliceArguments) -> Result<Outcome, CliError> {
+  let location = parse_location(&arguments.location)?;
+  let context = new_context();
+  load_document(&context, &location).await?;
+
+  let filter = ApiFilter {
+    include_tags: arguments.tag,
+    exclude_tags: arguments.exclude_tag,
+    include_paths: arguments.path,
+    exclude_paths: arguments.exclude_path,
+    include_operation_names: arguments.operation,
+    exclude_operation_names: arguments.exclude_operation,
+    exclude_internal: arguments.exclude_internal,
+    exclude_deprecated: arguments.exclude_deprecated,
+  };
+  let to_cli_error = |error| match error {
+    Error::NotFound => CliError::NoApiModel(location.clone()),
+    error => CliError::Document(location.clone(), error),
+  };
+  let api_slice = context
+    .slice_api_model(&location, &filter)
+    .map_err(to_cli_error)?;
+  let written_document =
+    write_openapi_document(&api_slice.api, arguments.to.into(), context.as_ref())
+      .map_err(to_cli_error)?;
+  let mut node = written_document.node;
+
+  let used_schema_names: BTreeSet<_> = api_slice
+    .schema_locations
+    .iter()
+    .filter(|schema_location| schema_location.set_root() == location.set_root())
+    .filter_map(
+      |schema_location| match schema_location.get_pointer()?.as_slice() {
+        [components, schemas, name, ..] if components == "components" && schemas == "schemas" => {
+          Some(name.clone())
+        }
+        _ => None,
+      },
+    )
+    .collect();
+  if let Some(schemas) = node
+    .pointer_mut("/components/schemas")
+    .and_then(|schemas| schemas.as_object_mut())
+  {
+    schemas.retain(|name, _| used_schema_names.contains(name));
+  }
+
+  write_document(&node, arguments.format, arguments.output.as_ref())?;
+
+  Ok(report_issues(&written_document.issues))
+}
@@ -0,0 +1,148 @@
+use super::{new_context, Outcome};
+use crate::error::CliError;
+use crate::location::parse_location;
+use skiffa_core::error::Error;
+use skiffa_core::utils::NodeLocation;
+
+#[derive(clap::Args)]
+pub struct ValidateArguments {
+  /// Files or urls of the documents to validate
+  #[arg(required = true)]
+  locations: Vec<String>,
+}
+
+/// Every document is loaded on it's own, together with the documents it
+/// references. Problems are reported per document.
+///
+pub async fn validate(arguments: ValidateArguments) -> Result<Outcome, CliError> {
+  let mut outcome = Outcome::Success;
+
+  for argument in &arguments.locations {
+    let location = parse_location(argument)?;
+    let problems = validate_document(&location).await;
+    if problems.is_empty() {
+      println!("{}: valid", argument);
+      continue;
+    }
+
+    outcome = Outcome::Findings;
+    for problem in problems {
+      println!("{}: {}", argument, problem);
+    }
+  }
+
+  Ok(outcome)
+}
+
+async fn validate_document(location: &NodeLocation) -> Vec<String> {
+  let context = new_context();
+  if let Err(error) = context.load_from_location(location).await {
+    return vec![format!("could not load document ({})", error)];
+  }
+
+  let mut problems = Vec::new();
+  if let Err(error) = context.load_referenced_nodes(location).await {
+    problems.push(format!("could not load a referenced document ({})", error));
+  }
+
+  for document_location in context.get_document_locations() {
+    match context.get_referenced_locations(&document_location) {
+      Ok(referenced_locations) => {
+        for referenced_location in referenced_locations {
+          if context.get_node(&referenced_location).is_none() {
+            problems.push(format!(
+              "{} references {}, which does not exist",
+              document_location, referenced_location
+            ));
+          }
+        }
+      }
+      Err(error) => problems.push(format!(
+        "{} has invalid references ({})",
+        document_location, error
+      )),
+    }
+
+    let results = [
+      context.get_api_model(&document_location).map(drop),
+      context.get_async_api_model(&document_location).map(drop),
+    ];
+    for result in results {
+      match result {
+        Ok(()) | Err(Error::NotFound) | Err(Error::NotImplemented) => {}
+        Err(error) => problems.push(format!(
+          "{} does not describe a valid api ({})",
+          document_location, error
+        )),
+      }
+    }
+  }
+
+  problems
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::commands::write_test_files;
+
+  #[tokio::test]
+  async fn test_validate() {
+    let directory = write_test_files(
+      "validate",
+      &[
+        (
+          "api.yaml",
+          r#"
+openapi: 3.0.3
+paths:
+  /pets:
+    get:
+      operationId: list-pets
+      responses:
+        "200":
+          description: Ok
+          content:
+            application/json:
+              schema:
+                $ref: "./schemas.yaml#/pet"
+"#,
+        ),
+        ("schemas.yaml", "pet:\n  type: object\n"),
+        (
+          "broken.yaml",
+          r#"
+openapi: 3.0.3
+paths:
+  /pets:
+    $ref: "./missing.yaml#/pets"
+"#,
+        ),
+        (
+          "swagger.yaml",
+          r#"
+swagger: "2.0"
+definitions:
+  pet:
+    $ref: "./schemas.yaml#/pet"
+"#,
+        ),
+      ],
+    );
+    let location = |file_name: &str| directory.join(file_name).to_str().unwrap().to_owned();
+
+    let outcome = validate(ValidateArguments {
+      locations: vec![location("api.yaml"), location("swagger.yaml")],
+    })
+    .await
+    .unwrap();
+    assert_eq!(outcome, Outcome::Success);
+
+    let outcome = validate(ValidateArguments {
+      locations: vec![location("api.yaml"), location("broken.yaml")],
+    })
+    .await
+    .unwrap();
+    assert_eq!(outcome, Outcome::Findings);
+  }
+}
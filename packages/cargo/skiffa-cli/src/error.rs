@@ -0,0 +1,34 @@
+use skiffa_core::error::Error;
+use skiffa_core::utils::NodeLocation;
+use std::fmt::Display;
+
+#[derive(Debug)]
+pub enum CliError {
+  InvalidLocation(String),
+  InvalidArguments(String),
+  Document(NodeLocation, Error),
+  NoApiModel(NodeLocation),
+  Output(std::io::Error),
+  SerializationError,
+}
+
+impl std::error::Error for CliError {}
+
+impl Display for CliError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::InvalidLocation(argument) => write!(f, "{} is not a valid location", argument),
+      Self::InvalidArguments(message) => write!(f, "{}", message),
+      Self::Document(location, error) => write!(f, "{}: {}", location, error),
+      Self::NoApiModel(location) => write!(f, "{} does not describe an http api", location),
+      Self::Output(error) => write!(f, "could not write output: {}", error),
+      Self::SerializationError => write!(f, "could not serialize output"),
+    }
+  }
+}
+
+impl From<std::io::Error> for CliError {
+  fn from(value: std::io::Error) -> Self {
+    Self::Output(value)
+  }
+}
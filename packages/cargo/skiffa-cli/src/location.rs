@@ -0,0 +1,46 @@
+use crate::error::CliError;
+use skiffa_core::utils::NodeLocation;
+use std::path::Path;
+
+/// Parse a command line argument as the location of a document. Arguments
+/// with a scheme (`https://`) are urls, everything else is a path on the file
+/// system. Relative paths are made absolute so references resolve against the
+/// directory of the document.
+///
+pub fn parse_location(argument: &str) -> Result<NodeLocation, CliError> {
+  let invalid_location = || CliError::InvalidLocation(argument.to_owned());
+
+  let location = if argument.contains("://") {
+    argument.to_owned()
+  } else {
+    let path = std::path::absolute(Path::new(argument)).map_err(|_error| invalid_location())?;
+    path.to_str().ok_or_else(invalid_location)?.to_owned()
+  };
+
+  let location: NodeLocation = location.parse().map_err(|_error| invalid_location())?;
+  Ok(location.set_root())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse_location() {
+    let location = parse_location("https://example.com/api.yaml").unwrap();
+    assert_eq!(location.to_string(), "https://example.com/api.yaml#");
+
+    let location = parse_location("api.yaml").unwrap();
+    assert!(location.is_file());
+    assert_eq!(location.get_path().last().unwrap(), "api.yaml");
+    assert_eq!(
+      location.to_fetch_string(),
+      std::env::current_dir()
+        .unwrap()
+        .join("api.yaml")
+        .to_str()
+        .unwrap()
+        .replace('\\', "/")
+    );
+  }
+}
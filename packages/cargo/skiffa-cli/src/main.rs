@@ -0,0 +1,87 @@
+mod commands;
+mod error;
+mod location;
+
+use clap::{Parser, Subcommand};
+use commands::Outcome;
+use std::process::ExitCode;
+
+/// Exit code when the command succeeded and found nothing to report
+const EXIT_SUCCESS: u8 = 0;
+/// Exit code when the command ran, but found problems (invalid documents, lint
+/// findings or breaking changes)
+const EXIT_FINDINGS: u8 = 1;
+/// Exit code when the command could not run, this is also the exit code for
+/// invalid arguments
+const EXIT_FAILURE: u8 = 2;
+
+/// Validate, lint, bundle and compare OpenApi, Swagger and AsyncApi documents.
+/// Documents are read from files or urls.
+///
+#[derive(Parser)]
+#[command(name = "skiffa", version, about)]
+struct Cli {
+  #[command(subcommand)]
+  command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+  /// Load documents and everything they reference, report documents that can
+  /// not be loaded
+  Validate(commands::ValidateArguments),
+  /// Report operations and paths that do not follow common conventions
+  Lint(commands::LintArguments),
+  /// Convert a Swagger 2.0 or OpenApi 3.0 document to OpenApi 3.0 or 3.1
+  Convert(commands::ConvertArguments),
+  /// Include every referenced document in one document
+  Bundle(commands::DocumentOutputArguments),
+  /// Replace every reference with the node it references
+  Dereference(commands::DocumentOutputArguments),
+  /// Compare two versions of an api, fail on breaking changes
+  Diff(commands::DiffArguments),
+  /// Write the api model as json (the intermediate representation)
+  DumpModel(commands::DumpModelArguments),
+  /// List the schemas in a document and the documents it references
+  ListSchemas(commands::ListSchemasArguments),
+  /// Merge the apis of documents into one OpenApi 3.0 or 3.1 document, only
+  /// the schemas of the first document are copied into it
+  Merge(commands::MergeArguments),
+  /// Write a document again from its api model, as OpenApi 3.0 or 3.1
+  Normalize(commands::NormalizeArguments),
+  /// Write a document with only the operations that pass a filter
+  Slice(commands::SliceArguments),
+}
+
+fn main() -> ExitCode {
+  let cli = Cli::parse();
+
+  let runtime = tokio::runtime::Builder::new_current_thread()
+    .enable_all()
+    .build()
+    .unwrap();
+  let result = runtime.block_on(async {
+    match cli.command {
+      Command::Validate(arguments) => commands::validate(arguments).await,
+      Command::Lint(arguments) => commands::lint(arguments).await,
+      Command::Convert(arguments) => commands::convert(arguments).await,
+      Command::Bundle(arguments) => commands::bundle(arguments).await,
+      Command::Dereference(arguments) => commands::dereference(arguments).await,
+      Command::Diff(arguments) => commands::diff(arguments).await,
+      Command::DumpModel(arguments) => commands::dump_model(arguments).await,
+      Command::ListSchemas(arguments) => commands::list_schemas(arguments).await,
+      Command::Merge(arguments) => commands::merge(arguments).await,
+      Command::Normalize(arguments) => commands::normalize(arguments).await,
+      Command::Slice(arguments) => commands::slice(arguments).await,
+    }
+  });
+
+  match result {
+    Ok(Outcome::Success) => ExitCode::from(EXIT_SUCCESS),
+    Ok(Outcome::Findings) => ExitCode::from(EXIT_FINDINGS),
+    Err(error) => {
+      eprintln!("error: {}", error);
+      ExitCode::from(EXIT_FAILURE)
+    }
+  }
+}
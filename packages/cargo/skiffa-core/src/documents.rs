@@ -1,13 +1,22 @@
+mod bundle;
+mod common;
 mod context;
 mod error;
 mod interface;
+mod oas3;
 mod r#type;
+mod writer;
 
+pub mod asyncapi2;
+pub mod asyncapi3;
+pub mod jsonschema;
 pub mod oas30;
 pub mod oas31;
 pub mod swagger2;
 
+pub use bundle::*;
 pub use context::*;
 pub use error::*;
 pub use interface::*;
 pub use r#type::*;
+pub use writer::*;
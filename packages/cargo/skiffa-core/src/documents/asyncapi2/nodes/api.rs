@@ -0,0 +1,75 @@
+use super::*;
+use std::collections::BTreeMap;
+
+#[derive(Clone)]
+pub struct Api(serde_json::Value);
+
+impl Api {
+  pub fn default_content_type(&self) -> Option<&str> {
+    self.0.as_object()?.get("defaultContentType")?.as_str()
+  }
+
+  pub fn channels(&self) -> Option<BTreeMap<Vec<String>, NodeOrReference<Channel>>> {
+    let member = "channels";
+    Some(
+      self
+        .0
+        .as_object()?
+        .get(member)?
+        .as_object()?
+        .into_iter()
+        .map(|(key, node)| (vec![member.to_owned(), key.clone()], node.clone().into()))
+        .collect(),
+    )
+  }
+
+  pub fn schemas(&self) -> Option<BTreeMap<Vec<String>, Schema>> {
+    let member = "components";
+    let member_1 = "schemas";
+    Some(
+      self
+        .0
+        .as_object()?
+        .get(member)?
+        .as_object()?
+        .get(member_1)?
+        .as_object()?
+        .into_iter()
+        .map(|(key, node)| {
+          (
+            vec![member.to_owned(), member_1.to_owned(), key.clone()],
+            node.clone().into(),
+          )
+        })
+        .collect(),
+    )
+  }
+
+  pub fn messages(&self) -> Option<BTreeMap<Vec<String>, NodeOrReference<Message>>> {
+    let member = "components";
+    let member_1 = "messages";
+    Some(
+      self
+        .0
+        .as_object()?
+        .get(member)?
+        .as_object()?
+        .get(member_1)?
+        .as_object()?
+        .into_iter()
+        .map(|(key, node)| {
+          (
+            vec![member.to_owned(), member_1.to_owned(), key.clone()],
+            node.clone().into(),
+          )
+        })
+        .collect(),
+    )
+  }
+}
+
+impl From<serde_json::Value> for Api {
+  fn from(value: serde_json::Value) -> Self {
+    Self(value)
+  }
+}
@@ -0,0 +1,38 @@
+use super::*;
+use std::collections::BTreeMap;
+
+#[derive(Clone)]
+pub struct Channel(serde_json::Value);
+
+impl Channel {
+  pub fn description(&self) -> Option<&str> {
+    self.0.as_object()?.get("description")?.as_str()
+  }
+
+  /// The `publish` and `subscribe` operations of the channel, keyed by their
+  /// pointer.
+  ///
+  pub fn operations(&self) -> Option<BTreeMap<Vec<String>, Operation>> {
+    Some(
+      self
+        .0
+        .as_object()?
+        .iter()
+        .filter(|(key, _node)| *key == "publish" || *key == "subscribe")
+        .map(|(key, node)| (vec![key.clone()], node.clone().into()))
+        .collect(),
+    )
+  }
+
+  pub fn bindings(&self) -> Option<(Vec<String>, NodeOrReference<Bindings>)> {
+    let member = "bindings";
+    let node = self.0.as_object()?.get(member)?;
+    Some((vec![member.to_owned()], node.clone().into()))
+  }
+}
+
+impl From<serde_json::Value> for Channel {
+  fn from(value: serde_json::Value) -> Self {
+    Self(value)
+  }
+}
@@ -0,0 +1,69 @@
+use super::*;
+
+/// Schema formats of which the schemas are json schemas, other formats (like
+/// avro) are not schemas we can work with.
+///
+const JSON_SCHEMA_FORMATS: [&str; 4] = [
+  "application/vnd.aai.asyncapi",
+  "application/schema+json",
+  "application/schema+yaml",
+  "application/vnd.oai.openapi",
+];
+
+#[derive(Clone)]
+pub struct Message(serde_json::Value);
+
+impl Message {
+  pub fn name(&self) -> Option<&str> {
+    self.0.as_object()?.get("name")?.as_str()
+  }
+
+  pub fn title(&self) -> Option<&str> {
+    self.0.as_object()?.get("title")?.as_str()
+  }
+
+  pub fn content_type(&self) -> Option<&str> {
+    self.0.as_object()?.get("contentType")?.as_str()
+  }
+
+  pub fn schema_format(&self) -> Option<&str> {
+    self.0.as_object()?.get("schemaFormat")?.as_str()
+  }
+
+  pub fn payload_pointer(&self) -> Option<Vec<String>> {
+    if let Some(schema_format) = self.schema_format() {
+      if !JSON_SCHEMA_FORMATS
+        .iter()
+        .any(|prefix| schema_format.starts_with(prefix))
+      {
+        return None;
+      }
+    }
+
+    self
+      .0
+      .as_object()?
+      .get("payload")
+      .map(|_value| vec!["payload".to_owned()])
+  }
+
+  pub fn headers_pointer(&self) -> Option<Vec<String>> {
+    self
+      .0
+      .as_object()?
+      .get("headers")
+      .map(|_value| vec!["headers".to_owned()])
+  }
+
+  pub fn bindings(&self) -> Option<(Vec<String>, NodeOrReference<Bindings>)> {
+    let member = "bindings";
+    let node = self.0.as_object()?.get(member)?;
+    Some((vec![member.to_owned()], node.clone().into()))
+  }
+}
+
+impl From<serde_json::Value> for Message {
+  fn from(value: serde_json::Value) -> Self {
+    Self(value)
+  }
+}
@@ -0,0 +1,10 @@
+mod api;
+mod channel;
+mod message;
+mod operation;
+
+pub use crate::documents::common::nodes::*;
+pub use api::*;
+pub use channel::*;
+pub use message::*;
+pub use operation::*;
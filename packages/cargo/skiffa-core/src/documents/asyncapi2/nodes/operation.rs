@@ -0,0 +1,56 @@
+use super::*;
+
+#[derive(Clone)]
+pub struct Operation(serde_json::Value);
+
+impl Operation {
+  pub fn name(&self) -> Option<&str> {
+    self.0.as_object()?.get("operationId")?.as_str()
+  }
+
+  pub fn summary(&self) -> Option<&str> {
+    self.0.as_object()?.get("summary")?.as_str()
+  }
+
+  pub fn description(&self) -> Option<&str> {
+    self.0.as_object()?.get("description")?.as_str()
+  }
+
+  /// The message of the operation, or every message in `oneOf` if the
+  /// operation has more than one message. Messages are in the order of the
+  /// `oneOf` array.
+  ///
+  pub fn messages(&self) -> Option<Vec<(Vec<String>, NodeOrReference<Message>)>> {
+    let member = "message";
+    let member_1 = "oneOf";
+    let node = self.0.as_object()?.get(member)?;
+    if let Some(nodes) = node.get(member_1).and_then(|node| node.as_array()) {
+      return Some(
+        nodes
+          .iter()
+          .enumerate()
+          .map(|(key, node)| {
+            (
+              vec![member.to_owned(), member_1.to_owned(), key.to_string()],
+              node.clone().into(),
+            )
+          })
+          .collect(),
+      );
+    }
+
+    Some(vec![(vec![member.to_owned()], node.clone().into())])
+  }
+
+  pub fn bindings(&self) -> Option<(Vec<String>, NodeOrReference<Bindings>)> {
+    let member = "bindings";
+    let node = self.0.as_object()?.get(member)?;
+    Some((vec![member.to_owned()], node.clone().into()))
+  }
+}
+
+impl From<serde_json::Value> for Operation {
+  fn from(value: serde_json::Value) -> Self {
+    Self(value)
+  }
+}
@@ -0,0 +1,4 @@
+mod document;
+mod nodes;
+
+pub use document::*;
@@ -0,0 +1,431 @@
+use super::nodes;
+use crate::documents::common::{
+  dereference, get_node, get_referenced_locations_from_reference_entries,
+  get_schema_identity_location, get_schema_locations_from_schema,
+  get_schema_locations_from_schema_location, get_sub_locations_from_node_entries,
+};
+use crate::models::OperationAction;
+use crate::utils::sync;
+use crate::utils::NodeLocation;
+use crate::{
+  documents::{DocumentContext, DocumentError, DocumentInterface, SchemaLocation},
+  models,
+};
+use std::iter;
+
+pub struct Document {
+  context: sync::Weak<DocumentContext>,
+  retrieval_location: NodeLocation,
+}
+
+impl Document {
+  pub fn new(context: sync::Weak<DocumentContext>, retrieval_location: NodeLocation) -> Self {
+    Self {
+      context,
+      retrieval_location,
+    }
+  }
+}
+
+impl Document {
+  /// The canonical uri of the schema at the location, this is the location
+  /// itself, unless the schema has an `$id`.
+  ///
+  fn get_schema_identity_location(
+    &self,
+    location: &NodeLocation,
+  ) -> Result<NodeLocation, DocumentError> {
+    get_schema_identity_location(&self.context, location, location.clone())
+  }
+}
+
+impl DocumentInterface for Document {
+  fn get_default_schema_id(&self) -> String {
+    "http://json-schema.org/draft-07/schema#".to_owned()
+  }
+
+  fn get_document_location(&self) -> NodeLocation {
+    self.retrieval_location.clone()
+  }
+
+  fn get_identity_location(&self) -> Result<Option<NodeLocation>, DocumentError> {
+    Ok(None)
+  }
+
+  fn get_api_model(&self) -> Result<sync::Shared<models::Api>, DocumentError> {
+    Err(DocumentError::ModelNotSupported)
+  }
+
+  fn get_async_api_model(&self) -> Result<sync::Shared<models::AsyncApi>, DocumentError> {
+    let api_location = self.retrieval_location.clone();
+    let api_node = get_node(&self.context, &api_location)?;
+
+    self
+      .make_api_model(api_location, api_node)
+      .map(sync::Shared::new)
+  }
+
+  fn get_referenced_locations(&self) -> Result<Vec<NodeLocation>, DocumentError> {
+    let api_location = self.retrieval_location.clone();
+    let api_node = get_node(&self.context, &api_location)?;
+
+    self
+      .get_referenced_locations_from_api(api_location, api_node)
+      .collect()
+  }
+
+  fn get_schema_locations(&self) -> Result<Vec<SchemaLocation>, DocumentError> {
+    let api_location = self.retrieval_location.clone();
+    let api_node = get_node(&self.context, &api_location)?;
+
+    self
+      .get_schema_locations_from_api(api_location, api_node)
+      .collect()
+  }
+}
+
+impl Document {
+  fn make_api_model(
+    &self,
+    api_location: NodeLocation,
+    api_node: nodes::Api,
+  ) -> Result<models::AsyncApi, DocumentError> {
+    let channels = api_node
+      .channels()
+      .into_iter()
+      .flatten()
+      .map(|(pointer, node)| {
+        let name = pointer.last().unwrap().clone();
+        let location = api_location.push_pointer(pointer);
+        let (location, node) = dereference(&self.context, &location, node)?;
+        self
+          .make_channel_model(api_node.clone(), location, node, name)
+          .map(sync::Shared::new)
+      })
+      .collect::<Result<_, DocumentError>>()?;
+
+    let operations = api_node
+      .operations()
+      .into_iter()
+      .flatten()
+      .map(|(pointer, node)| {
+        let name = pointer.last().unwrap().clone();
+        let location = api_location.push_pointer(pointer);
+        let (location, node) = dereference(&self.context, &location, node)?;
+        self
+          .make_operation_model(api_node.clone(), location, node, name)
+          .map(sync::Shared::new)
+      })
+      .collect::<Result<_, DocumentError>>()?;
+
+    Ok(models::AsyncApi {
+      location: api_location.clone(),
+      channels,
+      operations,
+    })
+  }
+
+  fn make_channel_model(
+    &self,
+    api_node: nodes::Api,
+    channel_location: NodeLocation,
+    channel_node: nodes::Channel,
+    name: String,
+  ) -> Result<models::Channel, DocumentError> {
+    let messages = self.make_message_models(
+      api_node,
+      &channel_location,
+      channel_node.messages().into_iter().flatten(),
+    )?;
+    let bindings = self.make_binding_models(&channel_location, channel_node.bindings())?;
+
+    Ok(models::Channel {
+      location: channel_location.clone(),
+      name,
+      address: channel_node.address().map(Into::into),
+      description: channel_node.description().map(Into::into),
+      messages,
+      bindings,
+    })
+  }
+
+  fn make_operation_model(
+    &self,
+    api_node: nodes::Api,
+    operation_location: NodeLocation,
+    operation_node: nodes::Operation,
+    name: String,
+  ) -> Result<models::ChannelOperation, DocumentError> {
+    let action = match operation_node.action() {
+      Some("send") => OperationAction::Send,
+      Some("receive") => OperationAction::Receive,
+      _ => return Err(DocumentError::ParseOperationActionFailed),
+    };
+
+    let (pointer, channel_node) = operation_node
+      .channel()
+      .ok_or(DocumentError::NodeNotFound)?;
+    let (channel_location, channel_node) = dereference(
+      &self.context,
+      &operation_location.push_pointer(pointer),
+      channel_node,
+    )?;
+    let channel_name = channel_location
+      .get_pointer()
+      .and_then(|pointer| pointer.last().cloned())
+      .unwrap_or_default();
+
+    // without messages, all messages of the channel are used
+    let messages = match operation_node.messages() {
+      Some(messages) => {
+        self.make_message_models(api_node, &operation_location, messages.into_iter())?
+      }
+      None => self.make_message_models(
+        api_node,
+        &channel_location,
+        channel_node.messages().into_iter().flatten(),
+      )?,
+    };
+    let bindings = self.make_binding_models(&operation_location, operation_node.bindings())?;
+
+    Ok(models::ChannelOperation {
+      location: operation_location.clone(),
+      name: Some(name),
+      action,
+      channel_name,
+      summary: operation_node.summary().map(Into::into),
+      description: operation_node.description().map(Into::into),
+      messages,
+      bindings,
+    })
+  }
+
+  fn make_message_models(
+    &self,
+    api_node: nodes::Api,
+    location: &NodeLocation,
+    entries: impl Iterator<Item = (Vec<String>, nodes::NodeOrReference<nodes::Message>)>,
+  ) -> Result<Vec<sync::Shared<models::Message>>, DocumentError> {
+    entries
+      .map(|(pointer, node)| {
+        let location = location.push_pointer(pointer);
+        let (location, node) = dereference(&self.context, &location, node)?;
+        self
+          .make_message_model(api_node.clone(), location, node)
+          .map(sync::Shared::new)
+      })
+      .collect()
+  }
+
+  fn make_message_model(
+    &self,
+    api_node: nodes::Api,
+    message_location: NodeLocation,
+    message_node: nodes::Message,
+  ) -> Result<models::Message, DocumentError> {
+    let payload_schema_id = message_node
+      .payload_pointer()
+      .map(|pointer| self.get_schema_identity_location(&message_location.push_pointer(pointer)))
+      .transpose()?;
+    let headers_schema_id = message_node
+      .headers_pointer()
+      .map(|pointer| self.get_schema_identity_location(&message_location.push_pointer(pointer)))
+      .transpose()?;
+    let bindings = self.make_binding_models(&message_location, message_node.bindings())?;
+
+    Ok(models::Message {
+      location: message_location.clone(),
+      name: message_node.name().map(Into::into),
+      title: message_node.title().map(Into::into),
+      content_type: message_node
+        .content_type()
+        .or(api_node.default_content_type())
+        .map(Into::into),
+      payload_schema_id,
+      headers_schema_id,
+      bindings,
+    })
+  }
+
+  fn make_binding_models(
+    &self,
+    location: &NodeLocation,
+    entry: Option<(Vec<String>, nodes::NodeOrReference<nodes::Bindings>)>,
+  ) -> Result<Vec<sync::Shared<models::Binding>>, DocumentError> {
+    let Some((pointer, node)) = entry else {
+      return Ok(Vec::new());
+    };
+    let location = location.push_pointer(pointer);
+    let (location, node) = dereference(&self.context, &location, node)?;
+
+    Ok(
+      node
+        .protocols()
+        .into_iter()
+        .flatten()
+        .map(|(pointer, protocol)| {
+          sync::Shared::new(models::Binding {
+            location: location.push_pointer(pointer),
+            protocol,
+          })
+        })
+        .collect(),
+    )
+  }
+}
+
+impl Document {
+  fn get_referenced_locations_from_api(
+    &self,
+    location: NodeLocation,
+    node: nodes::Api,
+  ) -> impl Iterator<Item = Result<NodeLocation, DocumentError>> + '_ {
+    iter::empty()
+      .chain(get_referenced_locations_from_reference_entries(
+        &self.context,
+        location.clone(),
+        node.channels().into_iter().flatten(),
+      ))
+      .chain(get_referenced_locations_from_reference_entries(
+        &self.context,
+        location.clone(),
+        node.operations().into_iter().flatten(),
+      ))
+      .chain(get_referenced_locations_from_reference_entries(
+        &self.context,
+        location.clone(),
+        node.messages().into_iter().flatten(),
+      ))
+      .chain(get_sub_locations_from_node_entries(
+        location.clone(),
+        node
+          .channels()
+          .into_iter()
+          .flatten()
+          .filter_map(|(pointer, node)| node.into_node().map(|node| (pointer, node))),
+        |location, node| self.get_referenced_locations_from_channel(location, node),
+      ))
+      .chain(get_sub_locations_from_node_entries(
+        location.clone(),
+        node
+          .operations()
+          .into_iter()
+          .flatten()
+          .filter_map(|(pointer, node)| node.into_node().map(|node| (pointer, node))),
+        |location, node| self.get_referenced_locations_from_operation(location, node),
+      ))
+  }
+
+  fn get_referenced_locations_from_channel(
+    &self,
+    location: NodeLocation,
+    node: nodes::Channel,
+  ) -> impl Iterator<Item = Result<NodeLocation, DocumentError>> + '_ {
+    iter::empty()
+      .chain(get_referenced_locations_from_reference_entries(
+        &self.context,
+        location.clone(),
+        node.bindings().into_iter(),
+      ))
+      .chain(get_referenced_locations_from_reference_entries(
+        &self.context,
+        location.clone(),
+        node.messages().into_iter().flatten(),
+      ))
+  }
+
+  fn get_referenced_locations_from_operation(
+    &self,
+    location: NodeLocation,
+    node: nodes::Operation,
+  ) -> impl Iterator<Item = Result<NodeLocation, DocumentError>> + '_ {
+    iter::empty()
+      .chain(get_referenced_locations_from_reference_entries(
+        &self.context,
+        location.clone(),
+        node.channel().into_iter(),
+      ))
+      .chain(get_referenced_locations_from_reference_entries(
+        &self.context,
+        location.clone(),
+        node.bindings().into_iter(),
+      ))
+      .chain(get_referenced_locations_from_reference_entries(
+        &self.context,
+        location.clone(),
+        node.messages().into_iter().flatten(),
+      ))
+  }
+}
+
+impl Document {
+  fn get_schema_locations_from_api(
+    &self,
+    location: NodeLocation,
+    node: nodes::Api,
+  ) -> impl Iterator<Item = Result<SchemaLocation, DocumentError>> + '_ {
+    iter::empty()
+      .chain(get_sub_locations_from_node_entries(
+        location.clone(),
+        node.schemas().into_iter().flatten(),
+        |location, node| {
+          get_schema_locations_from_schema(location.clone(), location, node).into_iter()
+        },
+      ))
+      .chain(get_sub_locations_from_node_entries(
+        location.clone(),
+        node
+          .messages()
+          .into_iter()
+          .flatten()
+          .filter_map(|(pointer, node)| node.into_node().map(|node| (pointer, node))),
+        |location, node| self.get_schema_locations_from_message(location, node),
+      ))
+      .chain(get_sub_locations_from_node_entries(
+        location.clone(),
+        node
+          .channels()
+          .into_iter()
+          .flatten()
+          .filter_map(|(pointer, node)| node.into_node().map(|node| (pointer, node))),
+        |location, node| self.get_schema_locations_from_channel(location, node),
+      ))
+  }
+
+  fn get_schema_locations_from_channel(
+    &self,
+    location: NodeLocation,
+    node: nodes::Channel,
+  ) -> impl Iterator<Item = Result<SchemaLocation, DocumentError>> + '_ {
+    get_sub_locations_from_node_entries(
+      location,
+      node
+        .messages()
+        .into_iter()
+        .flatten()
+        .filter_map(|(pointer, node)| node.into_node().map(|node| (pointer, node))),
+      |location, node| self.get_schema_locations_from_message(location, node),
+    )
+  }
+
+  fn get_schema_locations_from_message(
+    &self,
+    location: NodeLocation,
+    node: nodes::Message,
+  ) -> std::vec::IntoIter<Result<SchemaLocation, DocumentError>> {
+    iter::empty()
+      .chain(node.payload_pointer())
+      .chain(node.headers_pointer())
+      .map(|pointer| location.push_pointer(pointer))
+      .flat_map(|location| self.get_schema_locations_from_schema_location(location))
+      .collect::<Vec<_>>()
+      .into_iter()
+  }
+
+  fn get_schema_locations_from_schema_location(
+    &self,
+    location: NodeLocation,
+  ) -> Vec<Result<SchemaLocation, DocumentError>> {
+    get_schema_locations_from_schema_location(&self.context, location.clone(), location)
+  }
+}
@@ -0,0 +1,41 @@
+use super::*;
+use std::collections::BTreeMap;
+
+#[derive(Clone)]
+pub struct Channel(serde_json::Value);
+
+impl Channel {
+  pub fn address(&self) -> Option<&str> {
+    self.0.as_object()?.get("address")?.as_str()
+  }
+
+  pub fn description(&self) -> Option<&str> {
+    self.0.as_object()?.get("description")?.as_str()
+  }
+
+  pub fn messages(&self) -> Option<BTreeMap<Vec<String>, NodeOrReference<Message>>> {
+    let member = "messages";
+    Some(
+      self
+        .0
+        .as_object()?
+        .get(member)?
+        .as_object()?
+        .into_iter()
+        .map(|(key, node)| (vec![member.to_owned(), key.clone()], node.clone().into()))
+        .collect(),
+    )
+  }
+
+  pub fn bindings(&self) -> Option<(Vec<String>, NodeOrReference<Bindings>)> {
+    let member = "bindings";
+    let node = self.0.as_object()?.get(member)?;
+    Some((vec![member.to_owned()], node.clone().into()))
+  }
+}
+
+impl From<serde_json::Value> for Channel {
+  fn from(value: serde_json::Value) -> Self {
+    Self(value)
+  }
+}
@@ -0,0 +1,70 @@
+use super::*;
+
+/// Schema formats of which the schemas are json schemas, other formats (like
+/// avro) are not schemas we can work with.
+///
+const JSON_SCHEMA_FORMATS: [&str; 4] = [
+  "application/vnd.aai.asyncapi",
+  "application/schema+json",
+  "application/schema+yaml",
+  "application/vnd.oai.openapi",
+];
+
+#[derive(Clone)]
+pub struct Message(serde_json::Value);
+
+impl Message {
+  pub fn name(&self) -> Option<&str> {
+    self.0.as_object()?.get("name")?.as_str()
+  }
+
+  pub fn title(&self) -> Option<&str> {
+    self.0.as_object()?.get("title")?.as_str()
+  }
+
+  pub fn content_type(&self) -> Option<&str> {
+    self.0.as_object()?.get("contentType")?.as_str()
+  }
+
+  pub fn payload_pointer(&self) -> Option<Vec<String>> {
+    Self::get_schema_pointer(&self.0, "payload")
+  }
+
+  pub fn headers_pointer(&self) -> Option<Vec<String>> {
+    Self::get_schema_pointer(&self.0, "headers")
+  }
+
+  pub fn bindings(&self) -> Option<(Vec<String>, NodeOrReference<Bindings>)> {
+    let member = "bindings";
+    let node = self.0.as_object()?.get(member)?;
+    Some((vec![member.to_owned()], node.clone().into()))
+  }
+
+  /// The schema may be a multi format schema, then the schema is in the
+  /// `schema` member, if the format is a json schema format.
+  ///
+  fn get_schema_pointer(node: &serde_json::Value, member: &str) -> Option<Vec<String>> {
+    let node = node.as_object()?.get(member)?;
+    let Some(schema_format) = node.get("schemaFormat") else {
+      return Some(vec![member.to_owned()]);
+    };
+
+    let schema_format = schema_format.as_str()?;
+    if !JSON_SCHEMA_FORMATS
+      .iter()
+      .any(|prefix| schema_format.starts_with(prefix))
+    {
+      return None;
+    }
+
+    node
+      .get("schema")
+      .map(|_value| vec![member.to_owned(), "schema".to_owned()])
+  }
+}
+
+impl From<serde_json::Value> for Message {
+  fn from(value: serde_json::Value) -> Self {
+    Self(value)
+  }
+}
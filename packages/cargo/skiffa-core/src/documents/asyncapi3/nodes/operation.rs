@@ -0,0 +1,60 @@
+use super::*;
+
+#[derive(Clone)]
+pub struct Operation(serde_json::Value);
+
+impl Operation {
+  pub fn action(&self) -> Option<&str> {
+    self.0.as_object()?.get("action")?.as_str()
+  }
+
+  pub fn summary(&self) -> Option<&str> {
+    self.0.as_object()?.get("summary")?.as_str()
+  }
+
+  pub fn description(&self) -> Option<&str> {
+    self.0.as_object()?.get("description")?.as_str()
+  }
+
+  pub fn channel(&self) -> Option<(Vec<String>, NodeOrReference<Channel>)> {
+    let member = "channel";
+    let node = self.0.as_object()?.get(member)?;
+    Some((vec![member.to_owned()], node.clone().into()))
+  }
+
+  /// The messages of the operation, these are references to messages of the
+  /// channel. If there are no messages, all messages of the channel apply.
+  /// Messages are in the order of the array.
+  ///
+  pub fn messages(&self) -> Option<Vec<(Vec<String>, NodeOrReference<Message>)>> {
+    let member = "messages";
+    Some(
+      self
+        .0
+        .as_object()?
+        .get(member)?
+        .as_array()?
+        .iter()
+        .enumerate()
+        .map(|(key, node)| {
+          (
+            vec![member.to_owned(), key.to_string()],
+            node.clone().into(),
+          )
+        })
+        .collect(),
+    )
+  }
+
+  pub fn bindings(&self) -> Option<(Vec<String>, NodeOrReference<Bindings>)> {
+    let member = "bindings";
+    let node = self.0.as_object()?.get(member)?;
+    Some((vec![member.to_owned()], node.clone().into()))
+  }
+}
+
+impl From<serde_json::Value> for Operation {
+  fn from(value: serde_json::Value) -> Self {
+    Self(value)
+  }
+}
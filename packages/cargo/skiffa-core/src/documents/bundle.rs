@@ -0,0 +1,289 @@
+use crate::error::Error;
+use crate::models::SchemaSource;
+use crate::utils::NodeLocation;
+use std::collections::BTreeMap;
+
+/// Bundle the document at the retrieval location, and the documents it
+/// references, into one document. The referenced documents should be in the
+/// node cache, see `DocumentContext::load_referenced_nodes`. Every reference to another document is
+/// replaced by the node it points to, references within the document are
+/// left alone. When a node from another document is referenced more than
+/// once, the node is included once and the other references point to it.
+///
+/// Nodes from other documents lose their `$id`, the references in them are
+/// rewritten to json pointers into the bundled document.
+///
+pub fn bundle_document(
+  source: &dyn SchemaSource,
+  retrieval_location: &NodeLocation,
+) -> Result<serde_json::Value, Error> {
+  Inliner::new(source, retrieval_location, false).inline_document()
+}
+
+/// Replace every reference in the document at the retrieval location by the
+/// node it points to. A reference to a node that is already being inlined
+/// (a recursive reference) can not be replaced, that reference will point to
+/// the node that is being inlined instead.
+///
+pub fn dereference_document(
+  source: &dyn SchemaSource,
+  retrieval_location: &NodeLocation,
+) -> Result<serde_json::Value, Error> {
+  Inliner::new(source, retrieval_location, true).inline_document()
+}
+
+struct Inliner<'a> {
+  source: &'a dyn SchemaSource,
+  document_location: NodeLocation,
+  /**
+   * also inline references to nodes in the document itself
+   */
+  dereference: bool,
+  /**
+   * pointers in the output of the nodes that are inlined, by the retrieval
+   * location of the node. When dereferencing these are only the nodes that
+   * are being inlined.
+   */
+  inlined_pointers: BTreeMap<NodeLocation, Vec<String>>,
+}
+
+impl<'a> Inliner<'a> {
+  fn new(
+    source: &'a dyn SchemaSource,
+    retrieval_location: &NodeLocation,
+    dereference: bool,
+  ) -> Self {
+    Self {
+      source,
+      document_location: retrieval_location.set_root(),
+      dereference,
+      inlined_pointers: Default::default(),
+    }
+  }
+
+  fn inline_document(mut self) -> Result<serde_json::Value, Error> {
+    let location = self.document_location.clone();
+    let node = self.source.get_node(&location).ok_or(Error::NotFound)?;
+    self.inline_node(&location, &node, &mut Vec::new())
+  }
+
+  fn inline_node(
+    &mut self,
+    location: &NodeLocation,
+    node: &serde_json::Value,
+    pointer: &mut Vec<String>,
+  ) -> Result<serde_json::Value, Error> {
+    match node {
+      serde_json::Value::Array(items) => {
+        let mut result = Vec::new();
+        for (index, item) in items.iter().enumerate() {
+          let member = index.to_string();
+          pointer.push(member.clone());
+          let item = self.inline_node(&location.push_pointer(vec![member]), item, pointer)?;
+          pointer.pop();
+          result.push(item);
+        }
+        Ok(serde_json::Value::Array(result))
+      }
+      serde_json::Value::Object(members) => {
+        let external = location.set_root() != self.document_location;
+        let mut result = serde_json::Map::new();
+        for (member, value) in members {
+          if member == "$ref" && value.is_string() {
+            continue;
+          }
+          if member == "$id" && external {
+            continue;
+          }
+          pointer.push(member.clone());
+          let value =
+            self.inline_node(&location.push_pointer(vec![member.clone()]), value, pointer)?;
+          pointer.pop();
+          result.insert(member.clone(), value);
+        }
+
+        match members.get("$ref").and_then(|value| value.as_str()) {
+          Some(reference) => self.inline_reference(location, reference, result, pointer),
+          None => Ok(serde_json::Value::Object(result)),
+        }
+      }
+      _ => Ok(node.clone()),
+    }
+  }
+
+  /// Inline the node the reference points to, the siblings of the reference
+  /// are added to the inlined node if it is an object.
+  ///
+  fn inline_reference(
+    &mut self,
+    location: &NodeLocation,
+    reference: &str,
+    mut siblings: serde_json::Map<String, serde_json::Value>,
+    pointer: &mut Vec<String>,
+  ) -> Result<serde_json::Value, Error> {
+    let external = location.set_root() != self.document_location;
+    let reference_location: NodeLocation = reference.parse()?;
+    let target_location = self.source.resolve_reference(location, &reference_location);
+    let internal_target = target_location.set_root() == self.document_location;
+
+    if internal_target && !self.dereference {
+      /*
+      references in the document itself stay as they are, references from
+      other documents to the document are rewritten to a pointer
+      */
+      let reference = match target_location.get_pointer() {
+        Some(target_pointer) if external => make_pointer_reference(target_pointer),
+        _ => reference.to_owned(),
+      };
+      siblings.insert("$ref".to_owned(), reference.into());
+      return Ok(serde_json::Value::Object(siblings));
+    }
+
+    if let Some(inlined_pointer) = self.inlined_pointers.get(&target_location) {
+      siblings.insert(
+        "$ref".to_owned(),
+        make_pointer_reference(inlined_pointer.clone()).into(),
+      );
+      return Ok(serde_json::Value::Object(siblings));
+    }
+
+    let target_node = self
+      .source
+      .get_node(&target_location)
+      .ok_or(Error::NotFound)?;
+    self
+      .inlined_pointers
+      .insert(target_location.clone(), pointer.clone());
+    let node = self.inline_node(&target_location, &target_node, pointer)?;
+    if self.dereference {
+      self.inlined_pointers.remove(&target_location);
+    }
+
+    match node {
+      serde_json::Value::Object(mut members) => {
+        members.append(&mut siblings);
+        Ok(serde_json::Value::Object(members))
+      }
+      node => Ok(node),
+    }
+  }
+}
+
+fn make_pointer_reference(pointer: Vec<String>) -> String {
+  let location: NodeLocation = "#".parse().unwrap();
+  location.set_pointer(pointer).to_string()
+}
+
+#[cfg(not(target_os = "unknown"))]
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::documents::DocumentContext;
+  use crate::utils::{sync, MemoryFetcher};
+  use serde_json::json;
+
+  async fn load(location: &NodeLocation) -> sync::Shared<DocumentContext> {
+    let context = sync::Shared::new(DocumentContext::new());
+    context.register_well_known_factories();
+    context.set_fetcher(MemoryFetcher::from_iter([
+      (
+        "http://example.com/api.yaml"
+          .parse::<NodeLocation>()
+          .unwrap(),
+        r##"
+openapi: 3.0.3
+paths:
+  /pets:
+    get:
+      operationId: list-pets
+      responses:
+        "200":
+          $ref: "#/components/responses/pets"
+    post:
+      operationId: add-pet
+      requestBody:
+        content:
+          application/json:
+            schema:
+              $ref: schemas.yaml#/pet
+components:
+  responses:
+    pets:
+      description: pets
+      content:
+        application/json:
+          schema:
+            type: array
+            items:
+              $ref: schemas.yaml#/pet
+"##,
+      ),
+      (
+        "http://example.com/schemas.yaml"
+          .parse::<NodeLocation>()
+          .unwrap(),
+        r##"
+pet:
+  type: object
+  properties:
+    name:
+      type: string
+    parent:
+      $ref: "#/pet"
+"##,
+      ),
+    ]));
+    context.load_from_location(location).await.unwrap();
+    context.load_referenced_nodes(location).await.unwrap();
+    context
+  }
+
+  #[tokio::test]
+  async fn test_bundle_document() {
+    let location = "http://example.com/api.yaml".parse().unwrap();
+    let context = load(&location).await;
+
+    let node = bundle_document(context.as_ref(), &location).unwrap();
+    // members are ordered, so the node is inlined in the components first
+    let pointer = "/components/responses/pets/content/application~1json/schema/items";
+    assert_eq!(
+      node.pointer(pointer).unwrap(),
+      &json!({
+        "type": "object",
+        "properties": {
+          "name": { "type": "string" },
+          "parent": { "$ref": format!("#{}", pointer) },
+        },
+      })
+    );
+    assert_eq!(
+      node
+        .pointer("/paths/~1pets/post/requestBody/content/application~1json/schema")
+        .unwrap(),
+      &json!({ "$ref": format!("#{}", pointer) })
+    );
+    assert_eq!(
+      node.pointer("/paths/~1pets/get/responses/200").unwrap(),
+      &json!({ "$ref": "#/components/responses/pets" })
+    );
+  }
+
+  #[tokio::test]
+  async fn test_dereference_document() {
+    let location = "http://example.com/api.yaml".parse().unwrap();
+    let context = load(&location).await;
+
+    let node = dereference_document(context.as_ref(), &location).unwrap();
+    let pointer = "/paths/~1pets/get/responses/200/content/application~1json/schema/items";
+    assert_eq!(
+      node.pointer(pointer).unwrap(),
+      &json!({
+        "type": "object",
+        "properties": {
+          "name": { "type": "string" },
+          "parent": { "$ref": format!("#{}", pointer) },
+        },
+      })
+    );
+  }
+}
@@ -0,0 +1,7 @@
+//! Nodes and reader functions that are shared by the readers of the different
+//! document types.
+
+pub mod nodes;
+mod reader;
+
+pub use reader::*;
@@ -0,0 +1,25 @@
+#[derive(Clone)]
+pub struct Bindings(serde_json::Value);
+
+impl Bindings {
+  /// The protocols of the bindings, keyed by their pointer relative to the
+  /// bindings object.
+  ///
+  pub fn protocols(&self) -> Option<Vec<(Vec<String>, String)>> {
+    Some(
+      self
+        .0
+        .as_object()?
+        .keys()
+        .filter(|key| !key.starts_with('$'))
+        .map(|key| (vec![key.clone()], key.clone()))
+        .collect(),
+    )
+  }
+}
+
+impl From<serde_json::Value> for Bindings {
+  fn from(value: serde_json::Value) -> Self {
+    Self(value)
+  }
+}
@@ -0,0 +1,9 @@
+mod bindings;
+mod node_or_reference;
+mod reference;
+mod schema;
+
+pub use bindings::*;
+pub use node_or_reference::*;
+pub use reference::*;
+pub use schema::*;
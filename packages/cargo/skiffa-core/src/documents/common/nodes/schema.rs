@@ -0,0 +1,94 @@
+use std::collections::BTreeMap;
+
+/// keywords that hold a single sub schema
+const SCHEMA_KEYWORDS: [&str; 12] = [
+  "additionalItems",
+  "additionalProperties",
+  "contains",
+  "contentSchema",
+  "else",
+  "if",
+  "items",
+  "not",
+  "propertyNames",
+  "then",
+  "unevaluatedItems",
+  "unevaluatedProperties",
+];
+
+/// keywords that hold an array of sub schemas
+const SCHEMA_ARRAY_KEYWORDS: [&str; 5] = ["allOf", "anyOf", "oneOf", "prefixItems", "items"];
+
+/// keywords that hold a map of sub schemas. The values of `dependencies`
+/// (draft 04 to 07) may also be an array of property names, those are not
+/// sub schemas.
+const SCHEMA_MAP_KEYWORDS: [&str; 6] = [
+  "$defs",
+  "definitions",
+  "dependencies",
+  "dependentSchemas",
+  "patternProperties",
+  "properties",
+];
+
+#[derive(Clone)]
+pub struct Schema(serde_json::Value);
+
+impl Schema {
+  pub fn id(&self) -> Option<&str> {
+    self.0.as_object()?.get("$id")?.as_str()
+  }
+
+  pub fn anchor(&self) -> Option<&str> {
+    self.0.as_object()?.get("$anchor")?.as_str()
+  }
+
+  pub fn dynamic_anchor(&self) -> Option<&str> {
+    self.0.as_object()?.get("$dynamicAnchor")?.as_str()
+  }
+
+  /// All sub schemas of this schema, keyed by their pointer relative to this
+  /// schema.
+  ///
+  pub fn sub_schemas(&self) -> Option<BTreeMap<Vec<String>, Schema>> {
+    let object = self.0.as_object()?;
+    let mut result = BTreeMap::new();
+
+    for member in SCHEMA_KEYWORDS {
+      if let Some(node) = object.get(member) {
+        if node.is_object() || node.is_boolean() {
+          result.insert(vec![member.to_owned()], node.clone().into());
+        }
+      }
+    }
+
+    for member in SCHEMA_ARRAY_KEYWORDS {
+      if let Some(node) = object.get(member).and_then(|node| node.as_array()) {
+        for (key, node) in node.iter().enumerate() {
+          result.insert(
+            vec![member.to_owned(), key.to_string()],
+            node.clone().into(),
+          );
+        }
+      }
+    }
+
+    for member in SCHEMA_MAP_KEYWORDS {
+      if let Some(node) = object.get(member).and_then(|node| node.as_object()) {
+        for (key, node) in node {
+          if node.is_object() || node.is_boolean() {
+            result.insert(vec![member.to_owned(), key.clone()], node.clone().into());
+          }
+        }
+      }
+    }
+
+    Some(result)
+  }
+}
+
+impl From<serde_json::Value> for Schema {
+  fn from(value: serde_json::Value) -> Self {
+    Self(value)
+  }
+}
@@ -0,0 +1,316 @@
+use super::nodes;
+use crate::documents::{DocumentContext, DocumentError, SchemaLocation};
+use crate::utils::sync;
+use crate::utils::NodeLocation;
+use std::iter;
+
+/// How many references we follow before we give up, references could point
+/// to each other.
+///
+const MAXIMUM_REFERENCE_DEPTH: usize = 32;
+
+pub fn get_node<T>(
+  context: &sync::Weak<DocumentContext>,
+  location: &NodeLocation,
+) -> Result<T, DocumentError>
+where
+  T: From<serde_json::Value>,
+{
+  let context = context.upgrade().unwrap();
+  let node = context
+    .get_node(location)
+    .ok_or(DocumentError::NodeNotFound)?;
+  let node: T = node.clone().into();
+  Ok(node)
+}
+
+/// Follow references until we find a node. Things like messages are often
+/// referenced via a reference to a reference.
+///
+pub fn dereference<T>(
+  context: &sync::Weak<DocumentContext>,
+  location: &NodeLocation,
+  node: nodes::NodeOrReference<T>,
+) -> Result<(NodeLocation, T), DocumentError>
+where
+  T: From<serde_json::Value>,
+{
+  let context = context.upgrade().unwrap();
+  let mut location = location.clone();
+  let mut node = node;
+  for _ in 0..MAXIMUM_REFERENCE_DEPTH {
+    match node {
+      nodes::NodeOrReference::Reference(reference) => {
+        let reference_location: NodeLocation = reference.parse()?;
+        location = context.resolve_reference(&location, &reference_location);
+        node = context
+          .get_node(&location)
+          .ok_or(DocumentError::NodeNotFound)?
+          .into();
+      }
+      nodes::NodeOrReference::Node(node) => return Ok((location, node)),
+    }
+  }
+  Err(DocumentError::NodeNotFound)
+}
+
+/// The resolved locations of the entries that are references.
+///
+pub fn get_referenced_locations_from_reference_entries<'a, N>(
+  context: &'a sync::Weak<DocumentContext>,
+  location: NodeLocation,
+  entries: impl Iterator<Item = (Vec<String>, nodes::NodeOrReference<N>)> + 'a,
+) -> impl Iterator<Item = Result<NodeLocation, DocumentError>> + 'a
+where
+  N: From<serde_json::Value> + 'a,
+{
+  entries
+    .filter_map(move |(pointer, node)| {
+      let location = location.push_pointer(pointer);
+      if let nodes::NodeOrReference::Reference(reference) = node {
+        Some((location, reference))
+      } else {
+        None
+      }
+    })
+    .map(|(location, reference)| {
+      let reference_location: NodeLocation = reference.parse()?;
+      let context = context.upgrade().unwrap();
+      Ok(context.resolve_reference(&location, &reference_location))
+    })
+}
+
+pub fn get_sub_locations_from_node_entries<N, L, SR>(
+  location: NodeLocation,
+  entries: impl Iterator<Item = (Vec<String>, N)>,
+  selector: impl Fn(NodeLocation, N) -> SR,
+) -> impl Iterator<Item = Result<L, DocumentError>>
+where
+  N: From<serde_json::Value>,
+  SR: Iterator<Item = Result<L, DocumentError>>,
+{
+  entries
+    .map(move |(pointer, node)| {
+      let location = location.push_pointer(pointer);
+      (location, node)
+    })
+    .flat_map(move |(location, node)| (selector)(location, node))
+}
+
+/// The canonical uri of the schema at the location. This is the identity
+/// location the schema would have without an `$id`, unless it has an `$id`.
+/// Schemas that are registered with the context have an identity that takes
+/// the `$id`s of their ancestors into account, that identity is used when the
+/// schema, or one of it's ancestors, is registered.
+///
+pub fn get_schema_identity_location(
+  context: &sync::Weak<DocumentContext>,
+  location: &NodeLocation,
+  identity_location: NodeLocation,
+) -> Result<NodeLocation, DocumentError> {
+  let node: nodes::Schema = get_node(context, location)?;
+  let context = context.upgrade().unwrap();
+  let Some(id) = node.id() else {
+    return Ok(
+      context
+        .get_identity_location(location)
+        .unwrap_or(identity_location),
+    );
+  };
+  let id_location: NodeLocation = id.parse()?;
+  // the `$id` is resolved against the base uri of the parent
+  let base_location = Some(location)
+    .filter(|location| !location.is_root())
+    .and_then(|location| context.get_identity_location(&location.set_parent()))
+    .unwrap_or(identity_location);
+  Ok(base_location.set_root().join(&id_location))
+}
+
+pub fn get_schema_locations_from_schema_location(
+  context: &sync::Weak<DocumentContext>,
+  location: NodeLocation,
+  identity_location: NodeLocation,
+) -> Vec<Result<SchemaLocation, DocumentError>> {
+  match get_node(context, &location) {
+    Ok(node) => get_schema_locations_from_schema(location, identity_location, node),
+    Err(error) => vec![Err(error)],
+  }
+}
+
+/// Walks the schema and all of it's sub schemas. The identity location is
+/// the canonical uri of the schema if it had no `$id`, for the root schema
+/// this is the same as the retrieval location.
+///
+pub fn get_schema_locations_from_schema(
+  location: NodeLocation,
+  identity_location: NodeLocation,
+  node: nodes::Schema,
+) -> Vec<Result<SchemaLocation, DocumentError>> {
+  let identity_location = match node.id().map(|id| id.parse::<NodeLocation>()) {
+    None => identity_location,
+    Some(Ok(id_location)) => identity_location.set_root().join(&id_location),
+    Some(Err(error)) => return vec![Err(error.into())],
+  };
+
+  let base_location = identity_location.set_root();
+  let anchor_locations = iter::empty()
+    .chain(node.anchor())
+    .chain(node.dynamic_anchor())
+    .map(|anchor| base_location.set_anchor(anchor.to_owned()))
+    .collect();
+
+  iter::once(Ok(SchemaLocation {
+    retrieval_location: location.clone(),
+    identity_location: identity_location.clone(),
+    anchor_locations,
+  }))
+  .chain(
+    node
+      .sub_schemas()
+      .into_iter()
+      .flatten()
+      .flat_map(|(pointer, node)| {
+        get_schema_locations_from_schema(
+          location.push_pointer(pointer.clone()),
+          identity_location.push_pointer(pointer),
+          node,
+        )
+      }),
+  )
+  .collect()
+}
+
+/// Members that hold literal values, they are never walked
+const LITERAL_MEMBERS: [&str; 4] = ["const", "default", "enum", "example"];
+
+/// What a member of a node that is not read via typed nodes holds. The
+/// schemas pointer is the pointer (relative to the node) of a map of schemas,
+/// like `definitions`.
+///
+enum Member<'a> {
+  Literal,
+  Schemas,
+  Schema,
+  Node(&'a [&'a str]),
+}
+
+impl<'a> Member<'a> {
+  fn new(member: &str, node: &serde_json::Value, schemas_pointer: &'a [&'a str]) -> Self {
+    if LITERAL_MEMBERS.contains(&member) {
+      Self::Literal
+    } else if schemas_pointer == [member] {
+      Self::Schemas
+    } else if member == "schema" && node.is_object() {
+      Self::Schema
+    } else if schemas_pointer.first() == Some(&member) {
+      Self::Node(&schemas_pointer[1..])
+    } else {
+      Self::Node(&[])
+    }
+  }
+}
+
+/// Walks a node that is not read via typed nodes, every `$ref` in the node
+/// is resolved against the location where it is found. References in
+/// schemas are not included, like the other readers these are loaded via
+/// `DocumentContext::load_referenced_nodes`.
+///
+pub fn get_referenced_locations_from_node(
+  context: &sync::Weak<DocumentContext>,
+  location: NodeLocation,
+  node: &serde_json::Value,
+  schemas_pointer: &[&str],
+) -> Vec<Result<NodeLocation, DocumentError>> {
+  let mut result = Vec::new();
+
+  match node {
+    serde_json::Value::Object(object_node) => {
+      if let Some(reference) = object_node.get("$ref").and_then(|node| node.as_str()) {
+        result.push(reference.parse().map_err(Into::into).map(
+          |reference_location: NodeLocation| {
+            let context = context.upgrade().unwrap();
+            context.resolve_reference(&location, &reference_location)
+          },
+        ));
+      }
+      for (member, node) in object_node {
+        if let Member::Node(schemas_pointer) = Member::new(member, node, schemas_pointer) {
+          result.extend(get_referenced_locations_from_node(
+            context,
+            location.push_pointer(vec![member.clone()]),
+            node,
+            schemas_pointer,
+          ));
+        }
+      }
+    }
+    serde_json::Value::Array(array_node) => {
+      for (index, node) in array_node.iter().enumerate() {
+        result.extend(get_referenced_locations_from_node(
+          context,
+          location.push_pointer(vec![index.to_string()]),
+          node,
+          &[],
+        ));
+      }
+    }
+    _ => {}
+  }
+
+  result
+}
+
+/// Walks a node that is not read via typed nodes and finds the schemas in
+/// it. Every member of the map at the schemas pointer is a schema, and so is
+/// every `schema` member. Schemas are identified by their retrieval location.
+///
+pub fn get_schema_locations_from_node(
+  location: NodeLocation,
+  node: &serde_json::Value,
+  schemas_pointer: &[&str],
+) -> Vec<Result<SchemaLocation, DocumentError>> {
+  let mut result = Vec::new();
+
+  match node {
+    serde_json::Value::Object(object_node) => {
+      for (member, node) in object_node {
+        let location = location.push_pointer(vec![member.clone()]);
+        match Member::new(member, node, schemas_pointer) {
+          Member::Literal => {}
+          Member::Schemas => {
+            for (member, node) in node.as_object().into_iter().flatten() {
+              let location = location.push_pointer(vec![member.clone()]);
+              result.extend(get_schema_locations_from_schema(
+                location.clone(),
+                location,
+                node.clone().into(),
+              ));
+            }
+          }
+          Member::Schema => result.extend(get_schema_locations_from_schema(
+            location.clone(),
+            location,
+            node.clone().into(),
+          )),
+          Member::Node(schemas_pointer) => result.extend(get_schema_locations_from_node(
+            location,
+            node,
+            schemas_pointer,
+          )),
+        }
+      }
+    }
+    serde_json::Value::Array(array_node) => {
+      for (index, node) in array_node.iter().enumerate() {
+        result.extend(get_schema_locations_from_node(
+          location.push_pointer(vec![index.to_string()]),
+          node,
+          &[],
+        ));
+      }
+    }
+    _ => {}
+  }
+
+  result
+}
@@ -1,23 +1,59 @@
 use super::interface::DocumentFactory;
-use super::{DocumentInterface, DocumentType};
+use super::{DocumentError, DocumentInterface, DocumentType, SchemaLocation};
 use crate::documents::DocumentConfiguration;
-use crate::documents::{oas30, oas31, swagger2};
+use crate::documents::{asyncapi2, asyncapi3, jsonschema, oas3, oas30, oas31, swagger2};
 use crate::error::Error;
 use crate::models;
-use crate::utils::{NodeCache, NodeLocation};
-use std::cell::RefCell;
-use std::collections::BTreeMap;
-use std::rc;
+use crate::utils::sync;
+use crate::utils::sync::Lock;
+use crate::utils::{
+  collect_references, DefaultFetcher, FetchLockOptions, Fetcher, HttpFetchOptions, LockedFetcher,
+  NodeCache, NodeLocation, TextFormat,
+};
+use futures::stream::FuturesUnordered;
+use futures::StreamExt;
+use std::collections::{btree_map, BTreeMap, BTreeSet, VecDeque};
+use std::time::Duration;
 use wasm_bindgen::prelude::*;
 
+/// The maximum number of documents that are fetched at the same time while
+/// loading.
+///
+pub const MAXIMUM_CONCURRENT_FETCHES: usize = 8;
+
+/// The document at a location and the schemas in it, as taken out of the
+/// context.
+///
+struct LocationState {
+  node: Option<serde_json::Value>,
+  document: Option<Box<dyn DocumentInterface>>,
+  identity_locations: BTreeMap<NodeLocation, NodeLocation>,
+  retrieval_locations: BTreeMap<NodeLocation, NodeLocation>,
+}
+
 #[derive(Default)]
 pub struct DocumentContext {
-  cache: rc::Rc<RefCell<NodeCache>>,
+  cache: sync::Shared<Lock<NodeCache>>,
   /**
    * document factories by document type key
    */
-  factories: RefCell<BTreeMap<DocumentType, DocumentFactory>>,
-  documents: RefCell<BTreeMap<NodeLocation, Box<dyn DocumentInterface>>>,
+  factories: Lock<BTreeMap<DocumentType, DocumentFactory>>,
+  documents: Lock<BTreeMap<NodeLocation, Box<dyn DocumentInterface>>>,
+  /**
+   * document types set by the caller, by retrieval location, these are used
+   * instead of detecting the type
+   */
+  document_types: Lock<BTreeMap<NodeLocation, DocumentType>>,
+  /**
+   * canonical (identity) locations of schemas by their retrieval location
+   */
+  identity_locations: Lock<BTreeMap<NodeLocation, NodeLocation>>,
+  /**
+   * retrieval locations of schemas by their identity or anchor location
+   */
+  retrieval_locations: Lock<BTreeMap<NodeLocation, NodeLocation>>,
+  http_fetch_options: Lock<HttpFetchOptions>,
+  fetch_lock_options: Lock<Option<FetchLockOptions>>,
 }
 
 impl DocumentContext {
@@ -25,34 +61,12 @@ impl DocumentContext {
     Self::default()
   }
 
-  pub fn register_factory(&self, r#type: DocumentType, factory: DocumentFactory) {
-    /*
-    don't check if the factory is already registered here so we can
-    override factories
-    */
-    self.factories.borrow_mut().insert(r#type, factory);
-  }
-
-  pub fn get_node(&self, retrieval_location: &NodeLocation) -> Option<serde_json::Value> {
-    self.cache.borrow().get_node(retrieval_location).cloned()
-  }
-}
-
-#[wasm_bindgen]
-#[derive(Default)]
-pub struct DocumentContextContainer(rc::Rc<DocumentContext>);
-
-#[wasm_bindgen]
-impl DocumentContextContainer {
-  #[wasm_bindgen(constructor)]
-  pub fn new() -> Self {
-    Self::default()
-  }
-
-  #[wasm_bindgen(js_name = "registerWellKnownFactories")]
-  pub fn register_well_known_factories(&self) {
-    let context = rc::Rc::downgrade(&self.0);
-    self.0.register_factory(
+  /// Register the factories for all document types that are supported by
+  /// this crate.
+  ///
+  pub fn register_well_known_factories(self: &sync::Shared<Self>) {
+    let context = sync::Shared::downgrade(self);
+    self.register_factory(
       DocumentType::Swagger2,
       Box::new(move |configuration| {
         Box::new(swagger2::Document::new(
@@ -61,18 +75,19 @@ impl DocumentContextContainer {
         ))
       }),
     );
-    let context = rc::Rc::downgrade(&self.0);
-    self.0.register_factory(
+    let context = sync::Shared::downgrade(self);
+    self.register_factory(
       DocumentType::OpenApiV30,
       Box::new(move |configuration| {
-        Box::new(oas30::Document::new(
+        Box::new(oas3::Document::new(
           context.clone(),
           configuration.retrieval_location,
+          DocumentType::OpenApiV30,
         ))
       }),
     );
-    let context = rc::Rc::downgrade(&self.0);
-    self.0.register_factory(
+    let context = sync::Shared::downgrade(self);
+    self.register_factory(
       DocumentType::OpenApiV31,
       Box::new(move |configuration| {
         Box::new(oas31::Document::new(
@@ -81,143 +96,1123 @@ impl DocumentContextContainer {
         ))
       }),
     );
+    let context = sync::Shared::downgrade(self);
+    self.register_factory(
+      DocumentType::OpenApiV32,
+      Box::new(move |configuration| {
+        Box::new(oas3::Document::new(
+          context.clone(),
+          configuration.retrieval_location,
+          DocumentType::OpenApiV32,
+        ))
+      }),
+    );
+    let context = sync::Shared::downgrade(self);
+    self.register_factory(
+      DocumentType::AsyncApiV2,
+      Box::new(move |configuration| {
+        Box::new(asyncapi2::Document::new(
+          context.clone(),
+          configuration.retrieval_location,
+        ))
+      }),
+    );
+    let context = sync::Shared::downgrade(self);
+    self.register_factory(
+      DocumentType::AsyncApiV3,
+      Box::new(move |configuration| {
+        Box::new(asyncapi3::Document::new(
+          context.clone(),
+          configuration.retrieval_location,
+        ))
+      }),
+    );
+    let context = sync::Shared::downgrade(self);
+    self.register_factory(
+      DocumentType::JsonSchema,
+      Box::new(move |configuration| {
+        Box::new(jsonschema::Document::new(
+          context.clone(),
+          configuration.retrieval_location,
+        ))
+      }),
+    );
   }
 
-  #[wasm_bindgen(js_name = "loadFromLocation")]
-  #[allow(clippy::await_holding_refcell_ref)]
-  pub async fn load_from_location(&self, retrieval_location: &str) -> Result<(), Error> {
-    let retrieval_location = retrieval_location.parse().unwrap();
-    let mut queue = Vec::new();
-    queue.push(retrieval_location);
+  pub fn register_factory(&self, r#type: DocumentType, factory: DocumentFactory) {
+    /*
+    don't check if the factory is already registered here so we can
+    override factories
+    */
+    self.factories.borrow_mut().insert(r#type, factory);
+  }
 
-    while let Some(retrieval_location) = queue.pop() {
-      if self.0.documents.borrow().contains_key(&retrieval_location) {
-        continue;
+  /// Use this type for the document at the location instead of detecting it,
+  /// or detect it again when the type is `None`. Set the type before loading
+  /// the document.
+  ///
+  pub fn set_document_type(
+    &self,
+    retrieval_location: &NodeLocation,
+    document_type: Option<DocumentType>,
+  ) {
+    let retrieval_location = retrieval_location.set_root();
+    let mut document_types = self.document_types.borrow_mut();
+    match document_type {
+      Some(document_type) => document_types.insert(retrieval_location, document_type),
+      None => document_types.remove(&retrieval_location),
+    };
+  }
+
+  /// Use this fetcher to load all documents
+  ///
+  pub fn set_fetcher(&self, fetcher: impl Fetcher + 'static) {
+    self.cache.borrow_mut().set_fetcher(fetcher);
+  }
+
+  /// Change the options for fetching documents over http(s), this will
+  /// replace the fetcher with the default fetcher.
+  ///
+  pub fn update_http_fetch_options(&self, update: impl FnOnce(&mut HttpFetchOptions)) {
+    update(&mut self.http_fetch_options.borrow_mut());
+    self.reset_fetcher();
+  }
+
+  /// Pin remote documents in a fetch lock, or stop doing so by passing `None`.
+  /// This will replace the fetcher with the default fetcher.
+  ///
+  pub fn set_fetch_lock_options(&self, fetch_lock_options: Option<FetchLockOptions>) {
+    *self.fetch_lock_options.borrow_mut() = fetch_lock_options;
+    self.reset_fetcher();
+  }
+
+  fn reset_fetcher(&self) {
+    let fetcher = DefaultFetcher::new_with_http_options(self.http_fetch_options.borrow().clone());
+    match self.fetch_lock_options.borrow().clone() {
+      Some(FetchLockOptions { directory, offline }) => {
+        self.set_fetcher(LockedFetcher::new(fetcher, directory, offline))
+      }
+      None => self.set_fetcher(fetcher),
+    }
+  }
+
+  /// Load the document at the location and all documents it references.
+  /// Documents are fetched concurrently, with at most
+  /// `MAXIMUM_CONCURRENT_FETCHES` fetches at the same time. No borrows are
+  /// held while fetching.
+  ///
+  pub async fn load_from_location(&self, retrieval_location: &NodeLocation) -> Result<(), Error> {
+    let fetcher = self.cache.borrow().get_fetcher();
+    let mut seen = BTreeSet::new();
+    let mut queue = self.add_documents(vec![retrieval_location.set_root()], &mut seen)?;
+    let mut fetching = FuturesUnordered::new();
+
+    loop {
+      while fetching.len() < MAXIMUM_CONCURRENT_FETCHES {
+        let Some(retrieval_location) = queue.pop_front() else {
+          break;
+        };
+        let fetcher = fetcher.clone();
+        fetching.push(async move {
+          let result = NodeCache::fetch_node(fetcher.as_ref(), &retrieval_location).await;
+          (retrieval_location, result)
+        });
+      }
+
+      let Some((retrieval_location, result)) = fetching.next().await else {
+        break;
+      };
+      let node = result?;
+
+      // the document might be added to the cache while we were fetching, the
+      // check and the insert happen under one borrow
+      {
+        let mut cache = self.cache.borrow_mut();
+        if !cache.contains_location(&retrieval_location) {
+          cache.load_from_node(&retrieval_location, node)?;
+        }
       }
 
+      let referenced_locations = self.add_document(&retrieval_location)?;
+      queue.extend(self.add_documents(referenced_locations, &mut seen)?);
+    }
+
+    Ok(())
+  }
+
+  /// Add a document that is not fetched, like a document that is edited in
+  /// memory, and load the documents it references. Loading the same node
+  /// again is fine, loading a different node for a location that is already
+  /// loaded is a conflict. If loading fails nothing is added, so it can be
+  /// tried again with another node.
+  ///
+  pub async fn load_from_node(
+    &self,
+    retrieval_location: &NodeLocation,
+    node: serde_json::Value,
+  ) -> Result<(), Error> {
+    let retrieval_location = retrieval_location.set_root();
+    let loaded_locations = self.get_loaded_locations();
+    self
+      .cache
+      .borrow_mut()
+      .load_from_node(&retrieval_location, node)?;
+    self
+      .load_from_location_or_rollback(&retrieval_location, &loaded_locations)
+      .await
+  }
+
+  /// Parse the text of a document that is not fetched and load it, like
+  /// `load_from_node`.
+  ///
+  pub async fn load_from_text(
+    &self,
+    retrieval_location: &NodeLocation,
+    text: &str,
+    format: TextFormat,
+  ) -> Result<(), Error> {
+    let retrieval_location = retrieval_location.set_root();
+    let loaded_locations = self.get_loaded_locations();
+    self
+      .cache
+      .borrow_mut()
+      .load_from_text(&retrieval_location, text, format)?;
+    self
+      .load_from_location_or_rollback(&retrieval_location, &loaded_locations)
+      .await
+  }
+
+  /// Load like `load_from_location`, when that fails every location that was
+  /// not in `loaded_locations` is invalidated again.
+  ///
+  async fn load_from_location_or_rollback(
+    &self,
+    retrieval_location: &NodeLocation,
+    loaded_locations: &BTreeSet<NodeLocation>,
+  ) -> Result<(), Error> {
+    let result = self.load_from_location(retrieval_location).await;
+    if result.is_err() {
+      self.invalidate_new_locations(loaded_locations);
+    }
+    result
+  }
+
+  /// Fetch the document at the location again and load the documents it now
+  /// references. Returns the paths and operations that changed in the api
+  /// models of all loaded documents. Nothing changes if the document, or a
+  /// document it now references, can not be fetched or loaded.
+  ///
+  pub async fn reload_from_location(
+    &self,
+    retrieval_location: &NodeLocation,
+  ) -> Result<Vec<models::ApiChange>, Error> {
+    let retrieval_location = retrieval_location.set_root();
+    let fetcher = self.cache.borrow().get_fetcher();
+    let node = NodeCache::fetch_node(fetcher.as_ref(), &retrieval_location).await?;
+
+    let old_api_models = self.get_api_models()?;
+    let loaded_locations = self.get_loaded_locations();
+    let old_state = self.take_location(&retrieval_location);
+    let result = async {
       self
-        .0
         .cache
         .borrow_mut()
-        .load_from_location(&retrieval_location)
-        .await?;
+        .load_from_node(&retrieval_location, node)?;
+      self.load_from_location(&retrieval_location).await
+    }
+    .await;
+    if let Err(error) = result {
+      // put everything back like it was before the reload
+      self.invalidate_location(&retrieval_location);
+      self.invalidate_new_locations(&loaded_locations);
+      self.restore_location(&retrieval_location, old_state);
+      return Err(error);
+    }
+    let new_api_models = self.get_api_models()?;
 
-      let document_type = self
-        .0
-        .cache
-        .borrow()
-        .get_node(&retrieval_location)
-        .ok_or(Error::NotFound)?
-        .try_into()?;
+    let api_locations: BTreeSet<_> = old_api_models.keys().chain(new_api_models.keys()).collect();
+    let changes = api_locations
+      .into_iter()
+      .flat_map(|api_location| {
+        models::ApiChange::get_changes(
+          api_location,
+          old_api_models.get(api_location).map(AsRef::as_ref),
+          new_api_models.get(api_location).map(AsRef::as_ref),
+        )
+      })
+      .collect();
 
-      let document = {
-        let factories = self.0.factories.borrow();
-        let factory = factories.get(&document_type).ok_or(Error::NotFound)?;
-        factory(DocumentConfiguration {
-          retrieval_location: retrieval_location.clone(),
-        })
-      };
+    Ok(changes)
+  }
+
+  /// Load every node that is referenced (via `$ref`) from the document at the
+  /// location, and from the nodes it references, in the node cache. Nodes of
+  /// a supported document type are added as documents so the `$id`s in them
+  /// resolve, other nodes are only cached, so this also works for documents
+  /// that are not supported, like json schema documents.
+  ///
+  pub async fn load_referenced_nodes(
+    &self,
+    retrieval_location: &NodeLocation,
+  ) -> Result<(), Error> {
+    let fetcher = self.cache.borrow().get_fetcher();
+    let root_location = retrieval_location.set_root();
+    let mut seen = BTreeSet::new();
+    let mut stack = vec![root_location.clone()];
+    let mut references = Vec::new();
+    let mut failed = BTreeMap::new();
+
+    loop {
+      while let Some(retrieval_location) = stack.pop() {
+        if !seen.insert(retrieval_location.clone()) {
+          continue;
+        }
 
-      for referenced_location in document.get_referenced_locations()? {
-        let referenced_retrieval_location = retrieval_location.join(&referenced_location);
+        if !self.cache.borrow().contains_location(&retrieval_location) {
+          match NodeCache::fetch_node(fetcher.as_ref(), &retrieval_location).await {
+            Ok(node) => self
+              .cache
+              .borrow_mut()
+              .load_from_node(&retrieval_location, node)?,
+            // this might be the `$id` of a schema in a document that is not
+            // loaded yet, we know when every other document is loaded
+            Err(error) => {
+              failed.insert(retrieval_location, error);
+              continue;
+            }
+          }
+        }
+        if !self.documents.borrow().contains_key(&retrieval_location) {
+          // unsupported documents are only cached
+          let _ = self.add_document(&retrieval_location);
+        }
 
-        queue.push(referenced_retrieval_location);
+        let node = self.get_node(&retrieval_location).ok_or(Error::NotFound)?;
+        let mut node_references = Vec::new();
+        collect_references(&retrieval_location, &node, &mut node_references);
+        for (location, reference) in node_references {
+          let reference_location: NodeLocation = reference.parse()?;
+          let referenced_location = self.resolve_reference(&location, &reference_location);
+          stack.push(referenced_location.set_root());
+          references.push((location, reference_location));
+        }
       }
 
-      assert!(self
-        .0
-        .documents
-        .borrow_mut()
-        .insert(retrieval_location.clone(), document)
-        .is_none());
+      // references resolve differently when a document with the `$id` they
+      // reference is loaded, so resolve them again until nothing changes
+      stack.extend(
+        references
+          .iter()
+          .map(|(location, reference_location)| {
+            self
+              .resolve_reference(location, reference_location)
+              .set_root()
+          })
+          .filter(|referenced_location| !seen.contains(referenced_location)),
+      );
+      if stack.is_empty() {
+        break;
+      }
+    }
+
+    let referenced_locations: BTreeSet<_> = references
+      .iter()
+      .map(|(location, reference_location)| {
+        self
+          .resolve_reference(location, reference_location)
+          .set_root()
+      })
+      .chain([root_location])
+      .collect();
+    for (retrieval_location, error) in failed {
+      if referenced_locations.contains(&retrieval_location) {
+        return Err(error.into());
+      }
     }
 
     Ok(())
   }
 
-  #[wasm_bindgen(js_name = "getApiModel")]
-  pub fn get_api_model(&self, retrieval_location: &str) -> Option<models::ApiContainer> {
-    let retrieval_location = retrieval_location.parse().unwrap();
-    let documents = self.0.documents.borrow();
-    let document = documents.get(&retrieval_location)?;
-    let api_model = document.get_api_model().unwrap();
+  /// Forget the document at the location and the schemas in it, it will be
+  /// fetched again the next time it is loaded.
+  ///
+  pub fn invalidate_location(&self, retrieval_location: &NodeLocation) {
+    self.take_location(retrieval_location);
+  }
+
+  /// Remove the document at the location and the schemas in it, and return
+  /// them so they can be restored.
+  ///
+  fn take_location(&self, retrieval_location: &NodeLocation) -> LocationState {
+    let root_location = retrieval_location.set_root();
 
-    Some(api_model.into())
+    let node = {
+      let mut cache = self.cache.borrow_mut();
+      let node = cache.get_node(&root_location).cloned();
+      cache.invalidate(&root_location);
+      node
+    };
+    let document = self.documents.borrow_mut().remove(&root_location);
+    let (identity_locations, rest) = std::mem::take(&mut *self.identity_locations.borrow_mut())
+      .into_iter()
+      .partition(|(retrieval_location, _)| retrieval_location.set_root() == root_location);
+    *self.identity_locations.borrow_mut() = rest;
+    let (retrieval_locations, rest) = std::mem::take(&mut *self.retrieval_locations.borrow_mut())
+      .into_iter()
+      .partition(|(_, retrieval_location)| retrieval_location.set_root() == root_location);
+    *self.retrieval_locations.borrow_mut() = rest;
+
+    LocationState {
+      node,
+      document,
+      identity_locations,
+      retrieval_locations,
+    }
   }
 
-  #[wasm_bindgen(js_name = "getSchemas")]
-  pub fn get_schemas(&self) -> Vec<DocumentSchemaContainer> {
-    let documents = self.0.documents.borrow();
-    documents
-      .values()
-      .flat_map(|document| {
-        document
-          .get_schema_locations()
-          .into_iter()
-          .flatten()
-          .map(|schema_location| {
-            rc::Rc::new(DocumentSchema {
-              schema_location,
-              document_location: document.get_document_location(),
-              default_schema_id: document.get_default_schema_id(),
-            })
-            .into()
-          })
-      })
+  /// Put back what `take_location` removed.
+  ///
+  fn restore_location(&self, retrieval_location: &NodeLocation, state: LocationState) {
+    let root_location = retrieval_location.set_root();
+
+    if let Some(node) = state.node {
+      // the location is invalidated, so this can not conflict
+      let _ = self.cache.borrow_mut().load_from_node(&root_location, node);
+    }
+    if let Some(document) = state.document {
+      self.documents.borrow_mut().insert(root_location, document);
+    }
+    self
+      .identity_locations
+      .borrow_mut()
+      .extend(state.identity_locations);
+    self
+      .retrieval_locations
+      .borrow_mut()
+      .extend(state.retrieval_locations);
+  }
+
+  /// The (root) locations of every document and node that is loaded.
+  ///
+  fn get_loaded_locations(&self) -> BTreeSet<NodeLocation> {
+    let mut locations: BTreeSet<_> = self.cache.borrow().get_root_locations().cloned().collect();
+    locations.extend(self.documents.borrow().keys().cloned());
+    locations
+  }
+
+  /// Invalidate every location that was loaded after `loaded_locations` was
+  /// taken, this undoes a load that failed halfway.
+  ///
+  fn invalidate_new_locations(&self, loaded_locations: &BTreeSet<NodeLocation>) {
+    for location in self.get_loaded_locations() {
+      if !loaded_locations.contains(&location) {
+        self.invalidate_location(&location);
+      }
+    }
+  }
+
+  fn get_api_models(&self) -> Result<BTreeMap<NodeLocation, sync::Shared<models::Api>>, Error> {
+    self
+      .documents
+      .borrow()
+      .iter()
+      .filter_map(
+        |(retrieval_location, document)| match document.get_api_model() {
+          Ok(api_model) => Some(Ok((retrieval_location.clone(), api_model))),
+          Err(DocumentError::ModelNotSupported) => None,
+          Err(error) => Some(Err(error.into())),
+        },
+      )
       .collect()
   }
-}
 
-pub struct DocumentSchema {
-  pub schema_location: NodeLocation,
-  pub document_location: NodeLocation,
-  pub default_schema_id: String,
-}
+  /// Add documents for every location that is already in the cache, and the
+  /// documents they reference. Returns the locations that still need to be
+  /// fetched.
+  ///
+  fn add_documents(
+    &self,
+    retrieval_locations: Vec<NodeLocation>,
+    seen: &mut BTreeSet<NodeLocation>,
+  ) -> Result<VecDeque<NodeLocation>, Error> {
+    let mut stack = retrieval_locations;
+    let mut result = VecDeque::new();
 
-#[wasm_bindgen]
-#[derive(Clone)]
-pub struct DocumentSchemaContainer(rc::Rc<DocumentSchema>);
+    while let Some(retrieval_location) = stack.pop() {
+      if !seen.insert(retrieval_location.clone()) {
+        continue;
+      }
+      if self.documents.borrow().contains_key(&retrieval_location) {
+        continue;
+      }
+      if !self.cache.borrow().contains_location(&retrieval_location) {
+        result.push_back(retrieval_location);
+        continue;
+      }
 
-#[wasm_bindgen]
-impl DocumentSchemaContainer {
-  #[wasm_bindgen(getter, js_name = "schemaLocation")]
-  pub fn schema_location(&self) -> String {
-    self.0.schema_location.to_string()
+      stack.extend(self.add_document(&retrieval_location)?);
+    }
+
+    Ok(result)
   }
 
-  #[wasm_bindgen(getter, js_name = "documentLocation")]
-  pub fn document_location(&self) -> String {
-    self.0.document_location.to_string()
+  /// Create the document for a location that is in the cache, returns the
+  /// (root) locations of the documents it references.
+  ///
+  fn add_document(&self, retrieval_location: &NodeLocation) -> Result<Vec<NodeLocation>, Error> {
+    let document_type = self
+      .document_types
+      .borrow()
+      .get(retrieval_location)
+      .copied();
+    let document_type = match document_type {
+      Some(document_type) => document_type,
+      None => self
+        .cache
+        .borrow()
+        .get_node(retrieval_location)
+        .ok_or(Error::NotFound)?
+        .try_into()?,
+    };
+
+    let document = {
+      let factories = self.factories.borrow();
+      let factory = factories.get(&document_type).ok_or(Error::NotFound)?;
+      factory(DocumentConfiguration {
+        retrieval_location: retrieval_location.clone(),
+      })
+    };
+
+    // references in the document resolve against it's identity, so we register
+    // it like we would register a schema with an `$id`
+    if let Some(identity_location) = document.get_identity_location()? {
+      self.register_schema_location(SchemaLocation {
+        retrieval_location: retrieval_location.clone(),
+        identity_location,
+        anchor_locations: Vec::new(),
+      });
+    }
+
+    for schema_location in document.get_schema_locations()? {
+      self.register_schema_location(schema_location);
+    }
+
+    // referenced locations are already resolved by the document, we only need
+    // the document they are in
+    let referenced_locations = document
+      .get_referenced_locations()?
+      .into_iter()
+      .map(|referenced_location| referenced_location.set_root())
+      .collect();
+
+    // another load might have added the document while we were fetching it,
+    // the document that was added first is kept
+    if let btree_map::Entry::Vacant(entry) = self
+      .documents
+      .borrow_mut()
+      .entry(retrieval_location.clone())
+    {
+      entry.insert(document);
+    }
+
+    Ok(referenced_locations)
   }
 
-  #[wasm_bindgen(getter, js_name = "defaultSchemaId")]
-  pub fn default_schema_id(&self) -> String {
-    self.0.default_schema_id.clone()
+  pub fn get_node(&self, retrieval_location: &NodeLocation) -> Option<serde_json::Value> {
+    self.cache.borrow().get_node(retrieval_location).cloned()
+  }
+
+  /// Adds the schema to the identity index so references to it's identity or
+  /// anchors can be resolved to the retrieval location.
+  ///
+  pub fn register_schema_location(&self, schema_location: SchemaLocation) {
+    let mut identity_locations = self.identity_locations.borrow_mut();
+    let mut retrieval_locations = self.retrieval_locations.borrow_mut();
+
+    for anchor_location in schema_location.anchor_locations {
+      retrieval_locations.insert(anchor_location, schema_location.retrieval_location.clone());
+    }
+    retrieval_locations.insert(
+      schema_location.identity_location.clone(),
+      schema_location.retrieval_location.clone(),
+    );
+    identity_locations.insert(
+      schema_location.retrieval_location,
+      schema_location.identity_location,
+    );
+  }
+
+  /// Get the canonical location for a retrieval location. If the location is
+  /// not a registered schema, then the identity of the closest registered
+  /// ancestor is used.
+  ///
+  pub fn get_identity_location(&self, retrieval_location: &NodeLocation) -> Option<NodeLocation> {
+    let identity_locations = self.identity_locations.borrow();
+
+    let mut ancestor_location = retrieval_location.clone();
+    let mut pointer = Vec::new();
+    loop {
+      if let Some(identity_location) = identity_locations.get(&ancestor_location) {
+        return Some(identity_location.push_pointer(pointer));
+      }
+
+      if ancestor_location.is_root() {
+        return None;
+      }
+
+      let mut ancestor_pointer = ancestor_location.get_pointer().unwrap_or_default();
+      if let Some(member) = ancestor_pointer.pop() {
+        pointer.insert(0, member);
+      }
+      ancestor_location = ancestor_location.set_parent();
+    }
+  }
+
+  /// Get the retrieval location for a canonical (identity) location, this
+  /// could also be an anchor.
+  ///
+  pub fn get_retrieval_location(&self, identity_location: &NodeLocation) -> Option<NodeLocation> {
+    let retrieval_locations = self.retrieval_locations.borrow();
+
+    if let Some(retrieval_location) = retrieval_locations.get(identity_location) {
+      return Some(retrieval_location.clone());
+    }
+
+    let pointer = identity_location.get_pointer()?;
+    let retrieval_location = retrieval_locations.get(&identity_location.set_root())?;
+    Some(retrieval_location.push_pointer(pointer))
+  }
+
+  /// Resolve a reference found at the retrieval location. The reference is
+  /// resolved against the base uri of the schema that contains the reference
+  /// (honouring `$id`) and then mapped back to a retrieval location. If the
+  /// reference points to a location that is not (yet) known the resolved
+  /// location is returned as is.
+  ///
+  pub fn resolve_reference(
+    &self,
+    retrieval_location: &NodeLocation,
+    reference_location: &NodeLocation,
+  ) -> NodeLocation {
+    let base_location = self
+      .get_identity_location(retrieval_location)
+      .unwrap_or_else(|| retrieval_location.clone());
+    let identity_location = base_location.join(reference_location);
+
+    self
+      .get_retrieval_location(&identity_location)
+      .unwrap_or(identity_location)
   }
 }
 
-impl From<rc::Rc<DocumentSchema>> for DocumentSchemaContainer {
-  fn from(interior: rc::Rc<DocumentSchema>) -> Self {
-    Self(interior)
+impl DocumentContext {
+  /// The retrieval locations of all loaded documents
+  pub fn get_document_locations(&self) -> Vec<NodeLocation> {
+    self.documents.borrow().keys().cloned().collect()
+  }
+
+  /// The locations the loaded document references, the references are
+  /// resolved.
+  ///
+  pub fn get_referenced_locations(
+    &self,
+    retrieval_location: &NodeLocation,
+  ) -> Result<Vec<NodeLocation>, Error> {
+    let documents = self.documents.borrow();
+    let document = documents.get(retrieval_location).ok_or(Error::NotFound)?;
+    Ok(document.get_referenced_locations()?)
+  }
+
+  /// The api model of a loaded document, `Error::NotFound` is returned when
+  /// the document is not loaded or does not describe an (http) api.
+  ///
+  pub fn get_api_model(
+    &self,
+    retrieval_location: &NodeLocation,
+  ) -> Result<sync::Shared<models::Api>, Error> {
+    let documents = self.documents.borrow();
+    let document = documents.get(retrieval_location).ok_or(Error::NotFound)?;
+    Ok(document.get_api_model()?)
+  }
+
+  /// The async api model of a loaded document, `Error::NotFound` is returned
+  /// when the document is not loaded or does not describe an async api.
+  ///
+  pub fn get_async_api_model(
+    &self,
+    retrieval_location: &NodeLocation,
+  ) -> Result<sync::Shared<models::AsyncApi>, Error> {
+    let documents = self.documents.borrow();
+    let document = documents.get(retrieval_location).ok_or(Error::NotFound)?;
+    Ok(document.get_async_api_model()?)
+  }
+
+  /// All schemas in all loaded documents
+  pub fn get_schemas(&self) -> Result<Vec<DocumentSchema>, Error> {
+    let documents = self.documents.borrow();
+    let mut schemas = Vec::new();
+    for document in documents.values() {
+      for schema_location in document.get_schema_locations()? {
+        schemas.push(DocumentSchema {
+          schema_location: schema_location.retrieval_location,
+          schema_id: schema_location.identity_location,
+          anchor_locations: schema_location.anchor_locations,
+          document_location: document.get_document_location(),
+          default_schema_id: document.get_default_schema_id(),
+        });
+      }
+    }
+    Ok(schemas)
+  }
+
+  /// Compare the api models of two loaded documents
+  pub fn diff_api_models(
+    &self,
+    old_retrieval_location: &NodeLocation,
+    new_retrieval_location: &NodeLocation,
+  ) -> Result<models::ApiDiff, Error> {
+    let (old_api_model, new_api_model) = {
+      let documents = self.documents.borrow();
+      let old_document = documents
+        .get(old_retrieval_location)
+        .ok_or(Error::NotFound)?;
+      let new_document = documents
+        .get(new_retrieval_location)
+        .ok_or(Error::NotFound)?;
+      (old_document.get_api_model()?, new_document.get_api_model()?)
+    };
+
+    Ok(models::ApiDiff::new(
+      &old_api_model,
+      &new_api_model,
+      self,
+      self,
+    ))
+  }
+
+  /// Merge the api models of loaded documents, the paths of every api get
+  /// the prefix that is paired with the location. `Error::NotFound` is
+  /// returned when there are no locations or a document does not describe an
+  /// (http) api.
+  ///
+  pub fn merge_api_models(
+    &self,
+    sources: &[(NodeLocation, String)],
+  ) -> Result<models::ApiMerge, Error> {
+    let sources = sources
+      .iter()
+      .map(|(retrieval_location, path_prefix)| {
+        Ok(models::ApiMergeSource {
+          api: self.get_api_model(retrieval_location)?,
+          path_prefix: path_prefix.clone(),
+        })
+      })
+      .collect::<Result<Vec<_>, Error>>()?;
+
+    models::ApiMerge::new(&sources).ok_or(Error::NotFound)
+  }
+
+  /// The operations of the api model of a loaded document that pass the
+  /// filter, with the schemas they use.
+  ///
+  pub fn slice_api_model(
+    &self,
+    retrieval_location: &NodeLocation,
+    filter: &models::ApiFilter,
+  ) -> Result<models::ApiSlice, Error> {
+    let api_model = self.get_api_model(retrieval_location)?;
+    Ok(models::ApiSlice::new(&api_model, filter, self))
+  }
+
+  /// Write an OpenApi document of the target version from the api model of a
+  /// loaded document, see `write_openapi_document`.
+  ///
+  pub fn write_openapi_document(
+    &self,
+    retrieval_location: &NodeLocation,
+    target: DocumentType,
+  ) -> Result<oas30::UpgradedDocument, Error> {
+    let api_model = self.get_api_model(retrieval_location)?;
+    super::write_openapi_document(&api_model, target, self)
   }
 }
 
-#[cfg(not(target_os = "unknown"))]
-#[cfg(test)]
-mod tests {
-  use super::*;
+impl models::SchemaSource for DocumentContext {
+  fn get_node(&self, retrieval_location: &NodeLocation) -> Option<serde_json::Value> {
+    DocumentContext::get_node(self, retrieval_location)
+  }
 
-  #[tokio::test]
-  async fn test_oas30() {
-    let context = DocumentContextContainer::default();
-    context.register_well_known_factories();
+  fn get_retrieval_location(&self, identity_location: &NodeLocation) -> Option<NodeLocation> {
+    DocumentContext::get_retrieval_location(self, identity_location)
+  }
 
-    let location = "../../../fixtures/specifications/echo.yaml#";
+  fn resolve_reference(
+    &self,
+    retrieval_location: &NodeLocation,
+    reference_location: &NodeLocation,
+  ) -> NodeLocation {
+    DocumentContext::resolve_reference(self, retrieval_location, reference_location)
+  }
+}
 
-    context.load_from_location(location).await.unwrap();
-    let api = context.get_api_model(location).unwrap();
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct DocumentContextContainer(sync::Shared<DocumentContext>);
 
-    assert_eq!(api.location(), location.to_string());
+#[wasm_bindgen]
+impl DocumentContextContainer {
+  #[wasm_bindgen(constructor)]
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  #[wasm_bindgen(js_name = "registerWellKnownFactories")]
+  pub fn register_well_known_factories(&self) {
+    self.0.register_well_known_factories();
+  }
+
+  #[wasm_bindgen(js_name = "setHttpHeader")]
+  pub fn set_http_header(&self, origin: &str, name: &str, value: &str) {
+    self
+      .0
+      .update_http_fetch_options(|options| options.set_header(origin, name, value));
+  }
+
+  #[wasm_bindgen(js_name = "setHttpBearerToken")]
+  pub fn set_http_bearer_token(&self, origin: &str, token: &str) {
+    self
+      .0
+      .update_http_fetch_options(|options| options.set_bearer_token(origin, token));
+  }
+
+  #[wasm_bindgen(js_name = "setHttpBasicAuthentication")]
+  pub fn set_http_basic_authentication(&self, origin: &str, username: &str, password: &str) {
+    self.0.update_http_fetch_options(|options| {
+      options.set_basic_authentication(origin, username, password)
+    });
+  }
+
+  #[wasm_bindgen(js_name = "setHttpTimeout")]
+  pub fn set_http_timeout(&self, milliseconds: Option<u32>) {
+    self.0.update_http_fetch_options(|options| {
+      options.timeout = milliseconds.map(|milliseconds| Duration::from_millis(milliseconds.into()))
+    });
+  }
+
+  #[wasm_bindgen(js_name = "setHttpMaximumRedirects")]
+  pub fn set_http_maximum_redirects(&self, maximum_redirects: usize) {
+    self
+      .0
+      .update_http_fetch_options(|options| options.maximum_redirects = maximum_redirects);
+  }
+
+  #[wasm_bindgen(js_name = "setHttpProxy")]
+  pub fn set_http_proxy(&self, proxy: Option<String>) {
+    self
+      .0
+      .update_http_fetch_options(|options| options.proxy = proxy);
+  }
+
+  /// Record the hashes of remote documents in a lock file in the directory and
+  /// fail when they change. When offline, remote documents are only loaded
+  /// from the directory.
+  ///
+  #[wasm_bindgen(js_name = "setFetchLock")]
+  pub fn set_fetch_lock(&self, directory: Option<String>, offline: bool) {
+    self
+      .0
+      .set_fetch_lock_options(directory.map(|directory| FetchLockOptions { directory, offline }));
+  }
+
+  /// Force the type of the document at the location instead of detecting it,
+  /// pass nothing to detect the type again.
+  ///
+  #[wasm_bindgen(js_name = "setDocumentType")]
+  pub fn set_document_type(
+    &self,
+    retrieval_location: &str,
+    document_type: Option<DocumentType>,
+  ) -> Result<(), Error> {
+    let retrieval_location = retrieval_location.parse()?;
+    self.0.set_document_type(&retrieval_location, document_type);
+    Ok(())
+  }
+
+  #[wasm_bindgen(js_name = "loadFromLocation")]
+  pub async fn load_from_location(&self, retrieval_location: &str) -> Result<(), Error> {
+    let retrieval_location = retrieval_location.parse()?;
+    self.0.load_from_location(&retrieval_location).await
+  }
+
+  /// Load a document from text that is already in memory, the text is not
+  /// fetched. Referenced documents are fetched.
+  ///
+  #[wasm_bindgen(js_name = "loadFromText")]
+  pub async fn load_from_text(
+    &self,
+    retrieval_location: &str,
+    text: &str,
+    format: TextFormat,
+  ) -> Result<(), Error> {
+    let retrieval_location = retrieval_location.parse()?;
+    self
+      .0
+      .load_from_text(&retrieval_location, text, format)
+      .await
+  }
+
+  /// Load a document from a (json compatible) javascript value. Referenced
+  /// documents are fetched.
+  ///
+  #[wasm_bindgen(js_name = "loadFromValue")]
+  pub async fn load_from_value(
+    &self,
+    retrieval_location: &str,
+    value: JsValue,
+  ) -> Result<(), Error> {
+    let retrieval_location = retrieval_location.parse()?;
+    let text = js_sys::JSON::stringify(&value)
+      .ok()
+      .and_then(|text| text.as_string())
+      .ok_or(Error::SerializationError)?;
+    let node = serde_json::from_str(&text).map_err(|_error| Error::SerializationError)?;
+    self.0.load_from_node(&retrieval_location, node).await
+  }
+
+  /// Reload a document that changed, returns the paths and operations that
+  /// changed as an array of `ApiChangeContainer`.
+  ///
+  #[wasm_bindgen(js_name = "reloadFromLocation")]
+  pub async fn reload_from_location(
+    &self,
+    retrieval_location: &str,
+  ) -> Result<js_sys::Array, Error> {
+    let retrieval_location = retrieval_location.parse()?;
+    let changes = self.0.reload_from_location(&retrieval_location).await?;
+
+    Ok(
+      changes
+        .into_iter()
+        .map(|change| JsValue::from(models::ApiChangeContainer::from(sync::Shared::new(change))))
+        .collect(),
+    )
+  }
+
+  /// The api model of a loaded document, nothing is returned when the
+  /// document is not loaded or does not describe an (http) api.
+  ///
+  #[wasm_bindgen(js_name = "getApiModel")]
+  pub fn get_api_model(
+    &self,
+    retrieval_location: &str,
+  ) -> Result<Option<models::ApiContainer>, Error> {
+    let retrieval_location = retrieval_location.parse()?;
+    match self.0.get_api_model(&retrieval_location) {
+      Ok(api_model) => Ok(Some(api_model.into())),
+      Err(Error::NotFound) => Ok(None),
+      Err(error) => Err(error),
+    }
+  }
+
+  #[wasm_bindgen(js_name = "diffApiModels")]
+  pub fn diff_api_models(
+    &self,
+    old_retrieval_location: &str,
+    new_retrieval_location: &str,
+  ) -> Result<models::ApiDiffContainer, Error> {
+    let old_retrieval_location = old_retrieval_location.parse()?;
+    let new_retrieval_location = new_retrieval_location.parse()?;
+    let api_diff = self
+      .0
+      .diff_api_models(&old_retrieval_location, &new_retrieval_location)?;
+
+    Ok(sync::Shared::new(api_diff).into())
+  }
+
+  /// Merge the api models of loaded documents, the path prefixes are paired
+  /// with the locations by position, missing prefixes are empty.
+  ///
+  #[wasm_bindgen(js_name = "mergeApiModels")]
+  pub fn merge_api_models(
+    &self,
+    retrieval_locations: Vec<String>,
+    path_prefixes: Vec<String>,
+  ) -> Result<models::ApiMergeContainer, Error> {
+    let sources = retrieval_locations
+      .iter()
+      .enumerate()
+      .map(|(index, retrieval_location)| {
+        let path_prefix = path_prefixes.get(index).cloned().unwrap_or_default();
+        Ok((retrieval_location.parse()?, path_prefix))
+      })
+      .collect::<Result<Vec<_>, Error>>()?;
+    let api_merge = self.0.merge_api_models(&sources)?;
+
+    Ok(sync::Shared::new(api_merge).into())
+  }
+
+  /// Slice the api model of a loaded document, the filter is an object like
+  /// `{ includeTags: ["pets"], excludeInternal: true }`.
+  ///
+  #[wasm_bindgen(js_name = "sliceApiModel")]
+  pub fn slice_api_model(
+    &self,
+    retrieval_location: &str,
+    filter: JsValue,
+  ) -> Result<models::ApiSliceContainer, Error> {
+    let retrieval_location = retrieval_location.parse()?;
+    let text = js_sys::JSON::stringify(&filter)
+      .ok()
+      .and_then(|text| text.as_string())
+      .ok_or(Error::SerializationError)?;
+    let filter = serde_json::from_str(&text).map_err(|_error| Error::SerializationError)?;
+    let api_slice = self.0.slice_api_model(&retrieval_location, &filter)?;
+
+    Ok(sync::Shared::new(api_slice).into())
+  }
+
+  /// Write an OpenApi document of the version of the document type from the
+  /// api model of a loaded document, returns the document as json text. The
+  /// issues of upgrading schemas are not returned.
+  ///
+  #[wasm_bindgen(js_name = "writeOpenApiDocument")]
+  pub fn write_openapi_document(
+    &self,
+    retrieval_location: &str,
+    document_type: DocumentType,
+  ) -> Result<String, Error> {
+    let retrieval_location = retrieval_location.parse()?;
+    let written_document = self
+      .0
+      .write_openapi_document(&retrieval_location, document_type)?;
+    serde_json::to_string_pretty(&written_document.node).map_err(|_error| Error::SerializationError)
+  }
+
+  /// The async api model of a loaded document, nothing is returned when the
+  /// document is not loaded or does not describe an async api.
+  ///
+  #[wasm_bindgen(js_name = "getAsyncApiModel")]
+  pub fn get_async_api_model(
+    &self,
+    retrieval_location: &str,
+  ) -> Result<Option<models::AsyncApiContainer>, Error> {
+    let retrieval_location = retrieval_location.parse()?;
+    match self.0.get_async_api_model(&retrieval_location) {
+      Ok(async_api_model) => Ok(Some(async_api_model.into())),
+      Err(Error::NotFound) => Ok(None),
+      Err(error) => Err(error),
+    }
+  }
+
+  #[wasm_bindgen(js_name = "getSchemas")]
+  pub fn get_schemas(&self) -> Result<Vec<DocumentSchemaContainer>, Error> {
+    Ok(
+      self
+        .0
+        .get_schemas()?
+        .into_iter()
+        .map(|schema| sync::Shared::new(schema).into())
+        .collect(),
+    )
+  }
+}
+
+impl DocumentContextContainer {
+  pub fn set_fetcher(&self, fetcher: impl Fetcher + 'static) {
+    self.0.set_fetcher(fetcher);
+  }
+}
+
+pub struct DocumentSchema {
+  pub schema_location: NodeLocation,
+  pub schema_id: NodeLocation,
+  pub anchor_locations: Vec<NodeLocation>,
+  pub document_location: NodeLocation,
+  pub default_schema_id: String,
+}
+
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct DocumentSchemaContainer(sync::Shared<DocumentSchema>);
+
+#[wasm_bindgen]
+impl DocumentSchemaContainer {
+  #[wasm_bindgen(getter, js_name = "schemaLocation")]
+  pub fn schema_location(&self) -> String {
+    self.0.schema_location.to_string()
+  }
+
+  #[wasm_bindgen(getter, js_name = "schemaId")]
+  pub fn schema_id(&self) -> String {
+    self.0.schema_id.to_string()
+  }
+
+  #[wasm_bindgen(getter, js_name = "anchorLocations")]
+  pub fn anchor_locations(&self) -> Vec<String> {
+    self
+      .0
+      .anchor_locations
+      .iter()
+      .map(|location| location.to_string())
+      .collect()
+  }
+
+  #[wasm_bindgen(getter, js_name = "documentLocation")]
+  pub fn document_location(&self) -> String {
+    self.0.document_location.to_string()
+  }
+
+  #[wasm_bindgen(getter, js_name = "defaultSchemaId")]
+  pub fn default_schema_id(&self) -> String {
+    self.0.default_schema_id.clone()
+  }
+}
+
+impl From<sync::Shared<DocumentSchema>> for DocumentSchemaContainer {
+  fn from(interior: sync::Shared<DocumentSchema>) -> Self {
+    Self(interior)
+  }
+}
+
+#[cfg(not(target_os = "unknown"))]
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::documents::common;
+
+  /// A context with the well known factories registered.
+  ///
+  fn new_test_context() -> DocumentContextContainer {
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+    context
+  }
+
+  /// Load `node` as the document at `location`, and everything it references.
+  ///
+  async fn load_test_node(
+    context: &DocumentContextContainer,
+    location: &str,
+    node: serde_json::Value,
+  ) {
+    context
+      .0
+      .load_from_node(&location.parse().unwrap(), node)
+      .await
+      .unwrap();
+  }
+
+  #[tokio::test]
+  async fn test_oas30() {
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+
+    let location = "../../../fixtures/specifications/echo.yaml#";
+
+    context.load_from_location(location).await.unwrap();
+    let api = context.get_api_model(location).unwrap().unwrap();
+
+    assert_eq!(api.location(), location.to_string());
 
     for path in api.paths() {
       assert!(path.id() > 0);
@@ -228,4 +1223,1557 @@ mod tests {
       }
     }
   }
+
+  #[tokio::test]
+  async fn test_oas30_and_oas32() {
+    let context = new_test_context();
+
+    let node = |version: &str| {
+      serde_json::json!({
+        "openapi": version,
+        "paths": {
+          "/pets": {
+            "get": {},
+            "additionalOperations": { "COPY": { "operationId": "copy-pets" } },
+            "parameters": [
+              { "name": "filter", "in": "querystring", "content": { "application/json": { "schema": {} } } }
+            ]
+          }
+        },
+        "tags": [{ "name": "pets", "summary": "Pets" }]
+      })
+    };
+
+    let location: NodeLocation = "memory:/v30.yaml".parse().unwrap();
+    context
+      .0
+      .load_from_node(&location, node("3.0.3"))
+      .await
+      .unwrap();
+    let api = context.0.get_api_model(&location).unwrap();
+    let operations = &api.paths[0].operations;
+    assert_eq!(operations.len(), 1);
+    // an operation without an operationId is named after it's method and path
+    assert_eq!(operations[0].name, "get /pets");
+    assert!(operations[0].querystring_parameters.is_empty());
+    assert_eq!(api.tags[0].summary, None);
+
+    let location: NodeLocation = "memory:/v32.yaml".parse().unwrap();
+    context
+      .0
+      .load_from_node(&location, node("3.2.0"))
+      .await
+      .unwrap();
+    let api = context.0.get_api_model(&location).unwrap();
+    let operations = &api.paths[0].operations;
+    assert_eq!(operations.len(), 2);
+    assert_eq!(operations[1].name, "copy-pets");
+    assert_eq!(operations[0].querystring_parameters.len(), 1);
+    assert_eq!(api.tags[0].summary.as_deref(), Some("Pets"));
+  }
+
+  #[tokio::test]
+  async fn test_load_concurrently() {
+    use crate::utils::{FetchTextFuture, MemoryFetcher};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// counts how many documents are fetched at the same time
+    struct CountingFetcher {
+      inner: MemoryFetcher,
+      current: sync::Shared<AtomicUsize>,
+      maximum: sync::Shared<AtomicUsize>,
+    }
+
+    impl Fetcher for CountingFetcher {
+      fn fetch_text<'a>(&'a self, location: &'a NodeLocation) -> FetchTextFuture<'a> {
+        Box::pin(async move {
+          let current = self.current.fetch_add(1, Ordering::SeqCst) + 1;
+          self.maximum.fetch_max(current, Ordering::SeqCst);
+          for _ in 0..4 {
+            tokio::task::yield_now().await;
+          }
+          self.current.fetch_sub(1, Ordering::SeqCst);
+          self.inner.fetch_text(location).await
+        })
+      }
+    }
+
+    let count = 20;
+    let mut inner = MemoryFetcher::new();
+    let paths: serde_json::Map<_, _> = (0..count)
+      .map(|index| {
+        (
+          format!("/{}", index),
+          serde_json::json!({ "$ref": format!("path-{}.yaml#/paths/~1", index) }),
+        )
+      })
+      .collect();
+    inner.insert(
+      &"http://example.com/api.yaml".parse().unwrap(),
+      serde_json::json!({ "openapi": "3.0.3", "paths": paths }).to_string(),
+    );
+    for index in 0..count {
+      inner.insert(
+        &format!("http://example.com/path-{}.yaml", index)
+          .parse()
+          .unwrap(),
+        serde_json::json!({ "openapi": "3.0.3", "paths": { "/": {} } }).to_string(),
+      );
+    }
+
+    let current = sync::Shared::new(AtomicUsize::new(0));
+    let maximum = sync::Shared::new(AtomicUsize::new(0));
+    let context = new_test_context();
+    context.set_fetcher(CountingFetcher {
+      inner,
+      current: current.clone(),
+      maximum: maximum.clone(),
+    });
+
+    context
+      .load_from_location("http://example.com/api.yaml")
+      .await
+      .unwrap();
+
+    assert_eq!(context.0.documents.borrow().len(), count + 1);
+    let maximum = maximum.load(Ordering::SeqCst);
+    assert!(maximum > 1);
+    assert!(maximum <= MAXIMUM_CONCURRENT_FETCHES);
+  }
+
+  #[tokio::test]
+  async fn test_load_overlapping() {
+    use crate::utils::{FetchTextFuture, MemoryFetcher};
+    use tokio::sync::Barrier;
+
+    /// lets two fetches of the entry document finish at the same time
+    struct BarrierFetcher {
+      inner: MemoryFetcher,
+      entry_location: NodeLocation,
+      barrier: Barrier,
+    }
+
+    impl Fetcher for BarrierFetcher {
+      fn fetch_text<'a>(&'a self, location: &'a NodeLocation) -> FetchTextFuture<'a> {
+        Box::pin(async move {
+          if *location == self.entry_location {
+            self.barrier.wait().await;
+          }
+          self.inner.fetch_text(location).await
+        })
+      }
+    }
+
+    let location = "http://example.com/api.yaml";
+    let context = new_test_context();
+    context.set_fetcher(BarrierFetcher {
+      inner: MemoryFetcher::from_iter([
+        (
+          location.parse::<NodeLocation>().unwrap(),
+          serde_json::json!({
+            "openapi": "3.0.3",
+            "paths": { "/": { "$ref": "path.yaml#/paths/~1" } },
+          })
+          .to_string(),
+        ),
+        (
+          "http://example.com/path.yaml"
+            .parse::<NodeLocation>()
+            .unwrap(),
+          serde_json::json!({ "openapi": "3.0.3", "paths": { "/": {} } }).to_string(),
+        ),
+      ]),
+      entry_location: location.parse().unwrap(),
+      barrier: Barrier::new(2),
+    });
+
+    let (first, second) = futures::join!(
+      context.load_from_location(location),
+      context.load_from_location(location),
+    );
+    first.unwrap();
+    second.unwrap();
+
+    assert_eq!(context.0.documents.borrow().len(), 2);
+    assert_eq!(
+      context
+        .get_api_model(location)
+        .unwrap()
+        .unwrap()
+        .paths()
+        .len(),
+      1
+    );
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn test_multi_threaded() {
+    use crate::utils::{FetchTextFuture, MemoryFetcher};
+    use tokio::sync::Barrier;
+
+    fn assert_send_sync<T: Send + Sync>(_value: &T) {}
+
+    /// makes every task wait for the others in the fetch, so all loads overlap
+    struct BarrierFetcher {
+      inner: MemoryFetcher,
+      barrier: Barrier,
+    }
+
+    impl Fetcher for BarrierFetcher {
+      fn fetch_text<'a>(&'a self, location: &'a NodeLocation) -> FetchTextFuture<'a> {
+        Box::pin(async move {
+          self.barrier.wait().await;
+          self.inner.fetch_text(location).await
+        })
+      }
+    }
+
+    let count = 4;
+    let location = "http://example.com/api.yaml";
+    let context = new_test_context();
+    context.set_fetcher(BarrierFetcher {
+      inner: MemoryFetcher::from_iter([(
+        location.parse::<NodeLocation>().unwrap(),
+        serde_json::json!({ "openapi": "3.0.3", "paths": { "/": {} } }).to_string(),
+      )]),
+      barrier: Barrier::new(count),
+    });
+    assert_send_sync(&context);
+
+    let context = sync::Shared::new(context);
+    let tasks: Vec<_> = (0..count)
+      .map(|_| {
+        let context = context.clone();
+        tokio::spawn(async move {
+          context.load_from_location(location).await.unwrap();
+          context
+            .get_api_model(location)
+            .unwrap()
+            .unwrap()
+            .paths()
+            .len()
+        })
+      })
+      .collect();
+
+    for task in tasks {
+      assert_eq!(task.await.unwrap(), 1);
+    }
+  }
+
+  #[tokio::test]
+  async fn test_reload_from_location() {
+    use crate::models::{ApiChange, ChangeKind, Method};
+    use crate::utils::{FetchTextError, FetchTextFuture};
+    use std::sync::Mutex;
+
+    /// serves documents that can be changed
+    #[derive(Clone, Default)]
+    struct ChangingFetcher(sync::Shared<Mutex<BTreeMap<NodeLocation, String>>>);
+
+    impl Fetcher for ChangingFetcher {
+      fn fetch_text<'a>(&'a self, location: &'a NodeLocation) -> FetchTextFuture<'a> {
+        let text = self.0.lock().unwrap().get(location).cloned();
+        Box::pin(async move { text.ok_or(FetchTextError::NotFound) })
+      }
+    }
+
+    let api_location: NodeLocation = "http://example.com/api.yaml".parse().unwrap();
+    let paths_location: NodeLocation = "http://example.com/paths.yaml".parse().unwrap();
+
+    let fetcher = ChangingFetcher::default();
+    let set_document = |location: &NodeLocation, node: serde_json::Value| {
+      fetcher
+        .0
+        .lock()
+        .unwrap()
+        .insert(location.clone(), node.to_string());
+    };
+    set_document(
+      &api_location,
+      serde_json::json!({
+        "openapi": "3.0.3",
+        "paths": {
+          "/a": { "get": { "operationId": "a-get" }, "put": { "operationId": "a-put" } },
+          "/b": { "get": { "operationId": "b-get" } },
+        }
+      }),
+    );
+
+    let context = DocumentContext::new();
+    let context = DocumentContextContainer(sync::Shared::new(context));
+    context.register_well_known_factories();
+    context.set_fetcher(fetcher.clone());
+    context.0.load_from_location(&api_location).await.unwrap();
+
+    // nothing changed
+    let changes = context.0.reload_from_location(&api_location).await.unwrap();
+    assert_eq!(changes, vec![]);
+
+    set_document(
+      &api_location,
+      serde_json::json!({
+        "openapi": "3.0.3",
+        "paths": {
+          "/a": {
+            "get": { "operationId": "a-get", "summary": "a" },
+            "post": { "operationId": "a-post" }
+          },
+          "/c": { "$ref": "paths.yaml#/paths/~1c" },
+        }
+      }),
+    );
+    set_document(
+      &paths_location,
+      serde_json::json!({
+        "openapi": "3.0.3",
+        "paths": {
+          "/c": { "get": { "operationId": "c-get" } },
+        }
+      }),
+    );
+
+    let changes = context.0.reload_from_location(&api_location).await.unwrap();
+    let change = |location: &NodeLocation, kind, pattern: &str, method| ApiChange {
+      kind,
+      api_location: location.clone(),
+      pattern: pattern.to_owned(),
+      method,
+    };
+    assert_eq!(
+      changes,
+      vec![
+        change(&api_location, ChangeKind::Changed, "/a", None),
+        change(&api_location, ChangeKind::Changed, "/a", Some(Method::Get)),
+        change(&api_location, ChangeKind::Removed, "/a", Some(Method::Put)),
+        change(&api_location, ChangeKind::Added, "/a", Some(Method::Post)),
+        change(&api_location, ChangeKind::Removed, "/b", None),
+        change(&api_location, ChangeKind::Removed, "/b", Some(Method::Get)),
+        change(&api_location, ChangeKind::Added, "/c", None),
+        change(&api_location, ChangeKind::Added, "/c", Some(Method::Get)),
+        change(&paths_location, ChangeKind::Added, "/c", None),
+        change(&paths_location, ChangeKind::Added, "/c", Some(Method::Get)),
+      ]
+    );
+    assert!(context.0.documents.borrow().contains_key(&paths_location));
+
+    // a change in a referenced document also changes the referencing api
+    set_document(
+      &paths_location,
+      serde_json::json!({
+        "openapi": "3.0.3",
+        "paths": {
+          "/c": { "get": { "operationId": "c-get", "deprecated": true } },
+        }
+      }),
+    );
+
+    let changes = context
+      .0
+      .reload_from_location(&paths_location)
+      .await
+      .unwrap();
+    assert_eq!(
+      changes,
+      vec![
+        change(&api_location, ChangeKind::Changed, "/c", None),
+        change(&api_location, ChangeKind::Changed, "/c", Some(Method::Get)),
+        change(&paths_location, ChangeKind::Changed, "/c", None),
+        change(
+          &paths_location,
+          ChangeKind::Changed,
+          "/c",
+          Some(Method::Get)
+        ),
+      ]
+    );
+
+    // a document that the new document references can not be fetched,
+    // nothing changes
+    let old_node = context.0.get_node(&api_location).unwrap();
+    let old_api = context.0.get_api_model(&api_location).unwrap();
+    set_document(
+      &api_location,
+      serde_json::json!({
+        "openapi": "3.0.3",
+        "paths": {
+          "/d": { "$ref": "missing.yaml#/paths/~1d" },
+          "/e": { "$ref": "paths-e.yaml#/paths/~1e" },
+        }
+      }),
+    );
+    set_document(
+      &"http://example.com/paths-e.yaml".parse().unwrap(),
+      serde_json::json!({ "openapi": "3.0.3", "paths": { "/e": {} } }),
+    );
+    assert!(context.0.reload_from_location(&api_location).await.is_err());
+    assert_eq!(context.0.get_node(&api_location), Some(old_node));
+    assert_eq!(context.0.get_api_model(&api_location).unwrap(), old_api);
+    assert!(context
+      .0
+      .get_node(&"http://example.com/paths-e.yaml".parse().unwrap())
+      .is_none());
+    assert_eq!(
+      context
+        .0
+        .documents
+        .borrow()
+        .keys()
+        .cloned()
+        .collect::<Vec<_>>(),
+      vec![api_location.clone(), paths_location.clone()]
+    );
+
+    // the document can not be fetched, nothing changes
+    fetcher.0.lock().unwrap().remove(&api_location);
+    assert!(context.0.reload_from_location(&api_location).await.is_err());
+    assert!(context.0.documents.borrow().contains_key(&api_location));
+  }
+
+  #[tokio::test]
+  async fn test_load_from_text() {
+    use crate::utils::MemoryFetcher;
+
+    let context = new_test_context();
+    context.set_fetcher(MemoryFetcher::from_iter([(
+      "http://example.com/paths.yaml"
+        .parse::<NodeLocation>()
+        .unwrap(),
+      "openapi: 3.0.3\npaths:\n  /b:\n    get:\n      operationId: b-get\n",
+    )]));
+
+    let location: NodeLocation = "memory:/api.yaml".parse().unwrap();
+    let text = r#"
+openapi: 3.0.3
+paths:
+  /a:
+    get:
+      operationId: a-get
+  /b:
+    $ref: http://example.com/paths.yaml#/paths/~1b
+"#;
+    context
+      .0
+      .load_from_text(&location, text, TextFormat::Yaml)
+      .await
+      .unwrap();
+
+    let api = context
+      .get_api_model(&location.to_string())
+      .unwrap()
+      .unwrap();
+    assert_eq!(api.paths().len(), 2);
+    assert!(context
+      .0
+      .documents
+      .borrow()
+      .contains_key(&"http://example.com/paths.yaml".parse().unwrap()));
+
+    // loading the same text again is fine, other text is a conflict
+    context
+      .0
+      .load_from_text(&location, text, TextFormat::Yaml)
+      .await
+      .unwrap();
+    let result = context
+      .0
+      .load_from_text(&location, "openapi: 3.0.3\npaths: {}\n", TextFormat::Yaml)
+      .await;
+    assert_eq!(result, Err(Error::Conflict));
+
+    let location: NodeLocation = "memory:/other.json".parse().unwrap();
+    let result = context
+      .0
+      .load_from_text(&location, "openapi: 3.0.3", TextFormat::Json)
+      .await;
+    assert_eq!(result, Err(Error::SerializationError));
+
+    // the document can not be loaded, nothing stays behind so other text can
+    // be loaded
+    let result = context
+      .0
+      .load_from_text(
+        &location,
+        r#"{"openapi": "3.0.3", "paths": {"/c": {"$ref": "http://example.com/missing.yaml#/paths/~1c"}}}"#,
+        TextFormat::Json,
+      )
+      .await;
+    assert!(result.is_err());
+    assert!(context.0.get_node(&location).is_none());
+    assert!(!context.0.documents.borrow().contains_key(&location));
+
+    context
+      .0
+      .load_from_text(&location, r#"{"openapi": "3.0.3"}"#, TextFormat::Json)
+      .await
+      .unwrap();
+  }
+
+  #[tokio::test]
+  async fn test_document_type() {
+    let context = new_test_context();
+
+    let location: NodeLocation = "memory:/api.yaml".parse().unwrap();
+    let node = serde_json::json!({
+      "paths": { "/a": { "get": { "operationId": "a-get" } } }
+    });
+
+    let result = context.0.load_from_node(&location, node.clone()).await;
+    assert_eq!(result, Err(Error::DocumentTypeError));
+
+    context.0.invalidate_location(&location);
+    context
+      .0
+      .set_document_type(&location, Some(DocumentType::OpenApiV30));
+    context.0.load_from_node(&location, node).await.unwrap();
+
+    let api = context
+      .get_api_model(&location.to_string())
+      .unwrap()
+      .unwrap();
+    assert_eq!(api.paths().len(), 1);
+
+    let location: NodeLocation = "memory:/preview.yaml".parse().unwrap();
+    let node = serde_json::json!({ "openapi": "4.0.0", "paths": {} });
+    let result = context.0.load_from_node(&location, node).await;
+    assert_eq!(result, Err(Error::UnsupportedVersion));
+
+    // errors other than not found are returned, not unwrapped
+    let location: NodeLocation = "memory:/swagger.yaml".parse().unwrap();
+    let node = serde_json::json!({ "swagger": "2.0", "paths": {} });
+    context.0.load_from_node(&location, node).await.unwrap();
+    assert_eq!(
+      context.get_api_model(&location.to_string()).err(),
+      Some(Error::NotImplemented)
+    );
+    assert!(context
+      .get_async_api_model(&location.to_string())
+      .unwrap()
+      .is_none());
+    assert!(context
+      .get_api_model("memory:/missing.yaml")
+      .unwrap()
+      .is_none());
+  }
+
+  #[tokio::test]
+  async fn test_oas32() {
+    let context = new_test_context();
+
+    let location = "http://example.com/api.yaml";
+    let node = serde_json::json!({
+      "openapi": "3.2.0",
+      "$self": "https://example.com/apis/pets",
+      "tags": [
+        { "name": "animals", "kind": "nav" },
+        { "name": "pets", "summary": "Pets", "parent": "animals" }
+      ],
+      "paths": {
+        "/pets": {
+          "query": {
+            "operationId": "search-pets",
+            "tags": ["pets"],
+            "parameters": [
+              {
+                "name": "filter",
+                "in": "querystring",
+                "content": {
+                  "application/x-www-form-urlencoded": {
+                    "schema": { "type": "object" }
+                  }
+                }
+              }
+            ],
+            "responses": {
+              "200": {
+                "description": "ok",
+                "content": {
+                  "application/jsonl": {
+                    "itemSchema": { "$ref": "#/components/schemas/pet" }
+                  }
+                }
+              }
+            }
+          },
+          "additionalOperations": {
+            "LINK": { "operationId": "link-pets", "responses": {} }
+          }
+        }
+      },
+      "components": {
+        "schemas": {
+          "pet": { "type": "object" }
+        },
+        "securitySchemes": {
+          "oauth": {
+            "type": "oauth2",
+            "flows": {
+              "deviceAuthorization": {
+                "deviceAuthorizationUrl": "https://example.com/device",
+                "tokenUrl": "https://example.com/token",
+                "scopes": { "read": "read pets" }
+              }
+            }
+          }
+        }
+      }
+    });
+    load_test_node(&context, location, node).await;
+    let api = context.get_api_model(location).unwrap().unwrap();
+
+    assert_eq!(
+      api.self_location(),
+      Some("https://example.com/apis/pets#".to_owned())
+    );
+
+    let tags = api.tags();
+    assert_eq!(tags.len(), 2);
+    assert_eq!(tags[0].kind(), Some("nav".to_owned()));
+    assert_eq!(tags[1].parent(), Some("animals".to_owned()));
+    assert_eq!(tags[1].summary(), Some("Pets".to_owned()));
+
+    let operations: BTreeMap<_, _> = api.paths()[0]
+      .operations()
+      .into_iter()
+      .map(|operation| (operation.method(), operation))
+      .collect();
+    assert_eq!(
+      operations.keys().cloned().collect::<Vec<_>>(),
+      vec!["LINK".to_owned(), "query".to_owned()]
+    );
+
+    let operation = &operations["query"];
+    assert_eq!(operation.tags(), vec!["pets".to_owned()]);
+    let parameters = operation.querystring_parameters();
+    assert_eq!(parameters.len(), 1);
+    assert_eq!(parameters[0].name(), "filter");
+    assert!(parameters[0].schema_id().is_some());
+
+    let body = &operation.operation_results()[0].bodies()[0];
+    assert_eq!(body.schema_id(), None);
+    assert_eq!(
+      body.item_schema_id(),
+      Some(
+        "https://example.com/apis/pets#/paths/~1pets/query/responses/200/content/application~1jsonl/itemSchema"
+          .to_owned()
+      )
+    );
+
+    let flows = api.authentication()[0].flows();
+    assert_eq!(flows.len(), 1);
+    assert_eq!(flows[0].kind(), "deviceAuthorization");
+    assert_eq!(
+      flows[0].device_authorization_url(),
+      Some("https://example.com/device".to_owned())
+    );
+    assert_eq!(flows[0].scopes(), vec!["read".to_owned()]);
+
+    // references resolve against `$self`
+    let actual = context.0.resolve_reference(
+      &"http://example.com/api.yaml#/paths".parse().unwrap(),
+      &"#/components/schemas/pet".parse().unwrap(),
+    );
+    let expected: NodeLocation = "http://example.com/api.yaml#/components/schemas/pet"
+      .parse()
+      .unwrap();
+    assert_eq!(actual, expected);
+  }
+
+  #[tokio::test]
+  async fn test_asyncapi2() {
+    let context = new_test_context();
+
+    let location = "http://example.com/events.yaml";
+    let node = serde_json::json!({
+      "asyncapi": "2.6.0",
+      "defaultContentType": "application/json",
+      "channels": {
+        "user/signedup": {
+          "bindings": { "kafka": { "topic": "users" } },
+          "publish": {
+            "operationId": "on-user-signed-up",
+            "message": { "$ref": "#/components/messages/user-signed-up" }
+          },
+          "subscribe": {
+            "operationId": "send-user-signed-up",
+            "message": {
+              "oneOf": [
+                { "$ref": "#/components/messages/user-signed-up" },
+                {
+                  "name": "avro",
+                  "schemaFormat": "application/vnd.apache.avro;version=1.9.0",
+                  "payload": { "type": "record" }
+                }
+              ]
+            }
+          }
+        }
+      },
+      "components": {
+        "messages": {
+          "user-signed-up": {
+            "name": "UserSignedUp",
+            "headers": { "type": "object" },
+            "payload": { "$ref": "#/components/schemas/user" }
+          }
+        },
+        "schemas": {
+          "user": { "type": "object" }
+        }
+      }
+    });
+    load_test_node(&context, location, node).await;
+    assert!(context.get_api_model(location).unwrap().is_none());
+    let api = context.get_async_api_model(location).unwrap().unwrap();
+
+    let channels = api.channels();
+    assert_eq!(channels.len(), 1);
+    assert_eq!(channels[0].address(), Some("user/signedup".to_owned()));
+    assert_eq!(channels[0].messages().len(), 2);
+    assert_eq!(channels[0].bindings()[0].protocol(), "kafka");
+
+    let operations = api.operations();
+    assert_eq!(operations.len(), 2);
+    assert_eq!(operations[0].action(), models::OperationAction::Receive);
+    assert_eq!(operations[0].name(), Some("on-user-signed-up".to_owned()));
+    assert_eq!(operations[0].channel_name(), "user/signedup");
+    assert_eq!(operations[1].action(), models::OperationAction::Send);
+
+    let message = &operations[0].messages()[0];
+    assert_eq!(message.name(), Some("UserSignedUp".to_owned()));
+    assert_eq!(message.content_type(), Some("application/json".to_owned()));
+    assert_eq!(
+      message.payload_schema_id(),
+      Some("http://example.com/events.yaml#/components/messages/user-signed-up/payload".to_owned())
+    );
+    assert_eq!(
+      message.headers_schema_id(),
+      Some("http://example.com/events.yaml#/components/messages/user-signed-up/headers".to_owned())
+    );
+
+    // avro is not a json schema
+    let message = &operations[1].messages()[1];
+    assert_eq!(message.payload_schema_id(), None);
+
+    let schemas: BTreeSet<_> = context
+      .get_schemas()
+      .unwrap()
+      .into_iter()
+      .map(|schema| schema.schema_location())
+      .collect();
+    assert!(schemas.contains("http://example.com/events.yaml#/components/schemas/user"));
+    assert!(schemas
+      .contains("http://example.com/events.yaml#/components/messages/user-signed-up/headers"));
+  }
+
+  #[tokio::test]
+  async fn test_asyncapi3() {
+    let context = new_test_context();
+
+    let location = "http://example.com/events.yaml";
+    let node = serde_json::json!({
+      "asyncapi": "3.0.0",
+      "channels": {
+        "user-signed-up": {
+          "address": "user/signedup",
+          "messages": {
+            "user-signed-up": { "$ref": "#/components/messages/user-signed-up" }
+          }
+        },
+        "chat": {
+          "address": null,
+          "bindings": { "ws": {} },
+          "messages": {
+            "text": { "contentType": "text/plain", "payload": { "type": "string" } },
+            "json": {
+              "payload": {
+                "schemaFormat": "application/schema+json;version=draft-07",
+                "schema": { "type": "object" }
+              }
+            }
+          }
+        }
+      },
+      "operations": {
+        "on-user-signed-up": {
+          "action": "receive",
+          "channel": { "$ref": "#/channels/user-signed-up" },
+          "messages": [
+            { "$ref": "#/channels/user-signed-up/messages/user-signed-up" }
+          ]
+        },
+        "send-chat": {
+          "action": "send",
+          "channel": { "$ref": "#/channels/chat" }
+        }
+      },
+      "components": {
+        "messages": {
+          "user-signed-up": {
+            "name": "UserSignedUp",
+            "payload": { "type": "object" }
+          }
+        }
+      }
+    });
+    load_test_node(&context, location, node).await;
+    let api = context.get_async_api_model(location).unwrap().unwrap();
+
+    let channels: BTreeMap<_, _> = api
+      .channels()
+      .into_iter()
+      .map(|channel| (channel.name(), channel))
+      .collect();
+    assert_eq!(channels["chat"].address(), None);
+    assert_eq!(channels["chat"].bindings()[0].protocol(), "ws");
+    assert_eq!(
+      channels["user-signed-up"].address(),
+      Some("user/signedup".to_owned())
+    );
+
+    let operations: BTreeMap<_, _> = api
+      .operations()
+      .into_iter()
+      .map(|operation| (operation.name().unwrap(), operation))
+      .collect();
+
+    let operation = &operations["on-user-signed-up"];
+    assert_eq!(operation.action(), models::OperationAction::Receive);
+    assert_eq!(operation.channel_name(), "user-signed-up");
+    let messages = operation.messages();
+    assert_eq!(messages.len(), 1);
+    assert_eq!(
+      messages[0].location(),
+      "http://example.com/events.yaml#/components/messages/user-signed-up"
+    );
+    assert_eq!(
+      messages[0].payload_schema_id(),
+      Some("http://example.com/events.yaml#/components/messages/user-signed-up/payload".to_owned())
+    );
+
+    // without messages the operation has all messages of the channel
+    let operation = &operations["send-chat"];
+    assert_eq!(operation.action(), models::OperationAction::Send);
+    let messages: BTreeMap<_, _> = operation
+      .messages()
+      .into_iter()
+      .map(|message| (message.location(), message))
+      .collect();
+    assert_eq!(messages.len(), 2);
+    let message = &messages["http://example.com/events.yaml#/channels/chat/messages/json"];
+    assert_eq!(
+      message.payload_schema_id(),
+      Some("http://example.com/events.yaml#/channels/chat/messages/json/payload/schema".to_owned())
+    );
+    let message = &messages["http://example.com/events.yaml#/channels/chat/messages/text"];
+    assert_eq!(message.content_type(), Some("text/plain".to_owned()));
+  }
+
+  #[tokio::test]
+  async fn test_asyncapi_message_order() {
+    let context = new_test_context();
+
+    let names: Vec<_> = (0..12).map(|index| format!("message-{}", index)).collect();
+
+    let location = "http://example.com/events-2.yaml";
+    let node = serde_json::json!({
+      "asyncapi": "2.6.0",
+      "channels": {
+        "events": {
+          "publish": {
+            "operationId": "on-event",
+            "message": {
+              "oneOf": names
+                .iter()
+                .map(|name| serde_json::json!({ "name": name }))
+                .collect::<Vec<_>>()
+            }
+          }
+        }
+      }
+    });
+    load_test_node(&context, location, node).await;
+    let api = context.get_async_api_model(location).unwrap().unwrap();
+    let message_names: Vec<_> = api.operations()[0]
+      .messages()
+      .into_iter()
+      .map(|message| message.name().unwrap())
+      .collect();
+    assert_eq!(message_names, names);
+
+    let location = "http://example.com/events-3.yaml";
+    let node = serde_json::json!({
+      "asyncapi": "3.0.0",
+      "channels": {
+        "events": {
+          "messages": names
+            .iter()
+            .map(|name| (name.clone(), serde_json::json!({ "name": name })))
+            .collect::<serde_json::Map<_, _>>()
+        }
+      },
+      "operations": {
+        "on-event": {
+          "action": "receive",
+          "channel": { "$ref": "#/channels/events" },
+          "messages": names
+            .iter()
+            .rev()
+            .map(|name| serde_json::json!({ "$ref": format!("#/channels/events/messages/{}", name) }))
+            .collect::<Vec<_>>()
+        }
+      }
+    });
+    load_test_node(&context, location, node).await;
+    let api = context.get_async_api_model(location).unwrap().unwrap();
+    let message_names: Vec<_> = api.operations()[0]
+      .messages()
+      .into_iter()
+      .map(|message| message.name().unwrap())
+      .collect();
+    assert_eq!(
+      message_names,
+      names.iter().rev().cloned().collect::<Vec<_>>()
+    );
+  }
+
+  #[tokio::test]
+  async fn test_asyncapi3_invalid_action() {
+    let location: NodeLocation = "http://example.com/events.yaml".parse().unwrap();
+    let node = serde_json::json!({
+      "asyncapi": "3.0.0",
+      "channels": {
+        "events": {}
+      },
+      "operations": {
+        "on-event": {
+          "channel": { "$ref": "#/channels/events" }
+        }
+      }
+    });
+
+    let context = new_test_context();
+    load_test_node(&context, &location.to_string(), node).await;
+
+    assert_eq!(
+      context.0.get_async_api_model(&location).unwrap_err(),
+      Error::ParseOperationActionFailed
+    );
+  }
+
+  #[tokio::test]
+  async fn test_diff_api_models() {
+    let context = new_test_context();
+
+    let old_location: NodeLocation = "http://example.com/v1.yaml".parse().unwrap();
+    let old_node = serde_json::json!({
+      "openapi": "3.0.3",
+      "paths": {
+        "/pets/{id}": {
+          "get": {
+            "operationId": "get-pet",
+            "parameters": [
+              { "name": "id", "in": "path", "required": true, "schema": { "type": "string" } },
+              { "name": "fields", "in": "query", "schema": { "type": "string" } }
+            ],
+            "responses": {
+              "200": {
+                "description": "ok",
+                "content": {
+                  "application/json": {
+                    "schema": { "$ref": "#/components/schemas/pet" }
+                  }
+                }
+              },
+              "404": { "description": "not found" }
+            }
+          },
+          "delete": { "operationId": "delete-pet", "responses": {} }
+        },
+        "/toys": {
+          "put": {
+            "operationId": "put-toy",
+            "requestBody": {
+              "content": {
+                "application/json": {
+                  "schema": { "$ref": "#/components/schemas/toy" }
+                }
+              }
+            },
+            "responses": {
+              "200": {
+                "description": "ok",
+                "content": {
+                  "application/json": {
+                    "schema": { "$ref": "#/components/schemas/toy" }
+                  }
+                }
+              }
+            }
+          }
+        }
+      },
+      "components": {
+        "schemas": {
+          "pet": {
+            "type": "object",
+            "required": ["name"],
+            "properties": {
+              "name": { "type": "string" },
+              "kind": { "enum": ["cat", "dog"] }
+            }
+          },
+          "toy": {
+            "type": "object",
+            "properties": {
+              "name": { "type": "string" },
+              "color": { "type": "string" }
+            }
+          }
+        }
+      }
+    });
+    let new_location: NodeLocation = "http://example.com/v2.yaml".parse().unwrap();
+    let new_node = serde_json::json!({
+      "openapi": "3.0.3",
+      "paths": {
+        "/pets/{petId}": {
+          "get": {
+            "operationId": "get-pet",
+            "parameters": [
+              // path parameters are matched by position, not by name
+              { "name": "petId", "in": "path", "required": true, "schema": { "type": "string" } },
+              { "name": "fields", "in": "query", "required": true, "schema": { "type": "string" } }
+            ],
+            "responses": {
+              "200": {
+                "description": "ok",
+                "content": {
+                  "application/json": {
+                    "schema": { "$ref": "#/components/schemas/pet" }
+                  },
+                  "application/xml": {}
+                }
+              }
+            }
+          }
+        },
+        "/toys": {
+          "put": {
+            "operationId": "put-toy",
+            "requestBody": {
+              "content": {
+                "application/json": {
+                  "schema": { "$ref": "#/components/schemas/toy" }
+                }
+              }
+            },
+            "responses": {
+              "200": {
+                "description": "ok",
+                "content": {
+                  "application/json": {
+                    "schema": { "$ref": "#/components/schemas/toy" }
+                  }
+                }
+              }
+            }
+          }
+        }
+      },
+      "components": {
+        "schemas": {
+          "pet": {
+            "type": "object",
+            "properties": {
+              "name": { "type": "string" },
+              "kind": { "enum": ["cat", "dog", "bird"] },
+              "age": { "type": "integer" }
+            }
+          },
+          "toy": {
+            "type": "object",
+            "properties": {
+              "name": { "type": "string" }
+            }
+          }
+        }
+      }
+    });
+    context
+      .0
+      .load_from_node(&old_location, old_node)
+      .await
+      .unwrap();
+    context
+      .0
+      .load_from_node(&new_location, new_node)
+      .await
+      .unwrap();
+
+    let api_diff = context
+      .0
+      .diff_api_models(&old_location, &new_location)
+      .unwrap();
+    assert!(api_diff.is_breaking());
+
+    let actual: Vec<_> = api_diff
+      .differences
+      .iter()
+      .map(|difference| (difference.kind, difference.breaking))
+      .collect();
+    let expected = vec![
+      (models::DifferenceKind::ParameterBecameRequired, true),
+      (models::DifferenceKind::SchemaPropertyBecameOptional, true),
+      (models::DifferenceKind::SchemaPropertyAdded, false),
+      (models::DifferenceKind::SchemaEnumValueAdded, true),
+      (models::DifferenceKind::ResponseContentTypeAdded, false),
+      (models::DifferenceKind::ResponseRemoved, true),
+      (models::DifferenceKind::OperationRemoved, true),
+      // the toy schema is used in the request and in the response
+      (models::DifferenceKind::SchemaPropertyRemoved, false),
+      (models::DifferenceKind::SchemaPropertyRemoved, true),
+    ];
+    assert_eq!(actual, expected);
+
+    let difference = &api_diff.differences[6];
+    assert_eq!(
+      difference.old_location,
+      Some(
+        "http://example.com/v1.yaml#/paths/~1pets~1{id}/delete"
+          .parse()
+          .unwrap()
+      )
+    );
+    assert_eq!(difference.new_location, None);
+
+    let report = api_diff.to_report();
+    assert_eq!(report["breaking"], true);
+    assert_eq!(report["summary"]["breaking"], 6);
+    assert_eq!(report["differences"][6]["kind"], "operation-removed");
+  }
+
+  #[tokio::test]
+  async fn test_slice_api_model() {
+    let context = new_test_context();
+
+    let location: NodeLocation = "http://example.com/api.yaml".parse().unwrap();
+    let node = serde_json::json!({
+      "openapi": "3.0.3",
+      "tags": [
+        { "name": "pets" },
+        { "name": "admin" }
+      ],
+      "paths": {
+        "/pets/{id}": {
+          "get": {
+            "operationId": "get-pet",
+            "tags": ["pets"],
+            "security": [{ "key": [] }],
+            "responses": {
+              "200": {
+                "description": "ok",
+                "content": {
+                  "application/json": {
+                    "schema": { "$ref": "#/components/schemas/pet" }
+                  }
+                }
+              }
+            }
+          },
+          "delete": {
+            "operationId": "delete-pet",
+            "tags": ["pets"],
+            "deprecated": true,
+            "responses": {}
+          }
+        },
+        "/admin/pets": {
+          "x-internal": true,
+          "post": {
+            "operationId": "import-pets",
+            "tags": ["admin"],
+            "requestBody": {
+              "content": {
+                "application/json": {
+                  "schema": { "$ref": "#/components/schemas/import" }
+                }
+              }
+            },
+            "responses": {}
+          }
+        }
+      },
+      "components": {
+        "schemas": {
+          "pet": {
+            "type": "object",
+            "properties": {
+              "owner": { "$ref": "#/components/schemas/owner" }
+            }
+          },
+          "owner": { "type": "string" },
+          "import": { "type": "array" }
+        },
+        "securitySchemes": {
+          "key": { "type": "apiKey", "name": "key", "in": "header" }
+        }
+      }
+    });
+    context.0.load_from_node(&location, node).await.unwrap();
+
+    let filter = models::ApiFilter {
+      exclude_internal: true,
+      exclude_deprecated: true,
+      ..Default::default()
+    };
+    let api_slice = context.0.slice_api_model(&location, &filter).unwrap();
+    let names: Vec<_> = api_slice
+      .api
+      .paths
+      .iter()
+      .flat_map(|path| path.operations.iter())
+      .map(|operation| operation.name.as_str())
+      .collect();
+    assert_eq!(names, vec!["get-pet"]);
+    let tags: Vec<_> = api_slice
+      .api
+      .tags
+      .iter()
+      .map(|tag| tag.name.as_str())
+      .collect();
+    assert_eq!(tags, vec!["pets"]);
+    assert_eq!(api_slice.api.authentication.len(), 1);
+    let schema_locations: Vec<_> = api_slice
+      .schema_locations
+      .iter()
+      .map(|location| location.to_string())
+      .collect();
+    assert_eq!(
+      schema_locations,
+      vec![
+        "http://example.com/api.yaml#/components/schemas/owner",
+        "http://example.com/api.yaml#/components/schemas/pet",
+        "http://example.com/api.yaml#/paths/~1pets~1%7Bid%7D/get/responses/200/content/application~1json/schema",
+      ]
+    );
+
+    let filter = models::ApiFilter {
+      include_paths: vec!["/admin/**".to_owned()],
+      ..Default::default()
+    };
+    let api_slice = context.0.slice_api_model(&location, &filter).unwrap();
+    assert_eq!(api_slice.api.paths.len(), 1);
+    assert_eq!(api_slice.api.paths[0].pattern, "/admin/pets");
+    assert!(api_slice.api.authentication.is_empty());
+
+    let filter = models::ApiFilter {
+      include_tags: vec!["pets".to_owned()],
+      exclude_operation_names: vec!["get-pet".to_owned()],
+      ..Default::default()
+    };
+    let api_slice = context.0.slice_api_model(&location, &filter).unwrap();
+    assert_eq!(api_slice.api.paths[0].operations[0].name, "delete-pet");
+    assert!(api_slice.schema_locations.is_empty());
+  }
+
+  #[tokio::test]
+  async fn test_oas30_schemas() {
+    let context = new_test_context();
+
+    let location = "http://example.com/api.yaml";
+    let node = serde_json::json!({
+      "openapi": "3.0.3",
+      "paths": {},
+      "components": {
+        "schemas": {
+          "a": {
+            "$id": "https://example.com/a.json",
+            "type": "object",
+            "properties": {
+              "b": { "$anchor": "b", "type": "string" }
+            }
+          },
+          "c": {
+            "type": "array",
+            "items": { "type": "string" }
+          }
+        }
+      }
+    });
+    load_test_node(&context, location, node).await;
+
+    let schemas: BTreeMap<_, _> = context
+      .get_schemas()
+      .unwrap()
+      .into_iter()
+      .map(|schema| (schema.schema_location(), schema))
+      .collect();
+
+    let schema = &schemas["http://example.com/api.yaml#/components/schemas/a"];
+    assert_eq!(schema.schema_id(), "https://example.com/a.json#");
+
+    let schema = &schemas["http://example.com/api.yaml#/components/schemas/a/properties/b"];
+    assert_eq!(
+      schema.schema_id(),
+      "https://example.com/a.json#/properties/b"
+    );
+    assert_eq!(
+      schema.anchor_locations(),
+      vec!["https://example.com/a.json#b".to_owned()]
+    );
+
+    let schema = &schemas["http://example.com/api.yaml#/components/schemas/c/items"];
+    assert_eq!(
+      schema.schema_id(),
+      "http://example.com/api.yaml#/components/schemas/c/items"
+    );
+  }
+
+  #[tokio::test]
+  async fn test_json_schema() {
+    let context = new_test_context();
+
+    let location = "http://example.com/schema.json";
+    let node = serde_json::json!({
+      "$schema": "http://json-schema.org/draft-07/schema#",
+      "$id": "https://example.com/schema.json",
+      "type": "object",
+      "properties": {
+        "a": { "type": "string" }
+      },
+      "dependencies": {
+        "a": { "$anchor": "a-dependency", "required": ["b"] },
+        "b": ["a"]
+      }
+    });
+    load_test_node(&context, location, node).await;
+
+    let schemas: BTreeMap<_, _> = context
+      .get_schemas()
+      .unwrap()
+      .into_iter()
+      .map(|schema| (schema.schema_location(), schema))
+      .collect();
+
+    let schema = &schemas["http://example.com/schema.json#"];
+    assert_eq!(schema.schema_id(), "https://example.com/schema.json#");
+    assert_eq!(
+      schema.default_schema_id(),
+      "http://json-schema.org/draft-07/schema#"
+    );
+
+    let schema = &schemas["http://example.com/schema.json#/properties/a"];
+    assert_eq!(
+      schema.schema_id(),
+      "https://example.com/schema.json#/properties/a"
+    );
+
+    // the values of dependencies are schemas or arrays of property names
+    let schema = &schemas["http://example.com/schema.json#/dependencies/a"];
+    assert_eq!(
+      schema.anchor_locations(),
+      vec!["https://example.com/schema.json#a-dependency".to_owned()]
+    );
+    assert!(!schemas.contains_key("http://example.com/schema.json#/dependencies/b"));
+
+    let result = context.0.get_api_model(&location.parse().unwrap());
+    assert_eq!(result.err(), Some(Error::NotFound));
+  }
+
+  #[tokio::test]
+  async fn test_schema_identity_location() {
+    let context = new_test_context();
+
+    let location = "http://example.com/api.yaml";
+    let node = serde_json::json!({
+      "openapi": "3.0.3",
+      "paths": {},
+      "components": {
+        "schemas": {
+          "a": {
+            "$id": "https://example.com/schemas/a.json",
+            "type": "object",
+            "properties": {
+              "b": { "type": "string" },
+              "c": { "$id": "c.json", "type": "string" }
+            }
+          }
+        }
+      }
+    });
+    load_test_node(&context, location, node).await;
+
+    let get_schema_identity_location = |pointer: &str| {
+      let location: NodeLocation = format!("{}#{}", location, pointer).parse().unwrap();
+      common::get_schema_identity_location(
+        &sync::Shared::downgrade(&context.0),
+        &location,
+        location.clone(),
+      )
+      .unwrap()
+      .to_string()
+    };
+
+    assert_eq!(
+      get_schema_identity_location("/components/schemas/a/properties/b"),
+      "https://example.com/schemas/a.json#/properties/b"
+    );
+    assert_eq!(
+      get_schema_identity_location("/components/schemas/a/properties/c"),
+      "https://example.com/schemas/c.json#"
+    );
+  }
+
+  #[tokio::test]
+  async fn test_load_referenced_nodes_by_identity() {
+    use crate::utils::MemoryFetcher;
+
+    // the api references a schema by the `$id` it has in another document,
+    // that `$id` can not be fetched
+    let location = "http://example.com/api.yaml";
+    let context = new_test_context();
+    context.set_fetcher(MemoryFetcher::from_iter([
+      (
+        location.parse::<NodeLocation>().unwrap(),
+        serde_json::json!({
+          "openapi": "3.0.3",
+          "paths": {},
+          "components": {
+            "schemas": {
+              "a": { "$ref": "https://example.com/b.json" },
+              "c": { "$ref": "schemas.yaml#/components/schemas/d" }
+            }
+          }
+        })
+        .to_string(),
+      ),
+      (
+        "http://example.com/schemas.yaml"
+          .parse::<NodeLocation>()
+          .unwrap(),
+        serde_json::json!({
+          "openapi": "3.0.3",
+          "paths": {},
+          "components": {
+            "schemas": {
+              "b": { "$id": "https://example.com/b.json", "type": "string" },
+              "d": { "type": "number" }
+            }
+          }
+        })
+        .to_string(),
+      ),
+    ]));
+
+    context.load_from_location(location).await.unwrap();
+    context
+      .0
+      .load_referenced_nodes(&location.parse().unwrap())
+      .await
+      .unwrap();
+
+    let actual = context.0.resolve_reference(
+      &"http://example.com/api.yaml#/components/schemas/a"
+        .parse()
+        .unwrap(),
+      &"https://example.com/b.json".parse().unwrap(),
+    );
+    let expected: NodeLocation = "http://example.com/schemas.yaml#/components/schemas/b"
+      .parse()
+      .unwrap();
+    assert_eq!(actual, expected);
+
+    // a location that can not be fetched, and is not an `$id`, is an error
+    let location = "memory:/other.yaml";
+    context
+      .0
+      .load_from_node(
+        &location.parse().unwrap(),
+        serde_json::json!({
+          "openapi": "3.0.3",
+          "paths": {},
+          "components": {
+            "schemas": { "a": { "$ref": "https://example.com/missing.json" } }
+          }
+        }),
+      )
+      .await
+      .unwrap();
+    assert!(context
+      .0
+      .load_referenced_nodes(&location.parse().unwrap())
+      .await
+      .is_err());
+  }
+
+  #[tokio::test]
+  async fn test_resolve_reference() {
+    let context = new_test_context();
+
+    let location = "http://example.com/api.yaml";
+    let node = serde_json::json!({
+      "openapi": "3.0.3",
+      "paths": {},
+      "components": {
+        "schemas": {
+          "a": {
+            "$id": "https://example.com/a.json",
+            "properties": {
+              "b": { "$ref": "#/$defs/c" },
+              "d": { "$ref": "#e" }
+            },
+            "$defs": {
+              "c": { "type": "string" },
+              "e": { "$anchor": "e", "type": "number" }
+            }
+          },
+          "f": { "$ref": "https://example.com/a.json#/properties/b" }
+        }
+      }
+    });
+    load_test_node(&context, location, node).await;
+
+    do_test(
+      &context,
+      "http://example.com/api.yaml#/components/schemas/a/properties/b",
+      "#/$defs/c",
+      "http://example.com/api.yaml#/components/schemas/a/$defs/c",
+    );
+
+    do_test(
+      &context,
+      "http://example.com/api.yaml#/components/schemas/a/properties/d",
+      "#e",
+      "http://example.com/api.yaml#/components/schemas/a/$defs/e",
+    );
+
+    do_test(
+      &context,
+      "http://example.com/api.yaml#/components/schemas/f",
+      "https://example.com/a.json#/properties/b",
+      "http://example.com/api.yaml#/components/schemas/a/properties/b",
+    );
+
+    do_test(
+      &context,
+      "http://example.com/api.yaml#/components/schemas/f",
+      "other.yaml#/x",
+      "http://example.com/other.yaml#/x",
+    );
+
+    fn do_test(
+      context: &DocumentContextContainer,
+      retrieval_location: &str,
+      reference: &str,
+      expected: &str,
+    ) {
+      let actual = context.0.resolve_reference(
+        &retrieval_location.parse().unwrap(),
+        &reference.parse().unwrap(),
+      );
+      let expected: NodeLocation = expected.parse().unwrap();
+      assert_eq!(actual, expected);
+    }
+  }
 }
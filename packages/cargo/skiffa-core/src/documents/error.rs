@@ -11,6 +11,15 @@ pub enum DocumentError {
   ParseLocationFailed,
   ParseMethodFailed,
   ParseStatusKindFailed,
+  /// The action of an AsyncAPI 3 operation is missing, or is not `send` or
+  /// `receive`
+  ParseOperationActionFailed,
+  /// The document does not describe this kind of model, like an AsyncAPI
+  /// document does not describe an `Api`
+  ModelNotSupported,
+  /// The document describes this kind of model, but reading the model from
+  /// this type of document is not implemented
+  ModelNotImplemented,
 }
 
 impl std::error::Error for DocumentError {}
@@ -23,6 +32,9 @@ impl Display for DocumentError {
       Self::ParseLocationFailed => write!(f, "ParseLocationFailed"),
       Self::ParseMethodFailed => write!(f, "ParseMethodFailed"),
       Self::ParseStatusKindFailed => write!(f, "ParseStatusKindFailed"),
+      Self::ParseOperationActionFailed => write!(f, "ParseOperationActionFailed"),
+      Self::ModelNotSupported => write!(f, "ModelNotSupported"),
+      Self::ModelNotImplemented => write!(f, "ModelNotImplemented"),
     }
   }
 }
@@ -1,18 +1,41 @@
 use super::DocumentError;
 use crate::models;
+use crate::utils::sync::{self, MaybeSendSync};
 use crate::utils::NodeLocation;
-use std::rc;
 
-pub trait DocumentInterface {
+pub trait DocumentInterface: MaybeSendSync {
   fn get_default_schema_id(&self) -> String;
   fn get_document_location(&self) -> NodeLocation;
-  fn get_schema_locations(&self) -> Result<Vec<NodeLocation>, DocumentError>;
+  /// The location the document identifies itself with (like `$self`), if it
+  /// is different from the retrieval location.
+  ///
+  fn get_identity_location(&self) -> Result<Option<NodeLocation>, DocumentError>;
+  fn get_schema_locations(&self) -> Result<Vec<SchemaLocation>, DocumentError>;
   fn get_referenced_locations(&self) -> Result<Vec<NodeLocation>, DocumentError>;
-  fn get_api_model(&self) -> Result<rc::Rc<models::Api>, DocumentError>;
+  fn get_api_model(&self) -> Result<sync::Shared<models::Api>, DocumentError>;
+  fn get_async_api_model(&self) -> Result<sync::Shared<models::AsyncApi>, DocumentError>;
+}
+
+/// A schema in a document. The retrieval location is where the schema node
+/// physically lives, the identity location is the canonical uri of the schema
+/// as json schema tooling would compute it (taking `$id` into account). Anchors
+/// (`$anchor` and `$dynamicAnchor`) are resolved against the base uri of the
+/// schema.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchemaLocation {
+  pub retrieval_location: NodeLocation,
+  pub identity_location: NodeLocation,
+  pub anchor_locations: Vec<NodeLocation>,
 }
 
 pub struct DocumentConfiguration {
   pub retrieval_location: NodeLocation,
 }
 
+#[cfg(not(target_os = "unknown"))]
+pub type DocumentFactory =
+  Box<dyn Fn(DocumentConfiguration) -> Box<dyn DocumentInterface> + Send + Sync>;
+
+#[cfg(target_os = "unknown")]
 pub type DocumentFactory = Box<dyn Fn(DocumentConfiguration) -> Box<dyn DocumentInterface>>;
@@ -0,0 +1,76 @@
+use crate::documents::common::{get_schema_locations_from_schema, nodes};
+use crate::utils::sync;
+use crate::utils::NodeLocation;
+use crate::{
+  documents::{DocumentContext, DocumentError, DocumentInterface, SchemaLocation},
+  models,
+};
+
+/// A document that is a json schema. It has no api model, the document
+/// itself and all of it's sub schemas are the schemas in the document.
+///
+pub struct Document {
+  retrieval_location: NodeLocation,
+  node: serde_json::Value,
+}
+
+impl Document {
+  pub fn new(context: sync::Weak<DocumentContext>, retrieval_location: NodeLocation) -> Self {
+    let node = context
+      .upgrade()
+      .unwrap()
+      .get_node(&retrieval_location)
+      .unwrap()
+      .clone();
+    Self {
+      retrieval_location,
+      node,
+    }
+  }
+}
+
+impl DocumentInterface for Document {
+  fn get_default_schema_id(&self) -> String {
+    self
+      .node
+      .as_object()
+      .and_then(|node| node.get("$schema"))
+      .and_then(|node| node.as_str())
+      .unwrap_or("https://json-schema.org/draft/2020-12/schema")
+      .to_owned()
+  }
+
+  fn get_document_location(&self) -> NodeLocation {
+    self.retrieval_location.clone()
+  }
+
+  fn get_identity_location(&self) -> Result<Option<NodeLocation>, DocumentError> {
+    // the `$id` of the root schema is registered as a schema location
+    Ok(None)
+  }
+
+  fn get_api_model(&self) -> Result<sync::Shared<models::Api>, DocumentError> {
+    Err(DocumentError::ModelNotSupported)
+  }
+
+  fn get_async_api_model(&self) -> Result<sync::Shared<models::AsyncApi>, DocumentError> {
+    Err(DocumentError::ModelNotSupported)
+  }
+
+  fn get_referenced_locations(&self) -> Result<Vec<NodeLocation>, DocumentError> {
+    // like the schemas in other documents, references in the schema are loaded
+    // via `DocumentContext::load_referenced_nodes`
+    Ok(Vec::new())
+  }
+
+  fn get_schema_locations(&self) -> Result<Vec<SchemaLocation>, DocumentError> {
+    let node: nodes::Schema = self.node.clone().into();
+    get_schema_locations_from_schema(
+      self.retrieval_location.clone(),
+      self.retrieval_location.clone(),
+      node,
+    )
+    .into_iter()
+    .collect()
+  }
+}
@@ -1,132 +1,141 @@
 use super::nodes;
+use crate::documents::common::{
+  dereference, get_node, get_referenced_locations_from_reference_entries,
+  get_schema_identity_location, get_schema_locations_from_schema,
+  get_schema_locations_from_schema_location, get_sub_locations_from_node_entries,
+};
 use crate::models::{AuthenticationRequirement, AuthenticationRequirementGroup, StatusKind};
+use crate::utils::sync;
 use crate::utils::NodeLocation;
 use crate::{
-  documents::{DocumentContext, DocumentError, DocumentInterface},
+  documents::{DocumentContext, DocumentError, DocumentInterface, DocumentType, SchemaLocation},
   models,
 };
 use std::collections::{BTreeMap, BTreeSet};
-use std::{iter, rc};
+use std::iter;
 
+/// Reads OpenApi 3.0 and 3.2 documents, the document type tells which one.
+/// Things that are new in 3.2, like `$self`, `additionalOperations`,
+/// `querystring` parameters, `itemSchema`, the device authorization flow and
+/// the new tag fields, are only read from 3.2 documents.
+///
 pub struct Document {
-  context: rc::Weak<DocumentContext>,
+  context: sync::Weak<DocumentContext>,
   retrieval_location: NodeLocation,
+  document_type: DocumentType,
 }
 
 impl Document {
-  pub fn new(context: rc::Weak<DocumentContext>, retrieval_location: NodeLocation) -> Self {
+  pub fn new(
+    context: sync::Weak<DocumentContext>,
+    retrieval_location: NodeLocation,
+    document_type: DocumentType,
+  ) -> Self {
+    assert!(matches!(
+      document_type,
+      DocumentType::OpenApiV30 | DocumentType::OpenApiV32
+    ));
+
     Self {
       context,
       retrieval_location,
+      document_type,
     }
   }
-}
 
-impl Document {
-  fn get_referenced_locations_from_reference_entries<N>(
-    location: NodeLocation,
-    entries: impl Iterator<Item = (Vec<String>, nodes::NodeOrReference<N>)>,
-  ) -> impl Iterator<Item = Result<NodeLocation, DocumentError>>
-  where
-    N: From<serde_json::Value>,
-  {
-    entries
-      .filter_map(move |(pointer, node)| {
-        let location = location.push_pointer(pointer);
-        if let nodes::NodeOrReference::Reference(reference) = node {
-          Some((location, reference))
-        } else {
-          None
-        }
-      })
-      .map(|(location, reference)| {
-        let reference_location: NodeLocation = reference.parse()?;
-        Ok(location.join(&reference_location))
-      })
+  fn is_oas32(&self) -> bool {
+    self.document_type == DocumentType::OpenApiV32
   }
+}
 
-  fn get_sub_locations_from_node_entries<N, SR>(
-    location: NodeLocation,
-    entries: impl Iterator<Item = (Vec<String>, N)>,
-    selector: impl Fn(NodeLocation, N) -> SR,
-  ) -> impl Iterator<Item = Result<NodeLocation, DocumentError>>
-  where
-    N: From<serde_json::Value>,
-    SR: Iterator<Item = Result<NodeLocation, DocumentError>>,
-  {
-    entries
-      .map(move |(pointer, node)| {
-        let location = location.push_pointer(pointer);
-        (location, node)
-      })
-      .flat_map(move |(location, node)| (selector)(location, node))
+impl Document {
+  /// The location of the document as given by `$self`, resolved against the
+  /// retrieval location.
+  ///
+  fn get_self_location(&self) -> Result<Option<NodeLocation>, DocumentError> {
+    if !self.is_oas32() {
+      return Ok(None);
+    }
+    let api_node: nodes::Api = get_node(&self.context, &self.retrieval_location)?;
+    let Some(self_reference) = api_node.self_reference() else {
+      return Ok(None);
+    };
+    let self_location: NodeLocation = self_reference.parse()?;
+    Ok(Some(
+      self.retrieval_location.set_root().join(&self_location),
+    ))
   }
 
-  fn get_node<T>(&self, location: &NodeLocation) -> Result<T, DocumentError>
-  where
-    T: From<serde_json::Value>,
-  {
-    let context = self.context.upgrade().unwrap();
-    let node = context
-      .get_node(location)
-      .ok_or(DocumentError::NodeNotFound)?;
-    let node: T = node.clone().into();
-    Ok(node)
+  /// Translate a retrieval location in this document to a location relative
+  /// to `$self`, if there is one.
+  ///
+  fn get_base_identity_location(
+    &self,
+    location: &NodeLocation,
+  ) -> Result<NodeLocation, DocumentError> {
+    let Some(self_location) = self.get_self_location()? else {
+      return Ok(location.clone());
+    };
+    Ok(match location.get_pointer() {
+      Some(pointer) => self_location.set_pointer(pointer),
+      None => self_location.set_root(),
+    })
   }
 
-  fn dereference<T>(
+  /// The canonical uri of the schema at the location, this is the location
+  /// itself (relative to `$self`), unless the schema has an `$id`.
+  ///
+  fn get_schema_identity_location(
     &self,
     location: &NodeLocation,
-    node: nodes::NodeOrReference<T>,
-  ) -> Result<(NodeLocation, T), DocumentError>
-  where
-    T: From<serde_json::Value>,
-  {
-    match node {
-      nodes::NodeOrReference::Reference(reference) => {
-        let reference_location: NodeLocation = reference.parse()?;
-        let context = self.context.upgrade().unwrap();
-        let location = location.join(&reference_location);
-        let node = context
-          .get_node(&location)
-          .ok_or(DocumentError::NodeNotFound)?
-          .clone();
-        let node = node.into();
-        Ok((location, node))
-      }
-      nodes::NodeOrReference::Node(node) => Ok((location.clone(), node)),
-    }
+  ) -> Result<NodeLocation, DocumentError> {
+    let identity_location = self.get_base_identity_location(location)?;
+    get_schema_identity_location(&self.context, location, identity_location)
   }
 }
 
 impl DocumentInterface for Document {
   fn get_default_schema_id(&self) -> String {
-    "https://spec.openapis.org/oas/3.0/schema/2021-09-28#/definitions/Schema".to_owned()
+    if self.is_oas32() {
+      "https://spec.openapis.org/oas/3.2/dialect/2025-09-17".to_owned()
+    } else {
+      "https://spec.openapis.org/oas/3.0/schema/2021-09-28#/definitions/Schema".to_owned()
+    }
   }
 
   fn get_document_location(&self) -> NodeLocation {
     self.retrieval_location.clone()
   }
 
-  fn get_api_model(&self) -> Result<rc::Rc<models::Api>, DocumentError> {
+  fn get_identity_location(&self) -> Result<Option<NodeLocation>, DocumentError> {
+    self.get_self_location()
+  }
+
+  fn get_api_model(&self) -> Result<sync::Shared<models::Api>, DocumentError> {
     let api_location = self.retrieval_location.clone();
-    let api_node = self.get_node(&api_location)?;
+    let api_node = get_node(&self.context, &api_location)?;
 
-    self.make_api_model(api_location, api_node).map(rc::Rc::new)
+    self
+      .make_api_model(api_location, api_node)
+      .map(sync::Shared::new)
+  }
+
+  fn get_async_api_model(&self) -> Result<sync::Shared<models::AsyncApi>, DocumentError> {
+    Err(DocumentError::ModelNotSupported)
   }
 
   fn get_referenced_locations(&self) -> Result<Vec<NodeLocation>, DocumentError> {
     let api_location = self.retrieval_location.clone();
-    let api_node = self.get_node(&api_location)?;
+    let api_node = get_node(&self.context, &api_location)?;
 
     self
       .get_referenced_locations_from_api(api_location, api_node)
       .collect()
   }
 
-  fn get_schema_locations(&self) -> Result<Vec<NodeLocation>, DocumentError> {
+  fn get_schema_locations(&self) -> Result<Vec<SchemaLocation>, DocumentError> {
     let api_location = self.retrieval_location.clone();
-    let api_node = self.get_node(&api_location)?;
+    let api_node = get_node(&self.context, &api_location)?;
 
     self
       .get_schema_locations_from_api(api_location, api_node)
@@ -149,10 +158,10 @@ impl Document {
         let pattern = pointer.last().unwrap().clone();
         let id = index + 1;
         let location = api_location.push_pointer(pointer);
-        let (location, node) = self.dereference(&location, node)?;
+        let (location, node) = dereference(&self.context, &location, node)?;
         self
           .make_path_model(api_node.clone(), location, node, id, pattern)
-          .map(rc::Rc::new)
+          .map(sync::Shared::new)
       })
       .collect::<Result<_, DocumentError>>()?;
 
@@ -163,10 +172,20 @@ impl Document {
       .map(|(pointer, node)| {
         let name = pointer.last().unwrap().clone();
         let location = api_location.push_pointer(pointer);
-        let (location, node) = self.dereference(&location, node)?;
+        let (location, node) = dereference(&self.context, &location, node)?;
         self
           .make_authentication_model(location, node, name)
-          .map(rc::Rc::new)
+          .map(sync::Shared::new)
+      })
+      .collect::<Result<_, DocumentError>>()?;
+
+    let tags = api_node
+      .tags()
+      .into_iter()
+      .flatten()
+      .map(|(pointer, node)| {
+        let location = api_location.push_pointer(pointer);
+        self.make_tag_model(location, node).map(sync::Shared::new)
       })
       .collect::<Result<_, DocumentError>>()?;
 
@@ -174,6 +193,9 @@ impl Document {
       location: api_location.clone(),
       paths,
       authentication,
+      tags,
+      self_location: self.get_self_location()?,
+      extensions: api_node.extensions(),
     })
   }
 
@@ -185,23 +207,37 @@ impl Document {
     id: usize,
     pattern: String,
   ) -> Result<models::Path, DocumentError> {
+    let path_parameter_nodes = path_node
+      .request_parameters()
+      .into_iter()
+      .flatten()
+      .map(|(pointer, node)| {
+        let location = path_location.push_pointer(pointer);
+        dereference(&self.context, &location, node)
+      })
+      .collect::<Result<Vec<_>, DocumentError>>()?;
+
+    let additional_operations = path_node
+      .additional_operations()
+      .filter(|_| self.is_oas32());
     let operations = path_node
       .operations()
       .into_iter()
       .flatten()
+      .chain(additional_operations.into_iter().flatten())
       .map(|(pointer, node)| {
-        let method = pointer.last().unwrap().as_str().parse()?;
+        let method = models::Method::from_http_name(pointer.last().unwrap());
         let location = path_location.push_pointer(pointer);
         self
           .make_operation_model(
             api_node.clone(),
-            path_location.clone(),
-            path_node.clone(),
+            &path_parameter_nodes,
             location,
             node,
             method,
+            &pattern,
           )
-          .map(rc::Rc::new)
+          .map(sync::Shared::new)
       })
       .collect::<Result<_, DocumentError>>()?;
 
@@ -210,17 +246,18 @@ impl Document {
       location: path_location.clone(),
       pattern,
       operations,
+      extensions: path_node.extensions(),
     })
   }
 
   fn make_operation_model(
     &self,
     api_node: nodes::Api,
-    path_location: NodeLocation,
-    path_node: nodes::Path,
+    path_parameter_nodes: &[(NodeLocation, nodes::RequestParameter)],
     operation_location: NodeLocation,
     operation_node: nodes::Operation,
     method: models::Method,
+    pattern: &str,
   ) -> Result<models::Operation, DocumentError> {
     let mut status_codes_available = (100..600).collect();
     let authentication_requirements = None
@@ -229,20 +266,11 @@ impl Document {
       .into_iter()
       .flatten()
       .map(|requirements| self.make_authentication_requirement_group(requirements))
-      .map(rc::Rc::new)
+      .map(sync::Shared::new)
       .collect();
 
     let all_parameter_nodes = iter::empty()
-      .chain(
-        path_node
-          .request_parameters()
-          .into_iter()
-          .flatten()
-          .map(|(pointer, node)| {
-            let location = path_location.push_pointer(pointer);
-            self.dereference(&location, node)
-          }),
-      )
+      .chain(path_parameter_nodes.iter().cloned().map(Ok))
       .chain(
         operation_node
           .request_parameters()
@@ -250,7 +278,7 @@ impl Document {
           .flatten()
           .map(|(pointer, node)| {
             let location = operation_location.push_pointer(pointer);
-            self.dereference(&location, node)
+            dereference(&self.context, &location, node)
           }),
       )
       .collect::<Result<Vec<_>, DocumentError>>()?;
@@ -262,7 +290,22 @@ impl Document {
           Some(
             self
               .make_parameter_model_request(location.clone(), node.clone())
-              .map(rc::Rc::new),
+              .map(sync::Shared::new),
+          )
+        } else {
+          None
+        }
+      })
+      .collect::<Result<Vec<_>, DocumentError>>()?;
+
+    let querystring_parameters = all_parameter_nodes
+      .iter()
+      .filter_map(|(location, node)| {
+        if self.is_oas32() && node.r#in()? == "querystring" {
+          Some(
+            self
+              .make_parameter_model_request(location.clone(), node.clone())
+              .map(sync::Shared::new),
           )
         } else {
           None
@@ -277,7 +320,7 @@ impl Document {
           Some(
             self
               .make_parameter_model_request(location.clone(), node.clone())
-              .map(rc::Rc::new),
+              .map(sync::Shared::new),
           )
         } else {
           None
@@ -292,7 +335,7 @@ impl Document {
           Some(
             self
               .make_parameter_model_request(location.clone(), node.clone())
-              .map(rc::Rc::new),
+              .map(sync::Shared::new),
           )
         } else {
           None
@@ -307,7 +350,7 @@ impl Document {
           Some(
             self
               .make_parameter_model_request(location.clone(), node.clone())
-              .map(rc::Rc::new),
+              .map(sync::Shared::new),
           )
         } else {
           None
@@ -324,7 +367,7 @@ impl Document {
         let location = operation_location.push_pointer(pointer);
         self
           .make_body_model(location, node.clone(), content_type)
-          .map(rc::Rc::new)
+          .map(sync::Shared::new)
       })
       .collect::<Result<Vec<_>, DocumentError>>()?;
 
@@ -335,7 +378,7 @@ impl Document {
       .map(|(pointer, node)| {
         let status_kind: StatusKind = pointer.last().unwrap().clone().parse()?;
         let location = operation_location.push_pointer(pointer);
-        let (location, node) = self.dereference(&location, node)?;
+        let (location, node) = dereference(&self.context, &location, node)?;
         Ok((status_kind, location, node))
       })
       .collect::<Result<Vec<_>, DocumentError>>()?;
@@ -354,24 +397,32 @@ impl Document {
             status_kind,
             &mut status_codes_available,
           )
-          .map(rc::Rc::new)
+          .map(sync::Shared::new)
       })
       .collect::<Result<Vec<_>, DocumentError>>()?;
 
     Ok(models::Operation {
       location: operation_location.clone(),
-      name: operation_node.name().map(Into::into).unwrap(),
+      // operations without an operationId are named after their method and
+      // path
+      name: operation_node
+        .name()
+        .map(Into::into)
+        .unwrap_or_else(|| format!("{} {}", method.to_string(), pattern)),
       summary: operation_node.summary().map(Into::into),
       description: operation_node.description().map(Into::into),
       deprecated: operation_node.deprecated().unwrap_or(false),
+      tags: operation_node.tags().unwrap_or_default(),
       method,
       authentication_requirements,
       cookie_parameters,
+      querystring_parameters,
       header_parameters,
       path_parameters,
       query_parameters,
       bodies,
       operation_results,
+      extensions: operation_node.extensions(),
     })
   }
 
@@ -394,10 +445,10 @@ impl Document {
       .map(|(pointer, node)| {
         let name = pointer.last().unwrap().clone();
         let location = operation_result_location.push_pointer(pointer);
-        let (location, node) = self.dereference(&location, node)?;
+        let (location, node) = dereference(&self.context, &location, node)?;
         self
           .make_parameter_model_response(location, node, name)
-          .map(rc::Rc::new)
+          .map(sync::Shared::new)
       })
       .collect::<Result<Vec<_>, DocumentError>>()?;
 
@@ -410,7 +461,7 @@ impl Document {
         let location = operation_result_location.push_pointer(pointer);
         self
           .make_body_model(location, node.clone(), content_type)
-          .map(rc::Rc::new)
+          .map(sync::Shared::new)
       })
       .collect::<Result<Vec<_>, DocumentError>>()?;
 
@@ -421,6 +472,7 @@ impl Document {
       status_codes,
       header_parameters,
       bodies,
+      extensions: operation_result_node.extensions(),
     })
   }
 
@@ -432,12 +484,20 @@ impl Document {
   ) -> Result<models::Body, DocumentError> {
     let schema_id = body_node
       .schema_pointer()
-      .map(|pointer| body_location.push_pointer(pointer));
+      .map(|pointer| self.get_schema_identity_location(&body_location.push_pointer(pointer)))
+      .transpose()?;
+    let item_schema_id = body_node
+      .item_schema_pointer()
+      .filter(|_| self.is_oas32())
+      .map(|pointer| self.get_schema_identity_location(&body_location.push_pointer(pointer)))
+      .transpose()?;
 
     Ok(models::Body {
       location: body_location.clone(),
       content_type,
       schema_id,
+      item_schema_id,
+      extensions: body_node.extensions(),
     })
   }
 
@@ -448,13 +508,18 @@ impl Document {
   ) -> Result<models::Parameter, DocumentError> {
     let schema_id = parameter_node
       .schema_pointer()
-      .map(|pointer| parameter_location.push_pointer(pointer));
+      .map(|pointer| self.get_schema_identity_location(&parameter_location.push_pointer(pointer)))
+      .transpose()?;
 
     Ok(models::Parameter {
       location: parameter_location.clone(),
-      name: parameter_node.name().map(Into::into).unwrap(),
+      name: parameter_node
+        .name()
+        .map(Into::into)
+        .ok_or(DocumentError::NodeNotFound)?,
       required: parameter_node.required().unwrap_or(false),
       schema_id,
+      extensions: parameter_node.extensions(),
     })
   }
 
@@ -466,13 +531,15 @@ impl Document {
   ) -> Result<models::Parameter, DocumentError> {
     let schema_id = header_node
       .schema_pointer()
-      .map(|pointer| header_location.push_pointer(pointer));
+      .map(|pointer| self.get_schema_identity_location(&header_location.push_pointer(pointer)))
+      .transpose()?;
 
     Ok(models::Parameter {
       location: header_location.clone(),
       name,
       required: header_node.required().unwrap_or(false),
       schema_id,
+      extensions: header_node.extensions(),
     })
   }
 
@@ -482,6 +549,19 @@ impl Document {
     security_scheme_node: nodes::SecurityScheme,
     name: String,
   ) -> Result<models::Authentication, DocumentError> {
+    let flows = security_scheme_node
+      .flows()
+      .into_iter()
+      .flatten()
+      .map(|(pointer, node)| {
+        let kind = pointer.last().unwrap().clone();
+        let location = security_scheme_location.push_pointer(pointer);
+        self
+          .make_authentication_flow_model(location, node, kind)
+          .map(sync::Shared::new)
+      })
+      .collect::<Result<_, DocumentError>>()?;
+
     Ok(models::Authentication {
       location: security_scheme_location.clone(),
       name,
@@ -493,6 +573,49 @@ impl Document {
         .map(Into::into)
         .unwrap_or_default(),
       parameter_name: security_scheme_node.parameter_name().map(Into::into),
+      flows,
+      extensions: security_scheme_node.extensions(),
+    })
+  }
+
+  fn make_authentication_flow_model(
+    &self,
+    flow_location: NodeLocation,
+    flow_node: nodes::AuthenticationFlow,
+    kind: String,
+  ) -> Result<models::AuthenticationFlow, DocumentError> {
+    Ok(models::AuthenticationFlow {
+      location: flow_location.clone(),
+      kind,
+      authorization_url: flow_node.authorization_url().map(Into::into),
+      device_authorization_url: flow_node
+        .device_authorization_url()
+        .filter(|_| self.is_oas32())
+        .map(Into::into),
+      token_url: flow_node.token_url().map(Into::into),
+      refresh_url: flow_node.refresh_url().map(Into::into),
+      scopes: flow_node.scopes().unwrap_or_default(),
+    })
+  }
+
+  fn make_tag_model(
+    &self,
+    tag_location: NodeLocation,
+    tag_node: nodes::Tag,
+  ) -> Result<models::Tag, DocumentError> {
+    Ok(models::Tag {
+      location: tag_location.clone(),
+      name: tag_node.name().map(Into::into).unwrap_or_default(),
+      summary: tag_node
+        .summary()
+        .filter(|_| self.is_oas32())
+        .map(Into::into),
+      description: tag_node.description().map(Into::into),
+      parent: tag_node
+        .parent()
+        .filter(|_| self.is_oas32())
+        .map(Into::into),
+      kind: tag_node.kind().filter(|_| self.is_oas32()).map(Into::into),
     })
   }
 
@@ -504,7 +627,7 @@ impl Document {
       requirements: requirements
         .into_iter()
         .map(|(name, scopes)| self.make_authentication_requirement(name, scopes))
-        .map(rc::Rc::new)
+        .map(sync::Shared::new)
         .collect(),
     }
   }
@@ -528,11 +651,12 @@ impl Document {
     node: nodes::Api,
   ) -> impl Iterator<Item = Result<NodeLocation, DocumentError>> + '_ {
     iter::empty()
-      .chain(Self::get_referenced_locations_from_reference_entries(
+      .chain(get_referenced_locations_from_reference_entries(
+        &self.context,
         location.clone(),
         node.paths().into_iter().flatten(),
       ))
-      .chain(Self::get_sub_locations_from_node_entries(
+      .chain(get_sub_locations_from_node_entries(
         location.clone(),
         node
           .paths()
@@ -549,12 +673,13 @@ impl Document {
     node: nodes::Path,
   ) -> impl Iterator<Item = Result<NodeLocation, DocumentError>> + '_ {
     iter::empty()
-      .chain(Self::get_sub_locations_from_node_entries(
+      .chain(get_sub_locations_from_node_entries(
         location.clone(),
         node.operations().into_iter().flatten(),
         |location, node| self.get_referenced_locations_from_operation(location, node),
       ))
-      .chain(Self::get_referenced_locations_from_reference_entries(
+      .chain(get_referenced_locations_from_reference_entries(
+        &self.context,
         location.clone(),
         node.request_parameters().into_iter().flatten(),
       ))
@@ -566,11 +691,12 @@ impl Document {
     node: nodes::Operation,
   ) -> impl Iterator<Item = Result<NodeLocation, DocumentError>> + '_ {
     iter::empty()
-      .chain(Self::get_referenced_locations_from_reference_entries(
+      .chain(get_referenced_locations_from_reference_entries(
+        &self.context,
         location.clone(),
         node.operation_results().into_iter().flatten(),
       ))
-      .chain(Self::get_sub_locations_from_node_entries(
+      .chain(get_sub_locations_from_node_entries(
         location.clone(),
         node
           .operation_results()
@@ -579,7 +705,8 @@ impl Document {
           .filter_map(|(pointer, node)| node.into_node().map(|node| (pointer, node))),
         |location, node| self.get_referenced_locations_from_operation_result(location, node),
       ))
-      .chain(Self::get_referenced_locations_from_reference_entries(
+      .chain(get_referenced_locations_from_reference_entries(
+        &self.context,
         location.clone(),
         node.request_parameters().into_iter().flatten(),
       ))
@@ -589,8 +716,9 @@ impl Document {
     &self,
     location: NodeLocation,
     node: nodes::OperationResult,
-  ) -> impl Iterator<Item = Result<NodeLocation, DocumentError>> {
-    Self::get_referenced_locations_from_reference_entries(
+  ) -> impl Iterator<Item = Result<NodeLocation, DocumentError>> + '_ {
+    get_referenced_locations_from_reference_entries(
+      &self.context,
       location,
       node.response_headers().into_iter().flatten(),
     )
@@ -602,17 +730,51 @@ impl Document {
     &self,
     location: NodeLocation,
     node: nodes::Api,
-  ) -> impl Iterator<Item = Result<NodeLocation, DocumentError>> + '_ {
+  ) -> impl Iterator<Item = Result<SchemaLocation, DocumentError>> + '_ {
     iter::empty()
-      // .chain({
-      //   let location = location.clone();
-      //   node
-      //     .schema_component_pointers()
-      //     .into_iter()
-      //     .flatten()
-      //     .map(move |pointer| Ok(location.push_pointer(pointer)))
-      // })
-      .chain(Self::get_sub_locations_from_node_entries(
+      .chain(get_sub_locations_from_node_entries(
+        location.clone(),
+        node.schemas().into_iter().flatten(),
+        |location, node| match self.get_base_identity_location(&location) {
+          Ok(identity_location) => {
+            get_schema_locations_from_schema(location, identity_location, node).into_iter()
+          }
+          Err(error) => vec![Err(error)].into_iter(),
+        },
+      ))
+      .chain(get_sub_locations_from_node_entries(
+        location.clone(),
+        node
+          .request_parameters()
+          .into_iter()
+          .flatten()
+          .filter_map(|(pointer, node)| node.into_node().map(|node| (pointer, node))),
+        |location, node| self.get_schema_locations_from_request_parameter(location, node),
+      ))
+      .chain(get_sub_locations_from_node_entries(
+        location.clone(),
+        node
+          .response_headers()
+          .into_iter()
+          .flatten()
+          .filter_map(|(pointer, node)| node.into_node().map(|node| (pointer, node))),
+        |location, node| self.get_schema_locations_from_response_header(location, node),
+      ))
+      .chain(get_sub_locations_from_node_entries(
+        location.clone(),
+        node
+          .operation_results()
+          .into_iter()
+          .flatten()
+          .filter_map(|(pointer, node)| node.into_node().map(|node| (pointer, node))),
+        |location, node| {
+          self
+            .get_schema_locations_from_operation_result(location, node)
+            .collect::<Vec<_>>()
+            .into_iter()
+        },
+      ))
+      .chain(get_sub_locations_from_node_entries(
         location.clone(),
         node
           .paths()
@@ -627,9 +789,9 @@ impl Document {
     &self,
     location: NodeLocation,
     node: nodes::Path,
-  ) -> impl Iterator<Item = Result<NodeLocation, DocumentError>> + '_ {
+  ) -> impl Iterator<Item = Result<SchemaLocation, DocumentError>> + '_ {
     iter::empty()
-      .chain(Self::get_sub_locations_from_node_entries(
+      .chain(get_sub_locations_from_node_entries(
         location.clone(),
         node
           .request_parameters()
@@ -638,7 +800,7 @@ impl Document {
           .filter_map(|(pointer, node)| node.into_node().map(|node| (pointer, node))),
         |location, node| self.get_schema_locations_from_request_parameter(location, node),
       ))
-      .chain(Self::get_sub_locations_from_node_entries(
+      .chain(get_sub_locations_from_node_entries(
         location.clone(),
         node.operations().into_iter().flatten(),
         |location, node| self.get_schema_locations_from_operation(location, node),
@@ -649,9 +811,9 @@ impl Document {
     &self,
     location: NodeLocation,
     node: nodes::Operation,
-  ) -> impl Iterator<Item = Result<NodeLocation, DocumentError>> + '_ {
+  ) -> impl Iterator<Item = Result<SchemaLocation, DocumentError>> + '_ {
     iter::empty()
-      .chain(Self::get_sub_locations_from_node_entries(
+      .chain(get_sub_locations_from_node_entries(
         location.clone(),
         node
           .request_parameters()
@@ -660,12 +822,12 @@ impl Document {
           .filter_map(|(pointer, node)| node.into_node().map(|node| (pointer, node))),
         |location, node| self.get_schema_locations_from_request_parameter(location, node),
       ))
-      .chain(Self::get_sub_locations_from_node_entries(
+      .chain(get_sub_locations_from_node_entries(
         location.clone(),
         node.bodies().into_iter().flatten(),
         |location, node| self.get_schema_locations_from_body(location, node),
       ))
-      .chain(Self::get_sub_locations_from_node_entries(
+      .chain(get_sub_locations_from_node_entries(
         location.clone(),
         node
           .operation_results()
@@ -685,9 +847,9 @@ impl Document {
     &self,
     location: NodeLocation,
     node: nodes::OperationResult,
-  ) -> impl Iterator<Item = Result<NodeLocation, DocumentError>> + '_ {
+  ) -> impl Iterator<Item = Result<SchemaLocation, DocumentError>> + '_ {
     iter::empty()
-      .chain(Self::get_sub_locations_from_node_entries(
+      .chain(get_sub_locations_from_node_entries(
         location.clone(),
         node
           .response_headers()
@@ -701,7 +863,7 @@ impl Document {
             .into_iter()
         },
       ))
-      .chain(Self::get_sub_locations_from_node_entries(
+      .chain(get_sub_locations_from_node_entries(
         location.clone(),
         node.bodies().into_iter().flatten(),
         |location, node| {
@@ -717,35 +879,47 @@ impl Document {
     &self,
     location: NodeLocation,
     node: nodes::RequestParameter,
-  ) -> impl Iterator<Item = Result<NodeLocation, DocumentError>> {
+  ) -> impl Iterator<Item = Result<SchemaLocation, DocumentError>> + '_ {
     node
       .schema_pointer()
       .into_iter()
       .map(move |pointer| location.push_pointer(pointer))
-      .map(Ok)
+      .flat_map(|location| self.get_schema_locations_from_schema_location(location))
   }
 
   fn get_schema_locations_from_response_header(
     &self,
     location: NodeLocation,
     node: nodes::ResponseHeader,
-  ) -> impl Iterator<Item = Result<NodeLocation, DocumentError>> {
+  ) -> impl Iterator<Item = Result<SchemaLocation, DocumentError>> + '_ {
     node
       .schema_pointer()
       .into_iter()
       .map(move |pointer| location.push_pointer(pointer))
-      .map(Ok)
+      .flat_map(|location| self.get_schema_locations_from_schema_location(location))
   }
 
   fn get_schema_locations_from_body(
     &self,
     location: NodeLocation,
     node: nodes::Body,
-  ) -> impl Iterator<Item = Result<NodeLocation, DocumentError>> {
-    node
-      .schema_pointer()
-      .into_iter()
+  ) -> impl Iterator<Item = Result<SchemaLocation, DocumentError>> + '_ {
+    iter::empty()
+      .chain(node.schema_pointer())
+      .chain(node.item_schema_pointer().filter(|_| self.is_oas32()))
       .map(move |pointer| location.push_pointer(pointer))
-      .map(Ok)
+      .flat_map(|location| self.get_schema_locations_from_schema_location(location))
+  }
+
+  fn get_schema_locations_from_schema_location(
+    &self,
+    location: NodeLocation,
+  ) -> Vec<Result<SchemaLocation, DocumentError>> {
+    match self.get_base_identity_location(&location) {
+      Ok(identity_location) => {
+        get_schema_locations_from_schema_location(&self.context, location, identity_location)
+      }
+      Err(error) => vec![Err(error)],
+    }
   }
 }
@@ -0,0 +1,201 @@
+use super::*;
+use crate::utils::get_extensions;
+use std::collections::BTreeMap;
+
+#[derive(Clone)]
+pub struct Api(serde_json::Value);
+
+impl Api {
+  pub fn self_reference(&self) -> Option<&str> {
+    self.0.as_object()?.get("$self")?.as_str()
+  }
+
+  pub fn paths(&self) -> Option<BTreeMap<Vec<String>, NodeOrReference<Path>>> {
+    let member = "paths";
+    Some(
+      self
+        .0
+        .as_object()?
+        .get(member)?
+        .as_object()?
+        .into_iter()
+        .map(|(key, node)| (vec![member.to_owned(), key.clone()], node.clone().into()))
+        .collect(),
+    )
+  }
+
+  pub fn tags(&self) -> Option<BTreeMap<Vec<String>, Tag>> {
+    let member = "tags";
+    Some(
+      self
+        .0
+        .as_object()?
+        .get(member)?
+        .as_array()?
+        .iter()
+        .enumerate()
+        .map(|(key, node)| {
+          (
+            vec![member.to_owned(), key.to_string()],
+            node.clone().into(),
+          )
+        })
+        .collect(),
+    )
+  }
+
+  pub fn security_schemes(&self) -> Option<BTreeMap<Vec<String>, NodeOrReference<SecurityScheme>>> {
+    let member = "components";
+    let member_1 = "securitySchemes";
+    Some(
+      self
+        .0
+        .as_object()?
+        .get(member)?
+        .as_object()?
+        .get(member_1)?
+        .as_object()?
+        .into_iter()
+        .map(|(key, node)| {
+          (
+            vec![member.to_owned(), member_1.to_owned(), key.clone()],
+            node.clone().into(),
+          )
+        })
+        .collect(),
+    )
+  }
+
+  pub fn security(&self) -> Option<Vec<BTreeMap<String, Vec<String>>>> {
+    let member = "security";
+    Some(
+      self
+        .0
+        .as_object()?
+        .get(member)?
+        .as_array()?
+        .iter()
+        .filter_map(|value| {
+          Some(
+            value
+              .as_object()?
+              .iter()
+              .filter_map(|(key, value)| {
+                Some((
+                  key.to_owned(),
+                  value
+                    .as_array()?
+                    .iter()
+                    .filter_map(|value| Some(value.as_str()?.to_owned()))
+                    .collect(),
+                ))
+              })
+              .collect(),
+          )
+        })
+        .collect(),
+    )
+  }
+
+  pub fn schemas(&self) -> Option<BTreeMap<Vec<String>, Schema>> {
+    let member = "components";
+    let member_1 = "schemas";
+    Some(
+      self
+        .0
+        .as_object()?
+        .get(member)?
+        .as_object()?
+        .get(member_1)?
+        .as_object()?
+        .into_iter()
+        .map(|(key, node)| {
+          (
+            vec![member.to_owned(), member_1.to_owned(), key.clone()],
+            node.clone().into(),
+          )
+        })
+        .collect(),
+    )
+  }
+
+  pub fn request_parameters(
+    &self,
+  ) -> Option<BTreeMap<Vec<String>, NodeOrReference<RequestParameter>>> {
+    let member = "components";
+    let member_1 = "parameters";
+    Some(
+      self
+        .0
+        .as_object()?
+        .get(member)?
+        .as_object()?
+        .get(member_1)?
+        .as_object()?
+        .into_iter()
+        .map(|(key, node)| {
+          (
+            vec![member.to_owned(), member_1.to_owned(), key.clone()],
+            node.clone().into(),
+          )
+        })
+        .collect(),
+    )
+  }
+
+  pub fn response_headers(&self) -> Option<BTreeMap<Vec<String>, NodeOrReference<ResponseHeader>>> {
+    let member = "components";
+    let member_1 = "headers";
+    Some(
+      self
+        .0
+        .as_object()?
+        .get(member)?
+        .as_object()?
+        .get(member_1)?
+        .as_object()?
+        .into_iter()
+        .map(|(key, node)| {
+          (
+            vec![member.to_owned(), member_1.to_owned(), key.clone()],
+            node.clone().into(),
+          )
+        })
+        .collect(),
+    )
+  }
+
+  pub fn operation_results(
+    &self,
+  ) -> Option<BTreeMap<Vec<String>, NodeOrReference<OperationResult>>> {
+    let member = "components";
+    let member_1 = "responses";
+    Some(
+      self
+        .0
+        .as_object()?
+        .get(member)?
+        .as_object()?
+        .get(member_1)?
+        .as_object()?
+        .into_iter()
+        .map(|(key, node)| {
+          (
+            vec![member.to_owned(), member_1.to_owned(), key.clone()],
+            node.clone().into(),
+          )
+        })
+        .collect(),
+    )
+  }
+
+  pub fn extensions(&self) -> BTreeMap<String, serde_json::Value> {
+    get_extensions(&self.0)
+  }
+}
+
+impl From<serde_json::Value> for Api {
+  fn from(value: serde_json::Value) -> Self {
+    Self(value)
+  }
+}
@@ -0,0 +1,39 @@
+#[derive(Clone)]
+pub struct AuthenticationFlow(serde_json::Value);
+
+impl AuthenticationFlow {
+  pub fn authorization_url(&self) -> Option<&str> {
+    self.0.as_object()?.get("authorizationUrl")?.as_str()
+  }
+
+  pub fn device_authorization_url(&self) -> Option<&str> {
+    self.0.as_object()?.get("deviceAuthorizationUrl")?.as_str()
+  }
+
+  pub fn token_url(&self) -> Option<&str> {
+    self.0.as_object()?.get("tokenUrl")?.as_str()
+  }
+
+  pub fn refresh_url(&self) -> Option<&str> {
+    self.0.as_object()?.get("refreshUrl")?.as_str()
+  }
+
+  pub fn scopes(&self) -> Option<Vec<String>> {
+    Some(
+      self
+        .0
+        .as_object()?
+        .get("scopes")?
+        .as_object()?
+        .keys()
+        .cloned()
+        .collect(),
+    )
+  }
+}
+
+impl From<serde_json::Value> for AuthenticationFlow {
+  fn from(value: serde_json::Value) -> Self {
+    Self(value)
+  }
+}
@@ -0,0 +1,33 @@
+use crate::utils::get_extensions;
+use std::collections::BTreeMap;
+
+#[derive(Clone)]
+pub struct Body(serde_json::Value);
+
+impl Body {
+  pub fn schema_pointer(&self) -> Option<Vec<String>> {
+    self
+      .0
+      .as_object()?
+      .get("schema")
+      .map(|_value| vec!["schema".to_owned()])
+  }
+
+  pub fn item_schema_pointer(&self) -> Option<Vec<String>> {
+    self
+      .0
+      .as_object()?
+      .get("itemSchema")
+      .map(|_value| vec!["itemSchema".to_owned()])
+  }
+
+  pub fn extensions(&self) -> BTreeMap<String, serde_json::Value> {
+    get_extensions(&self.0)
+  }
+}
+
+impl From<serde_json::Value> for Body {
+  fn from(value: serde_json::Value) -> Self {
+    Self(value)
+  }
+}
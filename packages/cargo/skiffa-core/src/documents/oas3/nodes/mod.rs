@@ -1,21 +1,22 @@
 mod api;
+mod authentication_flow;
 mod body;
-mod node_or_reference;
 mod operation;
 mod operation_result;
 mod path;
-mod reference;
 mod request_parameter;
 mod response_header;
 mod security_scheme;
+mod tag;
 
+pub use crate::documents::common::nodes::*;
 pub use api::*;
+pub use authentication_flow::*;
 pub use body::*;
-pub use node_or_reference::*;
 pub use operation::*;
 pub use operation_result::*;
 pub use path::*;
-pub use reference::*;
 pub use request_parameter::*;
 pub use response_header::*;
 pub use security_scheme::*;
+pub use tag::*;
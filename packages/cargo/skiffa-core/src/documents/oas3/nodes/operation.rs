@@ -1,4 +1,5 @@
 use super::*;
+use crate::utils::get_extensions;
 
 use std::collections::BTreeMap;
 
@@ -22,6 +23,19 @@ impl Operation {
     self.0.as_object()?.get("deprecated")?.as_bool()
   }
 
+  pub fn tags(&self) -> Option<Vec<String>> {
+    Some(
+      self
+        .0
+        .as_object()?
+        .get("tags")?
+        .as_array()?
+        .iter()
+        .filter_map(|value| Some(value.as_str()?.to_owned()))
+        .collect(),
+    )
+  }
+
   pub fn request_parameters(
     &self,
   ) -> Option<BTreeMap<Vec<String>, NodeOrReference<RequestParameter>>> {
@@ -112,6 +126,10 @@ impl Operation {
         .collect(),
     )
   }
+
+  pub fn extensions(&self) -> BTreeMap<String, serde_json::Value> {
+    get_extensions(&self.0)
+  }
 }
 
 impl From<serde_json::Value> for Operation {
@@ -1,4 +1,5 @@
 use super::*;
+use crate::utils::get_extensions;
 
 use std::collections::BTreeMap;
 
@@ -42,6 +43,10 @@ impl OperationResult {
         .collect(),
     )
   }
+
+  pub fn extensions(&self) -> BTreeMap<String, serde_json::Value> {
+    get_extensions(&self.0)
+  }
 }
 
 impl From<serde_json::Value> for OperationResult {
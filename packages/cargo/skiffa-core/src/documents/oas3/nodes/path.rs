@@ -1,11 +1,14 @@
 use super::*;
 use crate::models;
+use crate::utils::get_extensions;
 use std::collections::BTreeMap;
 
 #[derive(Clone)]
 pub struct Path(serde_json::Value);
 
 impl Path {
+  /// The fixed operations (including `query`) keyed by their method.
+  ///
   pub fn operations(&self) -> Option<BTreeMap<Vec<String>, Operation>> {
     Some(
       self
@@ -18,6 +21,22 @@ impl Path {
     )
   }
 
+  /// The operations in `additionalOperations` keyed by their http method name.
+  ///
+  pub fn additional_operations(&self) -> Option<BTreeMap<Vec<String>, Operation>> {
+    let member = "additionalOperations";
+    Some(
+      self
+        .0
+        .as_object()?
+        .get(member)?
+        .as_object()?
+        .into_iter()
+        .map(|(key, node)| (vec![member.to_owned(), key.clone()], node.clone().into()))
+        .collect(),
+    )
+  }
+
   pub fn request_parameters(
     &self,
   ) -> Option<BTreeMap<Vec<String>, NodeOrReference<RequestParameter>>> {
@@ -39,6 +58,10 @@ impl Path {
         .collect(),
     )
   }
+
+  pub fn extensions(&self) -> BTreeMap<String, serde_json::Value> {
+    get_extensions(&self.0)
+  }
 }
 
 impl From<serde_json::Value> for Path {
@@ -0,0 +1,48 @@
+use crate::utils::get_extensions;
+use std::collections::BTreeMap;
+
+#[derive(Clone)]
+pub struct RequestParameter(serde_json::Value);
+
+impl RequestParameter {
+  /// The schema of the parameter, or the schema of the (single) media type
+  /// in `content`, as used by `querystring` parameters.
+  ///
+  pub fn schema_pointer(&self) -> Option<Vec<String>> {
+    let object = self.0.as_object()?;
+    if object.contains_key("schema") {
+      return Some(vec!["schema".to_owned()]);
+    }
+
+    let (media_type, node) = object.get("content")?.as_object()?.iter().next()?;
+    node.as_object()?.get("schema").map(|_value| {
+      vec![
+        "content".to_owned(),
+        media_type.clone(),
+        "schema".to_owned(),
+      ]
+    })
+  }
+
+  pub fn r#in(&self) -> Option<&str> {
+    self.0.as_object()?.get("in")?.as_str()
+  }
+
+  pub fn name(&self) -> Option<&str> {
+    self.0.as_object()?.get("name")?.as_str()
+  }
+
+  pub fn required(&self) -> Option<bool> {
+    self.0.as_object()?.get("required")?.as_bool()
+  }
+
+  pub fn extensions(&self) -> BTreeMap<String, serde_json::Value> {
+    get_extensions(&self.0)
+  }
+}
+
+impl From<serde_json::Value> for RequestParameter {
+  fn from(value: serde_json::Value) -> Self {
+    Self(value)
+  }
+}
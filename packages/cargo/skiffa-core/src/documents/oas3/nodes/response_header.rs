@@ -1,3 +1,6 @@
+use crate::utils::get_extensions;
+use std::collections::BTreeMap;
+
 #[derive(Clone)]
 pub struct ResponseHeader(serde_json::Value);
 
@@ -13,6 +16,10 @@ impl ResponseHeader {
   pub fn required(&self) -> Option<bool> {
     self.0.as_object()?.get("required")?.as_bool()
   }
+
+  pub fn extensions(&self) -> BTreeMap<String, serde_json::Value> {
+    get_extensions(&self.0)
+  }
 }
 
 impl From<serde_json::Value> for ResponseHeader {
@@ -1,3 +1,7 @@
+use super::*;
+use crate::utils::get_extensions;
+use std::collections::BTreeMap;
+
 #[derive(Clone)]
 pub struct SecurityScheme(serde_json::Value);
 
@@ -21,6 +25,24 @@ impl SecurityScheme {
   pub fn scheme(&self) -> Option<&str> {
     self.0.as_object()?.get("scheme")?.as_str()
   }
+
+  pub fn flows(&self) -> Option<BTreeMap<Vec<String>, AuthenticationFlow>> {
+    let member = "flows";
+    Some(
+      self
+        .0
+        .as_object()?
+        .get(member)?
+        .as_object()?
+        .into_iter()
+        .map(|(key, node)| (vec![member.to_owned(), key.clone()], node.clone().into()))
+        .collect(),
+    )
+  }
+
+  pub fn extensions(&self) -> BTreeMap<String, serde_json::Value> {
+    get_extensions(&self.0)
+  }
 }
 
 impl From<serde_json::Value> for SecurityScheme {
@@ -0,0 +1,30 @@
+#[derive(Clone)]
+pub struct Tag(serde_json::Value);
+
+impl Tag {
+  pub fn name(&self) -> Option<&str> {
+    self.0.as_object()?.get("name")?.as_str()
+  }
+
+  pub fn summary(&self) -> Option<&str> {
+    self.0.as_object()?.get("summary")?.as_str()
+  }
+
+  pub fn description(&self) -> Option<&str> {
+    self.0.as_object()?.get("description")?.as_str()
+  }
+
+  pub fn parent(&self) -> Option<&str> {
+    self.0.as_object()?.get("parent")?.as_str()
+  }
+
+  pub fn kind(&self) -> Option<&str> {
+    self.0.as_object()?.get("kind")?.as_str()
+  }
+}
+
+impl From<serde_json::Value> for Tag {
+  fn from(value: serde_json::Value) -> Self {
+    Self(value)
+  }
+}
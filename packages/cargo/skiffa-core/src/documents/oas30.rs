@@ -1,4 +1,5 @@
-mod document;
-mod nodes;
+mod schema;
+mod upgrade;
 
-pub use document::*;
+pub(crate) use schema::*;
+pub use upgrade::*;
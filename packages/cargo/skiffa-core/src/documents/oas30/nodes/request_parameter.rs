@@ -1,30 +0,0 @@
-#[derive(Clone)]
-pub struct RequestParameter(serde_json::Value);
-
-impl RequestParameter {
-  pub fn schema_pointer(&self) -> Option<Vec<String>> {
-    self
-      .0
-      .as_object()?
-      .get("schema")
-      .map(|_value| vec!["schema".to_owned()])
-  }
-
-  pub fn r#in(&self) -> Option<&str> {
-    self.0.as_object()?.get("in")?.as_str()
-  }
-
-  pub fn name(&self) -> Option<&str> {
-    self.0.as_object()?.get("name")?.as_str()
-  }
-
-  pub fn required(&self) -> Option<bool> {
-    self.0.as_object()?.get("required")?.as_bool()
-  }
-}
-
-impl From<serde_json::Value> for RequestParameter {
-  fn from(value: serde_json::Value) -> Self {
-    Self(value)
-  }
-}
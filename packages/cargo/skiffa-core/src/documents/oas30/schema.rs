@@ -0,0 +1,46 @@
+use serde_json::Value;
+
+/// Keywords of schemas that contain a schema
+const SCHEMA_KEYWORDS: [&str; 3] = ["items", "additionalProperties", "not"];
+/// Keywords of schemas that contain a list of schemas
+const SCHEMA_LIST_KEYWORDS: [&str; 3] = ["allOf", "anyOf", "oneOf"];
+/// Keywords of schemas that contain a map of schemas
+const SCHEMA_MAP_KEYWORDS: [&str; 1] = ["properties"];
+
+/// Map the sub schemas in a member of an OpenApi 3.0 (or Swagger 2.0) schema.
+/// The map function gets the pointer of the sub schema relative to the
+/// member. Returns `None` if the member does not contain sub schemas.
+///
+pub(crate) fn map_sub_schemas(
+  member: &str,
+  value: &Value,
+  mut map: impl FnMut(Vec<String>, &Value) -> Value,
+) -> Option<Value> {
+  if SCHEMA_KEYWORDS.contains(&member) {
+    return Some(map(Vec::new(), value));
+  }
+
+  if SCHEMA_LIST_KEYWORDS.contains(&member) {
+    return Some(match value.as_array() {
+      Some(schemas) => schemas
+        .iter()
+        .enumerate()
+        .map(|(index, schema)| map(vec![index.to_string()], schema))
+        .collect(),
+      None => value.clone(),
+    });
+  }
+
+  if SCHEMA_MAP_KEYWORDS.contains(&member) {
+    return Some(match value.as_object() {
+      Some(schemas) => schemas
+        .iter()
+        .map(|(name, schema)| (name.clone(), map(vec![name.clone()], schema)))
+        .collect::<serde_json::Map<_, _>>()
+        .into(),
+      None => value.clone(),
+    });
+  }
+
+  None
+}
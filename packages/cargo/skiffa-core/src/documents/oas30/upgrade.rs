@@ -0,0 +1,485 @@
+use super::map_sub_schemas;
+use crate::documents::DocumentType;
+use crate::error::Error;
+use crate::utils::{NodeCache, NodeLocation};
+use serde_json::{Map, Value};
+
+/// The version that is written in the `openapi` field of upgraded documents
+const OPENAPI_V31_VERSION: &str = "3.1.0";
+
+/// Something in the document that could not be upgraded without changing or
+/// losing information.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpgradeIssue {
+  pub location: NodeLocation,
+  pub message: String,
+}
+
+/// An upgraded document and the issues found while upgrading
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpgradedDocument {
+  pub node: Value,
+  pub issues: Vec<UpgradeIssue>,
+}
+
+/// Upgrade the OpenApi 3.0 document at the location in the cache to OpenApi
+/// 3.1. The node in the cache is not changed.
+///
+/// - `openapi` is set to 3.1
+/// - `nullable` (and `x-nullable`) become a `null` type
+/// - boolean `exclusiveMinimum` and `exclusiveMaximum` become numbers
+/// - `example` in a schema becomes `examples`
+/// - binary strings get a `contentMediaType`, base64 strings a
+///   `contentEncoding`
+/// - siblings of `$ref` in schemas are removed, these are ignored in 3.0 but
+///   not in 3.1
+/// - `x-webhooks` becomes `webhooks`
+///
+/// Anything that can not be upgraded losslessly is reported as an issue.
+///
+pub fn upgrade_to_oas31(
+  cache: &NodeCache,
+  retrieval_location: &NodeLocation,
+) -> Result<UpgradedDocument, Error> {
+  let node = cache.get_node(retrieval_location).ok_or(Error::NotFound)?;
+  upgrade_node_to_oas31(node, retrieval_location)
+}
+
+/// Upgrade an OpenApi 3.0 root node that is not in a cache, like
+/// `upgrade_to_oas31`. The locations of issues are in the document at the
+/// retrieval location.
+///
+pub fn upgrade_node_to_oas31(
+  node: &Value,
+  retrieval_location: &NodeLocation,
+) -> Result<UpgradedDocument, Error> {
+  if DocumentType::try_from(node) != Ok(DocumentType::OpenApiV30) {
+    return Err(Error::DocumentTypeError);
+  }
+
+  let mut upgrader = Upgrader { issues: Vec::new() };
+  let node = upgrader.upgrade_document(retrieval_location, node);
+
+  Ok(UpgradedDocument {
+    node,
+    issues: upgrader.issues,
+  })
+}
+
+/// Upgrade a single OpenApi 3.0 schema, like the schemas in a document are
+/// upgraded by `upgrade_to_oas31`. The location is the location of the
+/// schema, it is used for the issues.
+///
+pub(crate) fn upgrade_schema_to_oas31(
+  schema: &Value,
+  location: &NodeLocation,
+) -> (Value, Vec<UpgradeIssue>) {
+  let mut upgrader = Upgrader { issues: Vec::new() };
+  let schema = upgrader.upgrade_schema(location, schema);
+  (schema, upgrader.issues)
+}
+
+struct Upgrader {
+  issues: Vec<UpgradeIssue>,
+}
+
+impl Upgrader {
+  fn add_issue(&mut self, location: &NodeLocation, message: impl Into<String>) {
+    self.issues.push(UpgradeIssue {
+      location: location.clone(),
+      message: message.into(),
+    });
+  }
+
+  fn upgrade_document(&mut self, location: &NodeLocation, node: &Value) -> Value {
+    let Some(members) = node.as_object() else {
+      return node.clone();
+    };
+
+    let mut result = Map::new();
+    for (member, value) in members {
+      let member_location = location.push_pointer(vec![member.clone()]);
+      match member.as_str() {
+        "openapi" => {
+          result.insert(member.clone(), OPENAPI_V31_VERSION.into());
+        }
+        "x-webhooks" if !members.contains_key("webhooks") => {
+          result.insert(
+            "webhooks".to_owned(),
+            self.upgrade_node(&member_location, value),
+          );
+        }
+        "components" => {
+          result.insert(
+            member.clone(),
+            self.upgrade_components(&member_location, value),
+          );
+        }
+        member if member.starts_with("x-") => {
+          result.insert(member.to_owned(), value.clone());
+        }
+        _ => {
+          result.insert(member.clone(), self.upgrade_node(&member_location, value));
+        }
+      }
+    }
+
+    result.into()
+  }
+
+  fn upgrade_components(&mut self, location: &NodeLocation, node: &Value) -> Value {
+    let Some(members) = node.as_object() else {
+      return node.clone();
+    };
+
+    let mut result = Map::new();
+    for (member, value) in members {
+      let member_location = location.push_pointer(vec![member.clone()]);
+      let value = match (member.as_str(), value.as_object()) {
+        ("schemas", Some(schemas)) => schemas
+          .iter()
+          .map(|(name, schema)| {
+            let schema_location = member_location.push_pointer(vec![name.clone()]);
+            (name.clone(), self.upgrade_schema(&schema_location, schema))
+          })
+          .collect::<Map<_, _>>()
+          .into(),
+        (member, _) if member.starts_with("x-") => value.clone(),
+        _ => self.upgrade_node(&member_location, value),
+      };
+      result.insert(member.clone(), value);
+    }
+
+    result.into()
+  }
+
+  /// Upgrade a node that is not a schema, schemas are found in the `schema`
+  /// member of parameters, headers and media types.
+  ///
+  fn upgrade_node(&mut self, location: &NodeLocation, node: &Value) -> Value {
+    match node {
+      Value::Array(items) => items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+          self.upgrade_node(&location.push_pointer(vec![index.to_string()]), item)
+        })
+        .collect(),
+      Value::Object(members) => {
+        let mut result = Map::new();
+        for (member, value) in members {
+          let member_location = location.push_pointer(vec![member.clone()]);
+          let value = match member.as_str() {
+            "schema" => self.upgrade_schema(&member_location, value),
+            "example" | "examples" => value.clone(),
+            member if member.starts_with("x-") => value.clone(),
+            _ => self.upgrade_node(&member_location, value),
+          };
+          result.insert(member.clone(), value);
+        }
+        result.into()
+      }
+      _ => node.clone(),
+    }
+  }
+
+  fn upgrade_schema(&mut self, location: &NodeLocation, schema: &Value) -> Value {
+    let Some(members) = schema.as_object() else {
+      return schema.clone();
+    };
+
+    if members.contains_key("$ref") {
+      let siblings: Vec<_> = members
+        .keys()
+        .filter(|member| *member != "$ref" && !member.starts_with("x-"))
+        .cloned()
+        .collect();
+      if !siblings.is_empty() {
+        self.add_issue(
+          location,
+          format!(
+            "{} next to $ref is ignored in 3.0 and removed",
+            siblings.join(", ")
+          ),
+        );
+      }
+      return members
+        .iter()
+        .filter(|(member, _value)| *member == "$ref" || member.starts_with("x-"))
+        .map(|(member, value)| (member.clone(), value.clone()))
+        .collect::<Map<_, _>>()
+        .into();
+    }
+
+    let mut result = Map::new();
+    for (member, value) in members {
+      let member_location = location.push_pointer(vec![member.clone()]);
+      let value = match member.as_str() {
+        "nullable" | "x-nullable" | "exclusiveMinimum" | "exclusiveMaximum" => continue,
+        "example" => {
+          if !members.contains_key("examples") {
+            result.insert("examples".to_owned(), vec![value.clone()].into());
+          }
+          continue;
+        }
+        member => map_sub_schemas(member, value, |pointer, schema| {
+          self.upgrade_schema(&member_location.push_pointer(pointer), schema)
+        })
+        .unwrap_or_else(|| value.clone()),
+      };
+      result.insert(member.clone(), value);
+    }
+
+    self.upgrade_nullable(location, members, &mut result);
+    self.upgrade_exclusive(
+      location,
+      members,
+      &mut result,
+      "exclusiveMinimum",
+      "minimum",
+    );
+    self.upgrade_exclusive(
+      location,
+      members,
+      &mut result,
+      "exclusiveMaximum",
+      "maximum",
+    );
+
+    if result.get("type").and_then(Value::as_str) == Some("string") {
+      match result.get("format").and_then(Value::as_str) {
+        Some("binary") => {
+          result.remove("format");
+          result.insert(
+            "contentMediaType".to_owned(),
+            "application/octet-stream".into(),
+          );
+        }
+        Some("byte") => {
+          result.remove("format");
+          result.insert("contentEncoding".to_owned(), "base64".into());
+        }
+        _ => {}
+      }
+    }
+
+    result.into()
+  }
+
+  /// A nullable schema allows `null` as type and, if there is an enum, as
+  /// value.
+  ///
+  fn upgrade_nullable(
+    &mut self,
+    location: &NodeLocation,
+    members: &Map<String, Value>,
+    result: &mut Map<String, Value>,
+  ) {
+    let nullable = ["nullable", "x-nullable"]
+      .iter()
+      .any(|member| members.get(*member).and_then(Value::as_bool) == Some(true));
+    if !nullable {
+      return;
+    }
+
+    match result.get("type").cloned() {
+      Some(Value::String(r#type)) => {
+        result.insert("type".to_owned(), vec![r#type, "null".to_owned()].into());
+      }
+      _ => {
+        self.add_issue(
+          location,
+          "nullable without a type has no effect in 3.0 and is removed",
+        );
+        return;
+      }
+    }
+
+    if let Some(Value::Array(values)) = result.get_mut("enum") {
+      if !values.contains(&Value::Null) {
+        values.push(Value::Null);
+      }
+    }
+  }
+
+  /// A boolean exclusive keyword makes the limit exclusive, in 3.1 the
+  /// exclusive keyword is the limit.
+  ///
+  fn upgrade_exclusive(
+    &mut self,
+    location: &NodeLocation,
+    members: &Map<String, Value>,
+    result: &mut Map<String, Value>,
+    exclusive_keyword: &str,
+    limit_keyword: &str,
+  ) {
+    match members.get(exclusive_keyword) {
+      None => {}
+      Some(Value::Bool(false)) => {}
+      Some(Value::Bool(true)) => match result.remove(limit_keyword) {
+        Some(limit) => {
+          result.insert(exclusive_keyword.to_owned(), limit);
+        }
+        None => self.add_issue(
+          &location.push_pointer(vec![exclusive_keyword.to_owned()]),
+          format!(
+            "{} without {} has no effect in 3.0 and is removed",
+            exclusive_keyword, limit_keyword
+          ),
+        ),
+      },
+      // already a number, this is not valid in 3.0 but it is in 3.1
+      Some(value) => {
+        result.insert(exclusive_keyword.to_owned(), value.clone());
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn test_upgrade_to_oas31() {
+    let location: NodeLocation = "http://example.com/api.yaml".parse().unwrap();
+    let mut cache = NodeCache::new();
+    cache
+      .load_from_node(
+        &location,
+        json!({
+          "openapi": "3.0.3",
+          "info": { "title": "pets", "version": "1.0" },
+          "paths": {
+            "/pets": {
+              "post": {
+                "parameters": [{
+                  "name": "limit",
+                  "in": "query",
+                  "schema": { "type": "integer", "minimum": 0, "exclusiveMinimum": true },
+                  "example": 10,
+                }],
+                "requestBody": {
+                  "content": {
+                    "multipart/form-data": {
+                      "schema": {
+                        "type": "object",
+                        "properties": {
+                          "photo": { "type": "string", "format": "binary" },
+                          "thumbnail": { "type": "string", "format": "byte" },
+                        },
+                      },
+                      "example": { "schema": "not a schema" },
+                    },
+                  },
+                },
+                "responses": {},
+              },
+            },
+          },
+          "x-webhooks": {
+            "new-pet": {
+              "post": {
+                "requestBody": {
+                  "content": {
+                    "application/json": { "schema": { "$ref": "#/components/schemas/pet" } },
+                  },
+                },
+              },
+            },
+          },
+          "components": {
+            "schemas": {
+              "pet": {
+                "type": "object",
+                "properties": {
+                  "name": { "type": "string", "nullable": true, "example": "fluffy" },
+                  "kind": { "type": "string", "enum": ["cat", "dog"], "x-nullable": true },
+                  "age": { "type": "integer", "maximum": 100, "exclusiveMaximum": false },
+                  "owner": { "$ref": "#/components/schemas/owner", "description": "owner" },
+                  "weight": { "nullable": true, "exclusiveMaximum": true },
+                },
+              },
+            },
+          },
+        }),
+      )
+      .unwrap();
+
+    let upgraded = upgrade_to_oas31(&cache, &location).unwrap();
+    let node = &upgraded.node;
+    assert_eq!(node["openapi"], "3.1.0");
+
+    let operation = &node["paths"]["/pets"]["post"];
+    assert_eq!(
+      operation["parameters"][0],
+      json!({
+        "name": "limit",
+        "in": "query",
+        "schema": { "type": "integer", "exclusiveMinimum": 0 },
+        "example": 10,
+      })
+    );
+    assert_eq!(
+      operation["requestBody"]["content"]["multipart/form-data"],
+      json!({
+        "schema": {
+          "type": "object",
+          "properties": {
+            "photo": { "type": "string", "contentMediaType": "application/octet-stream" },
+            "thumbnail": { "type": "string", "contentEncoding": "base64" },
+          },
+        },
+        "example": { "schema": "not a schema" },
+      })
+    );
+    assert!(node.get("x-webhooks").is_none());
+    assert_eq!(
+      node["webhooks"]["new-pet"]["post"]["requestBody"]["content"]["application/json"]["schema"],
+      json!({ "$ref": "#/components/schemas/pet" })
+    );
+
+    assert_eq!(
+      node["components"]["schemas"]["pet"]["properties"],
+      json!({
+        "name": { "type": ["string", "null"], "examples": ["fluffy"] },
+        "kind": { "type": ["string", "null"], "enum": ["cat", "dog", null] },
+        "age": { "type": "integer", "maximum": 100 },
+        "owner": { "$ref": "#/components/schemas/owner" },
+        "weight": {},
+      })
+    );
+
+    let properties_location = location.push_pointer(
+      vec!["components", "schemas", "pet", "properties"]
+        .into_iter()
+        .map(ToOwned::to_owned)
+        .collect(),
+    );
+    let mut issues: Vec<_> = upgraded
+      .issues
+      .iter()
+      .map(|issue| issue.location.clone())
+      .collect();
+    issues.sort();
+    assert_eq!(
+      issues,
+      vec![
+        properties_location.push_pointer(vec!["owner".to_owned()]),
+        properties_location.push_pointer(vec!["weight".to_owned()]),
+        properties_location.push_pointer(vec!["weight".to_owned(), "exclusiveMaximum".to_owned()]),
+      ]
+    );
+
+    let swagger_location: NodeLocation = "http://example.com/swagger.yaml".parse().unwrap();
+    cache
+      .load_from_node(&swagger_location, json!({ "swagger": "2.0" }))
+      .unwrap();
+    assert_eq!(
+      upgrade_to_oas31(&cache, &swagger_location),
+      Err(Error::DocumentTypeError)
+    );
+  }
+}
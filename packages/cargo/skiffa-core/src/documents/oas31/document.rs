@@ -1,23 +1,34 @@
+use crate::documents::common::{
+  get_referenced_locations_from_node, get_schema_locations_from_node,
+};
+use crate::utils::sync;
 use crate::utils::NodeLocation;
 use crate::{
-  documents::{DocumentContext, DocumentError, DocumentInterface},
+  documents::{DocumentContext, DocumentError, DocumentInterface, SchemaLocation},
   models,
 };
-use std::rc;
 
-#[allow(dead_code)]
+/// There is no api model for this document type yet, references and schemas
+/// are found by walking the document.
+///
 pub struct Document {
+  context: sync::Weak<DocumentContext>,
   retrieval_location: NodeLocation,
   node: serde_json::Value,
 }
 
 impl Document {
-  pub fn new(context: rc::Weak<DocumentContext>, retrieval_location: NodeLocation) -> Self {
-    let context = context.upgrade().unwrap();
-    let node = context.get_node(&retrieval_location).unwrap();
+  pub fn new(context: sync::Weak<DocumentContext>, retrieval_location: NodeLocation) -> Self {
+    let node = context
+      .upgrade()
+      .unwrap()
+      .get_node(&retrieval_location)
+      .unwrap()
+      .clone();
     Self {
+      context,
       retrieval_location,
-      node: node.clone(),
+      node,
     }
   }
 }
@@ -31,15 +42,36 @@ impl DocumentInterface for Document {
     self.retrieval_location.clone()
   }
 
-  fn get_api_model(&self) -> Result<rc::Rc<models::Api>, DocumentError> {
-    todo!()
+  fn get_identity_location(&self) -> Result<Option<NodeLocation>, DocumentError> {
+    Ok(None)
+  }
+
+  fn get_api_model(&self) -> Result<sync::Shared<models::Api>, DocumentError> {
+    Err(DocumentError::ModelNotImplemented)
+  }
+
+  fn get_async_api_model(&self) -> Result<sync::Shared<models::AsyncApi>, DocumentError> {
+    Err(DocumentError::ModelNotSupported)
   }
 
   fn get_referenced_locations(&self) -> Result<Vec<NodeLocation>, DocumentError> {
-    todo!()
+    get_referenced_locations_from_node(
+      &self.context,
+      self.retrieval_location.clone(),
+      &self.node,
+      &["components", "schemas"],
+    )
+    .into_iter()
+    .collect()
   }
 
-  fn get_schema_locations(&self) -> Result<Vec<NodeLocation>, DocumentError> {
-    todo!()
+  fn get_schema_locations(&self) -> Result<Vec<SchemaLocation>, DocumentError> {
+    get_schema_locations_from_node(
+      self.retrieval_location.clone(),
+      &self.node,
+      &["components", "schemas"],
+    )
+    .into_iter()
+    .collect()
   }
 }
@@ -1,3 +1,5 @@
+mod convert;
 mod document;
 
+pub use convert::*;
 pub use document::*;
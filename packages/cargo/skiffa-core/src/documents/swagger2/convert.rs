@@ -0,0 +1,1129 @@
+use crate::documents::oas30::{
+  map_sub_schemas, upgrade_node_to_oas31, UpgradeIssue, UpgradedDocument,
+};
+use crate::documents::DocumentType;
+use crate::error::Error;
+use crate::utils::NodeLocation;
+use serde_json::{Map, Value};
+
+/// The version that is written in the `openapi` field of converted documents
+const OPENAPI_V30_VERSION: &str = "3.0.3";
+
+/// Http methods that may have an operation in a Swagger 2.0 path item
+const METHODS: [&str; 7] = ["get", "put", "post", "delete", "options", "head", "patch"];
+
+/// Keywords of (non body) parameters, items and headers that are part of the
+/// schema of the parameter in OpenApi 3.
+///
+const PARAMETER_SCHEMA_KEYWORDS: [&str; 16] = [
+  "type",
+  "format",
+  "items",
+  "default",
+  "maximum",
+  "exclusiveMaximum",
+  "minimum",
+  "exclusiveMinimum",
+  "maxLength",
+  "minLength",
+  "pattern",
+  "maxItems",
+  "minItems",
+  "uniqueItems",
+  "enum",
+  "multipleOf",
+];
+
+const FORM_MEDIA_TYPES: [&str; 2] = ["application/x-www-form-urlencoded", "multipart/form-data"];
+const DEFAULT_MEDIA_TYPE: &str = "application/json";
+
+/// Convert the root node of a Swagger 2.0 document into an equivalent
+/// OpenApi 3.0 or 3.1 (depending on the target) root node. The document is
+/// converted to 3.0 first, for 3.1 it is then upgraded like `upgrade_to_oas31`
+/// does. The locations of the issues of the upgrade are in the 3.0 document,
+/// with the root at the retrieval location.
+///
+/// - `host`, `basePath` and `schemes` become `servers`
+/// - `body` and `formData` parameters become a `requestBody`, with a media
+///   type for every type in `consumes`
+/// - response schemas get a media type for every type in `produces`
+/// - `definitions`, `parameters`, `responses` and `securityDefinitions` move
+///   to `components`, references to them are rewritten. References to other
+///   documents are not rewritten, those documents are still Swagger 2.0. They
+///   are reported as issues, with their location in the Swagger 2.0 document.
+///
+/// Extensions (`x-`) are kept.
+///
+pub fn convert_to_openapi(
+  node: &Value,
+  retrieval_location: &NodeLocation,
+  target: DocumentType,
+) -> Result<UpgradedDocument, Error> {
+  if DocumentType::try_from(node) != Ok(DocumentType::Swagger2) {
+    return Err(Error::DocumentTypeError);
+  }
+  let root = node.as_object().ok_or(Error::DocumentTypeError)?;
+  let mut issues = get_external_reference_issues(node, retrieval_location.clone(), None);
+  let node = Converter { root }.convert_document();
+
+  match target {
+    DocumentType::OpenApiV30 => Ok(UpgradedDocument { node, issues }),
+    DocumentType::OpenApiV31 => {
+      let upgraded_document = upgrade_node_to_oas31(&node, retrieval_location)?;
+      issues.extend(upgraded_document.issues);
+      Ok(UpgradedDocument {
+        node: upgraded_document.node,
+        issues,
+      })
+    }
+    _ => Err(Error::UnsupportedVersion),
+  }
+}
+
+/// Members that hold literal values, references in them are not references
+const LITERAL_MEMBERS: [&str; 4] = ["default", "enum", "example", "examples"];
+
+/// References to other documents are not converted, find them in the node
+/// so we can report them. The member is the member of the parent of the
+/// node that holds the node.
+///
+fn get_external_reference_issues(
+  node: &Value,
+  location: NodeLocation,
+  member: Option<&str>,
+) -> Vec<UpgradeIssue> {
+  let mut issues = Vec::new();
+
+  match node {
+    Value::Object(object_node) => {
+      if let Some(reference) = object_node.get("$ref").and_then(Value::as_str) {
+        if !is_internal_reference(reference) {
+          let message = if member == Some("parameters") {
+            format!(
+              "parameter {} in another document is not converted, it could be a body or form parameter",
+              reference
+            )
+          } else {
+            format!(
+              "reference {} to another document is not converted, that document should be converted as well",
+              reference
+            )
+          };
+          issues.push(UpgradeIssue {
+            location: location.clone(),
+            message,
+          });
+        }
+      }
+      for (member, node) in object_node {
+        if member.starts_with("x-") || LITERAL_MEMBERS.contains(&member.as_str()) {
+          continue;
+        }
+        issues.extend(get_external_reference_issues(
+          node,
+          location.push_pointer(vec![member.clone()]),
+          Some(member),
+        ));
+      }
+    }
+    Value::Array(array_node) => {
+      for (index, node) in array_node.iter().enumerate() {
+        issues.extend(get_external_reference_issues(
+          node,
+          location.push_pointer(vec![index.to_string()]),
+          member,
+        ));
+      }
+    }
+    _ => {}
+  }
+
+  issues
+}
+
+struct Converter<'a> {
+  root: &'a Map<String, Value>,
+}
+
+impl<'a> Converter<'a> {
+  fn convert_document(&self) -> Value {
+    let mut result = Map::new();
+
+    result.insert("openapi".to_owned(), OPENAPI_V30_VERSION.into());
+
+    for (member, value) in self.root {
+      match member.as_str() {
+        "info" | "tags" | "externalDocs" | "security" => {
+          result.insert(member.clone(), value.clone());
+        }
+        member if member.starts_with("x-") => {
+          result.insert(member.to_owned(), value.clone());
+        }
+        _ => {}
+      }
+    }
+
+    if let Some(servers) = self.convert_servers() {
+      result.insert("servers".to_owned(), servers);
+    }
+
+    let mut paths = Map::new();
+    for (pattern, path_item) in get_members(self.root, "paths") {
+      paths.insert(pattern.clone(), self.convert_path_item(path_item));
+    }
+    result.insert("paths".to_owned(), paths.into());
+
+    let components = self.convert_components();
+    if !components.is_empty() {
+      result.insert("components".to_owned(), components.into());
+    }
+
+    result.into()
+  }
+
+  fn convert_servers(&self) -> Option<Value> {
+    let host = self.root.get("host").and_then(Value::as_str);
+    let base_path = self.root.get("basePath").and_then(Value::as_str);
+
+    let urls: Vec<String> = match (host, base_path) {
+      (None, None) => return None,
+      (None, Some(base_path)) => vec![base_path.to_owned()],
+      (Some(host), base_path) => {
+        let base_path = base_path.unwrap_or_default();
+        let schemes: Vec<_> = get_items(self.root, "schemes")
+          .filter_map(Value::as_str)
+          .collect();
+        if schemes.is_empty() {
+          vec![format!("//{}{}", host, base_path)]
+        } else {
+          schemes
+            .into_iter()
+            .map(|scheme| format!("{}://{}{}", scheme, host, base_path))
+            .collect()
+        }
+      }
+    };
+
+    Some(
+      urls
+        .into_iter()
+        .map(|url| Value::from(Map::from_iter([("url".to_owned(), url.into())])))
+        .collect(),
+    )
+  }
+
+  fn convert_components(&self) -> Map<String, Value> {
+    let mut components = Map::new();
+
+    let schemas: Map<_, _> = get_members(self.root, "definitions")
+      .map(|(name, schema)| (name.clone(), self.convert_schema(schema)))
+      .collect();
+    if !schemas.is_empty() {
+      components.insert("schemas".to_owned(), schemas.into());
+    }
+
+    let mut parameters = Map::new();
+    let mut request_bodies = Map::new();
+    for (name, parameter) in get_members(self.root, "parameters") {
+      match get_parameter_in(parameter) {
+        // form parameters can not be a component, they are inlined
+        Some("formData") => {}
+        Some("body") => {
+          let consumes = self.get_media_types(None, "consumes");
+          request_bodies.insert(
+            name.clone(),
+            self.convert_body_parameter(parameter, &consumes),
+          );
+        }
+        _ => {
+          parameters.insert(name.clone(), self.convert_parameter(parameter));
+        }
+      }
+    }
+    if !parameters.is_empty() {
+      components.insert("parameters".to_owned(), parameters.into());
+    }
+    if !request_bodies.is_empty() {
+      components.insert("requestBodies".to_owned(), request_bodies.into());
+    }
+
+    let produces = self.get_media_types(None, "produces");
+    let responses: Map<_, _> = get_members(self.root, "responses")
+      .map(|(name, response)| (name.clone(), self.convert_response(response, &produces)))
+      .collect();
+    if !responses.is_empty() {
+      components.insert("responses".to_owned(), responses.into());
+    }
+
+    let security_schemes: Map<_, _> = get_members(self.root, "securityDefinitions")
+      .map(|(name, scheme)| (name.clone(), convert_security_scheme(scheme)))
+      .collect();
+    if !security_schemes.is_empty() {
+      components.insert("securitySchemes".to_owned(), security_schemes.into());
+    }
+
+    components
+  }
+
+  fn convert_path_item(&self, path_item: &Value) -> Value {
+    let Some(path_item) = path_item.as_object() else {
+      return path_item.clone();
+    };
+    let mut result = Map::new();
+
+    for (member, value) in path_item {
+      match member.as_str() {
+        "$ref" => {
+          result.insert(member.clone(), value.clone());
+        }
+        member if member.starts_with("x-") => {
+          result.insert(member.to_owned(), value.clone());
+        }
+        _ => {}
+      }
+    }
+
+    let path_parameters = self.split_parameters(path_item.get("parameters"));
+    if !path_parameters.parameters.is_empty() {
+      result.insert(
+        "parameters".to_owned(),
+        path_parameters.parameters.clone().into(),
+      );
+    }
+
+    for method in METHODS {
+      if let Some(operation) = path_item.get(method).and_then(Value::as_object) {
+        result.insert(
+          method.to_owned(),
+          self.convert_operation(operation, &path_parameters),
+        );
+      }
+    }
+
+    result.into()
+  }
+
+  fn convert_operation(
+    &self,
+    operation: &Map<String, Value>,
+    path_parameters: &SplitParameters,
+  ) -> Value {
+    let mut result = Map::new();
+
+    for (member, value) in operation {
+      match member.as_str() {
+        "tags" | "summary" | "description" | "externalDocs" | "operationId" | "deprecated"
+        | "security" => {
+          result.insert(member.clone(), value.clone());
+        }
+        member if member.starts_with("x-") => {
+          result.insert(member.to_owned(), value.clone());
+        }
+        _ => {}
+      }
+    }
+
+    let operation_parameters = self.split_parameters(operation.get("parameters"));
+    if !operation_parameters.parameters.is_empty() {
+      result.insert(
+        "parameters".to_owned(),
+        operation_parameters.parameters.into(),
+      );
+    }
+
+    /*
+    the body and form parameters of the operation replace those of the path
+    */
+    let consumes = self.get_media_types(Some(operation), "consumes");
+    let request_body = match (operation_parameters.body, &path_parameters.body) {
+      (Some(body), _) => Some(self.convert_request_body(body, &consumes)),
+      (None, Some(body)) => Some(self.convert_request_body(body.clone(), &consumes)),
+      (None, None) => {
+        let mut form_parameters = path_parameters.form.clone();
+        form_parameters.retain(|path_parameter| {
+          !operation_parameters
+            .form
+            .iter()
+            .any(|parameter| parameter.get("name") == path_parameter.get("name"))
+        });
+        form_parameters.extend(operation_parameters.form);
+        if form_parameters.is_empty() {
+          None
+        } else {
+          Some(self.convert_form_parameters(&form_parameters, &consumes))
+        }
+      }
+    };
+    if let Some(request_body) = request_body {
+      result.insert("requestBody".to_owned(), request_body);
+    }
+
+    let produces = self.get_media_types(Some(operation), "produces");
+    let responses: Map<_, _> = get_members(operation, "responses")
+      .map(|(status, response)| (status.clone(), self.convert_response(response, &produces)))
+      .collect();
+    result.insert("responses".to_owned(), responses.into());
+
+    result.into()
+  }
+
+  /// The media types of an operation, from the operation or the document, if
+  /// there are none json is assumed.
+  ///
+  fn get_media_types(&self, operation: Option<&Map<String, Value>>, member: &str) -> Vec<String> {
+    let media_types = operation
+      .and_then(|operation| operation.get(member))
+      .or_else(|| self.root.get(member))
+      .and_then(Value::as_array)
+      .map(|media_types| {
+        media_types
+          .iter()
+          .filter_map(Value::as_str)
+          .map(ToOwned::to_owned)
+          .collect::<Vec<_>>()
+      })
+      .unwrap_or_default();
+
+    if media_types.is_empty() {
+      vec![DEFAULT_MEDIA_TYPE.to_owned()]
+    } else {
+      media_types
+    }
+  }
+
+  /// Split parameters in parameters that stay parameters and the body or form
+  /// parameters that will be the request body. References to body and form
+  /// parameters are resolved via the parameters of the document.
+  ///
+  fn split_parameters(&self, parameters: Option<&Value>) -> SplitParameters {
+    let mut result = SplitParameters::default();
+
+    for parameter in parameters.and_then(Value::as_array).into_iter().flatten() {
+      let reference = parameter.get("$ref").and_then(Value::as_str);
+      let resolved = match reference {
+        Some(reference) => self.resolve_parameter_reference(reference),
+        None => Some(parameter),
+      };
+
+      match (reference, resolved.and_then(get_parameter_in)) {
+        (Some(reference), Some("body")) => {
+          result.body = Some(RequestBody::Reference(convert_reference_with(
+            reference,
+            "/parameters/",
+            "/components/requestBodies/",
+          )))
+        }
+        (None, Some("body")) => result.body = Some(RequestBody::Parameter(parameter.clone())),
+        (_, Some("formData")) => {
+          if let Some(parameter) = resolved.and_then(Value::as_object) {
+            result.form.push(parameter.clone());
+          }
+        }
+        (Some(reference), _) => result.parameters.push(make_reference(reference)),
+        (None, _) => result.parameters.push(self.convert_parameter(parameter)),
+      }
+    }
+
+    result
+  }
+
+  /// Find the parameter in the document that the reference points to, only
+  /// references within the document can be resolved.
+  ///
+  fn resolve_parameter_reference(&self, reference: &str) -> Option<&'a Value> {
+    let name = reference.strip_prefix("#/parameters/")?;
+    self.root.get("parameters")?.get(unescape_pointer(name))
+  }
+
+  fn convert_request_body(&self, body: RequestBody, consumes: &[String]) -> Value {
+    match body {
+      RequestBody::Reference(reference) => make_reference(&reference),
+      RequestBody::Parameter(parameter) => self.convert_body_parameter(&parameter, consumes),
+    }
+  }
+
+  fn convert_body_parameter(&self, parameter: &Value, consumes: &[String]) -> Value {
+    let mut result = Map::new();
+    let Some(parameter) = parameter.as_object() else {
+      return result.into();
+    };
+
+    for (member, value) in parameter {
+      match member.as_str() {
+        "description" | "required" => {
+          result.insert(member.clone(), value.clone());
+        }
+        member if member.starts_with("x-") => {
+          result.insert(member.to_owned(), value.clone());
+        }
+        _ => {}
+      }
+    }
+
+    let schema = parameter
+      .get("schema")
+      .map(|schema| self.convert_schema(schema))
+      .unwrap_or_else(|| Map::new().into());
+    let content: Map<_, _> = consumes
+      .iter()
+      .filter(|media_type| !FORM_MEDIA_TYPES.contains(&media_type.as_str()))
+      .map(|media_type| (media_type.clone(), make_media_type(schema.clone())))
+      .collect();
+    let content = if content.is_empty() {
+      Map::from_iter([(DEFAULT_MEDIA_TYPE.to_owned(), make_media_type(schema))])
+    } else {
+      content
+    };
+    result.insert("content".to_owned(), content.into());
+
+    result.into()
+  }
+
+  /// Form parameters become the properties of an object schema, that schema
+  /// is used for the form media types in consumes.
+  ///
+  fn convert_form_parameters(
+    &self,
+    parameters: &[Map<String, Value>],
+    consumes: &[String],
+  ) -> Value {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    let mut has_file = false;
+
+    for parameter in parameters {
+      let Some(name) = parameter.get("name").and_then(Value::as_str) else {
+        continue;
+      };
+      has_file = has_file || parameter.get("type").and_then(Value::as_str) == Some("file");
+
+      let mut schema = self.make_parameter_schema(parameter);
+      if let (Some(schema), Some(description)) =
+        (schema.as_object_mut(), parameter.get("description"))
+      {
+        schema.insert("description".to_owned(), description.clone());
+      }
+      properties.insert(name.to_owned(), schema);
+
+      if parameter.get("required").and_then(Value::as_bool) == Some(true) {
+        required.push(Value::from(name));
+      }
+    }
+
+    let mut schema = Map::new();
+    schema.insert("type".to_owned(), "object".into());
+    schema.insert("properties".to_owned(), properties.into());
+    if !required.is_empty() {
+      schema.insert("required".to_owned(), required.into());
+    }
+    let schema = Value::from(schema);
+
+    let mut media_types: Vec<_> = consumes
+      .iter()
+      .filter(|media_type| FORM_MEDIA_TYPES.contains(&media_type.as_str()))
+      .cloned()
+      .collect();
+    if media_types.is_empty() {
+      media_types.push(
+        if has_file {
+          "multipart/form-data"
+        } else {
+          "application/x-www-form-urlencoded"
+        }
+        .to_owned(),
+      );
+    }
+
+    let content: Map<_, _> = media_types
+      .into_iter()
+      .map(|media_type| (media_type, make_media_type(schema.clone())))
+      .collect();
+    Map::from_iter([("content".to_owned(), Value::from(content))]).into()
+  }
+
+  fn convert_parameter(&self, parameter: &Value) -> Value {
+    let Some(parameter) = parameter.as_object() else {
+      return parameter.clone();
+    };
+    if let Some(reference) = parameter.get("$ref").and_then(Value::as_str) {
+      return make_reference(reference);
+    }
+
+    let mut result = Map::new();
+    for (member, value) in parameter {
+      match member.as_str() {
+        "name" | "in" | "description" | "required" | "allowEmptyValue" => {
+          result.insert(member.clone(), value.clone());
+        }
+        member if member.starts_with("x-") => {
+          result.insert(member.to_owned(), value.clone());
+        }
+        _ => {}
+      }
+    }
+
+    if parameter.get("type").and_then(Value::as_str) == Some("array") {
+      let location = parameter.get("in").and_then(Value::as_str);
+      let collection_format = parameter
+        .get("collectionFormat")
+        .and_then(Value::as_str)
+        .unwrap_or("csv");
+      let (style, explode) = match (location, collection_format) {
+        (Some("query"), "multi") => ("form", true),
+        (Some("query"), "ssv") => ("spaceDelimited", false),
+        (Some("query"), "pipes") => ("pipeDelimited", false),
+        (Some("query"), _) => ("form", false),
+        _ => ("simple", false),
+      };
+      result.insert("style".to_owned(), style.into());
+      result.insert("explode".to_owned(), explode.into());
+    }
+
+    result.insert("schema".to_owned(), self.make_parameter_schema(parameter));
+
+    result.into()
+  }
+
+  /// Parameters, items and headers have the keywords of their schema as
+  /// members, these are collected in a schema.
+  ///
+  fn make_parameter_schema(&self, parameter: &Map<String, Value>) -> Value {
+    let mut schema = Map::new();
+    for keyword in PARAMETER_SCHEMA_KEYWORDS {
+      let Some(value) = parameter.get(keyword) else {
+        continue;
+      };
+      let value = match (keyword, value.as_object()) {
+        ("items", Some(items)) => self.make_parameter_schema(items),
+        _ => value.clone(),
+      };
+      schema.insert(keyword.to_owned(), value);
+    }
+    self.convert_schema(&schema.into())
+  }
+
+  fn convert_response(&self, response: &Value, produces: &[String]) -> Value {
+    let Some(response) = response.as_object() else {
+      return response.clone();
+    };
+    if let Some(reference) = response.get("$ref").and_then(Value::as_str) {
+      return make_reference(reference);
+    }
+
+    let mut result = Map::new();
+    for (member, value) in response {
+      match member.as_str() {
+        "description" => {
+          result.insert(member.clone(), value.clone());
+        }
+        member if member.starts_with("x-") => {
+          result.insert(member.to_owned(), value.clone());
+        }
+        _ => {}
+      }
+    }
+    // description is required in OpenApi 3
+    result.entry("description").or_insert_with(|| "".into());
+
+    let headers: Map<_, _> = get_members(response, "headers")
+      .map(|(name, header)| (name.clone(), self.convert_header(header)))
+      .collect();
+    if !headers.is_empty() {
+      result.insert("headers".to_owned(), headers.into());
+    }
+
+    if let Some(schema) = response.get("schema") {
+      let schema = self.convert_schema(schema);
+      let content: Map<_, _> = produces
+        .iter()
+        .map(|media_type| {
+          let mut media_type_object = make_media_type(schema.clone());
+          if let Some(example) = response
+            .get("examples")
+            .and_then(|examples| examples.get(media_type))
+          {
+            media_type_object
+              .as_object_mut()
+              .unwrap()
+              .insert("example".to_owned(), example.clone());
+          }
+          (media_type.clone(), media_type_object)
+        })
+        .collect();
+      result.insert("content".to_owned(), content.into());
+    }
+
+    result.into()
+  }
+
+  fn convert_header(&self, header: &Value) -> Value {
+    let Some(header) = header.as_object() else {
+      return header.clone();
+    };
+
+    let mut result = Map::new();
+    for (member, value) in header {
+      match member.as_str() {
+        "description" => {
+          result.insert(member.clone(), value.clone());
+        }
+        member if member.starts_with("x-") => {
+          result.insert(member.to_owned(), value.clone());
+        }
+        _ => {}
+      }
+    }
+    result.insert("schema".to_owned(), self.make_parameter_schema(header));
+
+    result.into()
+  }
+
+  /// Convert a schema and all of it's sub schemas. `file` types become binary
+  /// strings, `x-nullable` becomes `nullable` and the discriminator gets a
+  /// property name.
+  ///
+  fn convert_schema(&self, schema: &Value) -> Value {
+    let Some(schema) = schema.as_object() else {
+      return schema.clone();
+    };
+
+    let mut result = Map::new();
+    for (member, value) in schema {
+      let value = match member.as_str() {
+        "$ref" => value
+          .as_str()
+          .map(|reference| convert_reference(reference).into())
+          .unwrap_or_else(|| value.clone()),
+        "discriminator" => match value.as_str() {
+          Some(property_name) => {
+            Map::from_iter([("propertyName".to_owned(), property_name.into())]).into()
+          }
+          None => value.clone(),
+        },
+        "x-nullable" => continue,
+        member => map_sub_schemas(member, value, |_pointer, schema| {
+          self.convert_schema(schema)
+        })
+        .unwrap_or_else(|| value.clone()),
+      };
+      result.insert(member.clone(), value);
+    }
+
+    if result.get("type").and_then(Value::as_str) == Some("file") {
+      result.insert("type".to_owned(), "string".into());
+      result.insert("format".to_owned(), "binary".into());
+    }
+
+    if schema.get("x-nullable").and_then(Value::as_bool) == Some(true) {
+      result.insert("nullable".to_owned(), true.into());
+    }
+
+    result.into()
+  }
+}
+
+#[derive(Default)]
+struct SplitParameters {
+  parameters: Vec<Value>,
+  body: Option<RequestBody>,
+  form: Vec<Map<String, Value>>,
+}
+
+#[derive(Clone)]
+enum RequestBody {
+  Reference(String),
+  Parameter(Value),
+}
+
+fn convert_security_scheme(scheme: &Value) -> Value {
+  let Some(scheme) = scheme.as_object() else {
+    return scheme.clone();
+  };
+
+  let mut result = Map::new();
+  for (member, value) in scheme {
+    match member.as_str() {
+      "description" => {
+        result.insert(member.clone(), value.clone());
+      }
+      member if member.starts_with("x-") => {
+        result.insert(member.to_owned(), value.clone());
+      }
+      _ => {}
+    }
+  }
+
+  match scheme.get("type").and_then(Value::as_str) {
+    Some("basic") => {
+      result.insert("type".to_owned(), "http".into());
+      result.insert("scheme".to_owned(), "basic".into());
+    }
+    Some("apiKey") => {
+      result.insert("type".to_owned(), "apiKey".into());
+      for member in ["name", "in"] {
+        if let Some(value) = scheme.get(member) {
+          result.insert(member.to_owned(), value.clone());
+        }
+      }
+    }
+    Some("oauth2") => {
+      let flow_name = match scheme.get("flow").and_then(Value::as_str) {
+        Some("implicit") => "implicit",
+        Some("password") => "password",
+        Some("application") => "clientCredentials",
+        _ => "authorizationCode",
+      };
+      let mut flow = Map::new();
+      for member in ["authorizationUrl", "tokenUrl", "scopes"] {
+        if let Some(value) = scheme.get(member) {
+          flow.insert(member.to_owned(), value.clone());
+        }
+      }
+      flow.entry("scopes").or_insert_with(|| Map::new().into());
+
+      result.insert("type".to_owned(), "oauth2".into());
+      result.insert(
+        "flows".to_owned(),
+        Map::from_iter([(flow_name.to_owned(), Value::from(flow))]).into(),
+      );
+    }
+    _ => {}
+  }
+
+  result.into()
+}
+
+/// Rewrite a reference to the definitions, parameters or responses of a
+/// document to the components.
+///
+fn convert_reference(reference: &str) -> String {
+  [
+    ("/definitions/", "/components/schemas/"),
+    ("/parameters/", "/components/parameters/"),
+    ("/responses/", "/components/responses/"),
+  ]
+  .into_iter()
+  .find_map(|(from, to)| {
+    let converted = convert_reference_with(reference, from, to);
+    (converted != reference).then_some(converted)
+  })
+  .unwrap_or_else(|| reference.to_owned())
+}
+
+/// Only references within the document are rewritten, other documents are
+/// not converted.
+///
+fn convert_reference_with(reference: &str, from: &str, to: &str) -> String {
+  let Some(pointer) = reference.strip_prefix('#') else {
+    return reference.to_owned();
+  };
+  match pointer.strip_prefix(from) {
+    Some(name) => format!("#{}{}", to, name),
+    None => reference.to_owned(),
+  }
+}
+
+fn is_internal_reference(reference: &str) -> bool {
+  reference.starts_with('#')
+}
+
+fn make_reference(reference: &str) -> Value {
+  Map::from_iter([("$ref".to_owned(), convert_reference(reference).into())]).into()
+}
+
+fn make_media_type(schema: Value) -> Value {
+  Map::from_iter([("schema".to_owned(), schema)]).into()
+}
+
+fn get_parameter_in(parameter: &Value) -> Option<&str> {
+  parameter.get("in")?.as_str()
+}
+
+fn get_members<'a>(
+  node: &'a Map<String, Value>,
+  member: &str,
+) -> impl Iterator<Item = (&'a String, &'a Value)> {
+  node
+    .get(member)
+    .and_then(Value::as_object)
+    .into_iter()
+    .flatten()
+}
+
+fn get_items<'a>(node: &'a Map<String, Value>, member: &str) -> impl Iterator<Item = &'a Value> {
+  node
+    .get(member)
+    .and_then(Value::as_array)
+    .into_iter()
+    .flatten()
+}
+
+fn unescape_pointer(part: &str) -> String {
+  part.replace("~1", "/").replace("~0", "~")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn test_convert_to_openapi() {
+    let node = json!({
+      "swagger": "2.0",
+      "info": { "title": "pets", "version": "1.0" },
+      "host": "example.com",
+      "basePath": "/v1",
+      "schemes": ["https", "http"],
+      "consumes": ["application/json"],
+      "produces": ["application/json"],
+      "x-owner": "pets-team",
+      "paths": {
+        "/pets/{pet-id}": {
+          "parameters": [{ "$ref": "#/parameters/pet-id" }],
+          "get": {
+            "operationId": "get-pet",
+            "parameters": [
+              { "name": "fields", "in": "query", "type": "array", "items": { "type": "string" } },
+            ],
+            "responses": {
+              "200": {
+                "description": "the pet",
+                "schema": { "$ref": "#/definitions/pet" },
+                "headers": { "x-rate-limit": { "type": "integer" } },
+              },
+              "default": { "$ref": "#/responses/error" },
+            },
+          },
+          "put": {
+            "operationId": "update-pet",
+            "parameters": [{ "$ref": "#/parameters/pet" }],
+            "responses": { "204": { "description": "updated" } },
+          },
+          "post": {
+            "operationId": "upload-photo",
+            "consumes": ["multipart/form-data"],
+            "parameters": [
+              { "name": "photo", "in": "formData", "type": "file", "required": true },
+              { "name": "title", "in": "formData", "type": "string" },
+            ],
+            "responses": { "204": { "description": "uploaded" } },
+          },
+        },
+      },
+      "definitions": {
+        "pet": {
+          "type": "object",
+          "discriminator": "kind",
+          "properties": {
+            "kind": { "type": "string" },
+            "name": { "type": "string", "x-nullable": true },
+            "parent": { "$ref": "#/definitions/pet" },
+            "age": { "type": "integer", "minimum": 0, "exclusiveMinimum": true, "example": 3 },
+            "tag": { "x-nullable": true },
+          },
+        },
+      },
+      "parameters": {
+        "pet-id": { "name": "pet-id", "in": "path", "required": true, "type": "string" },
+        "pet": { "name": "pet", "in": "body", "schema": { "$ref": "#/definitions/pet" } },
+      },
+      "responses": {
+        "error": { "description": "error" },
+      },
+      "securityDefinitions": {
+        "basic": { "type": "basic" },
+        "oauth": {
+          "type": "oauth2",
+          "flow": "application",
+          "tokenUrl": "https://example.com/token",
+          "scopes": { "read": "read pets" },
+        },
+      },
+    });
+
+    let location: NodeLocation = "http://example.com/swagger.yaml".parse().unwrap();
+    let converted = convert_to_openapi(&node, &location, DocumentType::OpenApiV30).unwrap();
+    assert!(converted.issues.is_empty());
+    let converted = converted.node;
+    assert_eq!(converted["openapi"], "3.0.3");
+    assert_eq!(converted["x-owner"], "pets-team");
+    assert_eq!(
+      converted["servers"],
+      json!([{ "url": "https://example.com/v1" }, { "url": "http://example.com/v1" }])
+    );
+
+    let path_item = &converted["paths"]["/pets/{pet-id}"];
+    assert_eq!(
+      path_item["parameters"],
+      json!([{ "$ref": "#/components/parameters/pet-id" }])
+    );
+    assert_eq!(
+      path_item["get"]["parameters"],
+      json!([{
+        "name": "fields",
+        "in": "query",
+        "style": "form",
+        "explode": false,
+        "schema": { "type": "array", "items": { "type": "string" } },
+      }])
+    );
+    assert_eq!(
+      path_item["get"]["responses"]["200"],
+      json!({
+        "description": "the pet",
+        "headers": { "x-rate-limit": { "schema": { "type": "integer" } } },
+        "content": {
+          "application/json": { "schema": { "$ref": "#/components/schemas/pet" } },
+        },
+      })
+    );
+    assert_eq!(
+      path_item["get"]["responses"]["default"],
+      json!({ "$ref": "#/components/responses/error" })
+    );
+    assert_eq!(
+      path_item["put"]["requestBody"],
+      json!({ "$ref": "#/components/requestBodies/pet" })
+    );
+    assert_eq!(
+      path_item["post"]["requestBody"],
+      json!({
+        "content": {
+          "multipart/form-data": {
+            "schema": {
+              "type": "object",
+              "properties": {
+                "photo": { "type": "string", "format": "binary" },
+                "title": { "type": "string" },
+              },
+              "required": ["photo"],
+            },
+          },
+        },
+      })
+    );
+
+    let components = &converted["components"];
+    assert_eq!(
+      components["schemas"]["pet"],
+      json!({
+        "type": "object",
+        "discriminator": { "propertyName": "kind" },
+        "properties": {
+          "kind": { "type": "string" },
+          "name": { "type": "string", "nullable": true },
+          "parent": { "$ref": "#/components/schemas/pet" },
+          "age": { "type": "integer", "minimum": 0, "exclusiveMinimum": true, "example": 3 },
+          "tag": { "nullable": true },
+        },
+      })
+    );
+    assert_eq!(
+      components["requestBodies"]["pet"],
+      json!({
+        "content": {
+          "application/json": { "schema": { "$ref": "#/components/schemas/pet" } },
+        },
+      })
+    );
+    assert_eq!(
+      components["securitySchemes"],
+      json!({
+        "basic": { "type": "http", "scheme": "basic" },
+        "oauth": {
+          "type": "oauth2",
+          "flows": {
+            "clientCredentials": {
+              "tokenUrl": "https://example.com/token",
+              "scopes": { "read": "read pets" },
+            },
+          },
+        },
+      })
+    );
+
+    let converted = convert_to_openapi(&node, &location, DocumentType::OpenApiV31).unwrap();
+    let issues: Vec<_> = converted
+      .issues
+      .iter()
+      .map(|issue| issue.location.to_string())
+      .collect();
+    assert_eq!(
+      issues,
+      vec!["http://example.com/swagger.yaml#/components/schemas/pet/properties/tag"]
+    );
+    let converted = converted.node;
+    assert_eq!(converted["openapi"], "3.1.0");
+    let properties = &converted["components"]["schemas"]["pet"]["properties"];
+    assert_eq!(properties["name"], json!({ "type": ["string", "null"] }));
+    assert_eq!(
+      properties["age"],
+      json!({ "type": "integer", "exclusiveMinimum": 0, "examples": [3] })
+    );
+    assert_eq!(properties["tag"], json!({}));
+    assert_eq!(
+      converted["paths"]["/pets/{pet-id}"]["post"]["requestBody"]["content"]["multipart/form-data"]
+        ["schema"]["properties"]["photo"],
+      json!({ "type": "string", "contentMediaType": "application/octet-stream" })
+    );
+
+    // references to other documents are not rewritten, but reported
+    let node = json!({
+      "swagger": "2.0",
+      "info": { "title": "pets", "version": "1.0" },
+      "paths": {
+        "/pets": {
+          "post": {
+            "parameters": [{ "$ref": "parameters.yaml#/parameters/pet" }],
+            "responses": {
+              "200": {
+                "description": "the pet",
+                "schema": { "$ref": "definitions.yaml#/definitions/pet" },
+                "examples": { "application/json": { "$ref": "not-a-reference.yaml" } },
+              },
+            },
+          },
+        },
+      },
+    });
+    let converted = convert_to_openapi(&node, &location, DocumentType::OpenApiV30).unwrap();
+    let issues: Vec<_> = converted
+      .issues
+      .iter()
+      .map(|issue| issue.location.to_string())
+      .collect();
+    assert_eq!(
+      issues,
+      vec![
+        "http://example.com/swagger.yaml#/paths/~1pets/post/parameters/0",
+        "http://example.com/swagger.yaml#/paths/~1pets/post/responses/200/schema",
+      ]
+    );
+    assert!(converted.issues[0]
+      .message
+      .contains("body or form parameter"));
+    let operation = &converted.node["paths"]["/pets"]["post"];
+    assert_eq!(
+      operation["parameters"],
+      json!([{ "$ref": "parameters.yaml#/parameters/pet" }])
+    );
+    assert_eq!(
+      operation["responses"]["200"]["content"]["application/json"]["schema"],
+      json!({ "$ref": "definitions.yaml#/definitions/pet" })
+    );
+
+    assert_eq!(
+      convert_to_openapi(
+        &json!({ "openapi": "3.0.3" }),
+        &location,
+        DocumentType::OpenApiV31
+      ),
+      Err(Error::DocumentTypeError)
+    );
+    assert_eq!(
+      convert_to_openapi(&node, &location, DocumentType::Swagger2),
+      Err(Error::UnsupportedVersion)
+    );
+  }
+}
@@ -1,44 +1,205 @@
-use semver::Version;
+use std::fmt::Display;
 use wasm_bindgen::prelude::*;
 
+/// The numbers of the variants are exported to javascript, so new variants
+/// are added at the end.
+///
 #[wasm_bindgen]
-#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum DocumentType {
   OpenApiV30,
   OpenApiV31,
   Swagger2,
+  AsyncApiV2,
+  AsyncApiV3,
+  JsonSchema,
+  OpenApiV32,
 }
 
-pub struct DocumentTypeError;
+/// Keywords that, when at the root of a document, tell us that the document is
+/// a json schema. `$schema` is the most reliable of these.
+///
+const JSON_SCHEMA_KEYWORDS: [&str; 12] = [
+  "$schema",
+  "$id",
+  "$ref",
+  "$defs",
+  "definitions",
+  "type",
+  "properties",
+  "items",
+  "allOf",
+  "anyOf",
+  "oneOf",
+  "enum",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DocumentTypeError {
+  /// The type of the document could not be detected
+  Unknown,
+  /// The version field (like `openapi`) does not contain a version
+  InvalidVersion { field: String, version: String },
+  /// The version field contains a version that we do not support
+  UnsupportedVersion { field: String, version: String },
+}
+
+impl std::error::Error for DocumentTypeError {}
+
+impl Display for DocumentTypeError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Unknown => write!(f, "unknown document type"),
+      Self::InvalidVersion { field, version } => {
+        write!(f, "invalid {} version {}", field, version)
+      }
+      Self::UnsupportedVersion { field, version } => {
+        write!(f, "unsupported {} version {}", field, version)
+      }
+    }
+  }
+}
 
 impl TryFrom<&serde_json::Value> for DocumentType {
   type Error = DocumentTypeError;
 
   fn try_from(value: &serde_json::Value) -> Result<Self, Self::Error> {
-    let document = value.as_object().ok_or(DocumentTypeError)?;
+    let document = match value {
+      serde_json::Value::Object(document) => document,
+      // a boolean is a valid json schema
+      serde_json::Value::Bool(_) => return Ok(Self::JsonSchema),
+      _ => return Err(DocumentTypeError::Unknown),
+    };
 
     if let Some(version) = document.get("swagger") {
-      let version = version.as_str().ok_or(DocumentTypeError)?;
-      let version = Version::parse(version).map_err(|_error| DocumentTypeError)?;
-
-      if version.major == 2 && version.minor == 0 {
-        return Ok(Self::Swagger2);
-      }
+      return match get_version("swagger", version)? {
+        (2, 0) => Ok(Self::Swagger2),
+        _ => Err(get_unsupported_version_error("swagger", version)),
+      };
     }
 
     if let Some(version) = document.get("openapi") {
-      let version = version.as_str().ok_or(DocumentTypeError)?;
-      let version = Version::parse(version).map_err(|_error| DocumentTypeError)?;
+      return match get_version("openapi", version)? {
+        (3, 0) => Ok(Self::OpenApiV30),
+        (3, 1) => Ok(Self::OpenApiV31),
+        (3, 2) => Ok(Self::OpenApiV32),
+        _ => Err(get_unsupported_version_error("openapi", version)),
+      };
+    }
 
-      if version.major == 3 && version.minor == 0 {
-        return Ok(Self::OpenApiV30);
-      }
+    if let Some(version) = document.get("asyncapi") {
+      return match get_version("asyncapi", version)? {
+        (2, _) => Ok(Self::AsyncApiV2),
+        (3, _) => Ok(Self::AsyncApiV3),
+        _ => Err(get_unsupported_version_error("asyncapi", version)),
+      };
+    }
 
-      if version.major == 3 && version.minor == 1 {
-        return Ok(Self::OpenApiV31);
-      }
+    if JSON_SCHEMA_KEYWORDS
+      .iter()
+      .any(|keyword| document.contains_key(*keyword))
+    {
+      return Ok(Self::JsonSchema);
+    }
+
+    Err(DocumentTypeError::Unknown)
+  }
+}
+
+/// Get the major and minor version from a version field. We don't require a
+/// semantic version, so `3.0`, `3` and `3.1.0-rc0` are accepted. Versions that
+/// are numbers (`openapi: 3.0` in yaml) are accepted too.
+///
+fn get_version(field: &str, version: &serde_json::Value) -> Result<(u64, u64), DocumentTypeError> {
+  let version_string = get_version_string(version);
+  let invalid_version_error = || DocumentTypeError::InvalidVersion {
+    field: field.to_owned(),
+    version: version_string.clone(),
+  };
+
+  let mut parts = version_string.trim().splitn(3, '.');
+  let major = parts
+    .next()
+    .and_then(|part| part.parse().ok())
+    .ok_or_else(invalid_version_error)?;
+  let minor = match parts.next() {
+    None => 0,
+    Some(part) => {
+      let digits: String = part.chars().take_while(char::is_ascii_digit).collect();
+      digits.parse().map_err(|_error| invalid_version_error())?
     }
+  };
+
+  Ok((major, minor))
+}
+
+fn get_unsupported_version_error(field: &str, version: &serde_json::Value) -> DocumentTypeError {
+  DocumentTypeError::UnsupportedVersion {
+    field: field.to_owned(),
+    version: get_version_string(version),
+  }
+}
+
+fn get_version_string(version: &serde_json::Value) -> String {
+  version
+    .as_str()
+    .map(ToOwned::to_owned)
+    .unwrap_or_else(|| version.to_string())
+}
 
-    Err(DocumentTypeError)
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn test_document_type() {
+    do_test(json!({ "swagger": "2.0" }), Ok(DocumentType::Swagger2));
+    do_test(json!({ "openapi": "3.0.3" }), Ok(DocumentType::OpenApiV30));
+    do_test(json!({ "openapi": "3.0" }), Ok(DocumentType::OpenApiV30));
+    do_test(json!({ "openapi": 3.0 }), Ok(DocumentType::OpenApiV30));
+    do_test(json!({ "openapi": "3.1.0" }), Ok(DocumentType::OpenApiV31));
+    do_test(
+      json!({ "openapi": "3.1.1-rc0" }),
+      Ok(DocumentType::OpenApiV31),
+    );
+    do_test(json!({ "openapi": "3.2.0" }), Ok(DocumentType::OpenApiV32));
+    do_test(json!({ "asyncapi": "2.6.0" }), Ok(DocumentType::AsyncApiV2));
+    do_test(json!({ "asyncapi": "3.0.0" }), Ok(DocumentType::AsyncApiV3));
+    do_test(
+      json!({ "$schema": "https://json-schema.org/draft/2020-12/schema" }),
+      Ok(DocumentType::JsonSchema),
+    );
+    do_test(json!({ "type": "object" }), Ok(DocumentType::JsonSchema));
+    do_test(json!(true), Ok(DocumentType::JsonSchema));
+
+    do_test(
+      json!({ "openapi": "4.0.0" }),
+      Err(DocumentTypeError::UnsupportedVersion {
+        field: "openapi".to_owned(),
+        version: "4.0.0".to_owned(),
+      }),
+    );
+    do_test(
+      json!({ "swagger": "1.2" }),
+      Err(DocumentTypeError::UnsupportedVersion {
+        field: "swagger".to_owned(),
+        version: "1.2".to_owned(),
+      }),
+    );
+    do_test(
+      json!({ "openapi": "three" }),
+      Err(DocumentTypeError::InvalidVersion {
+        field: "openapi".to_owned(),
+        version: "three".to_owned(),
+      }),
+    );
+    do_test(json!({ "info": {} }), Err(DocumentTypeError::Unknown));
+    do_test(json!([]), Err(DocumentTypeError::Unknown));
+
+    fn do_test(value: serde_json::Value, expected: Result<DocumentType, DocumentTypeError>) {
+      let actual = DocumentType::try_from(&value);
+      assert_eq!(actual, expected);
+    }
   }
 }
@@ -0,0 +1,707 @@
+use crate::documents::oas30::{upgrade_schema_to_oas31, UpgradeIssue, UpgradedDocument};
+use crate::documents::DocumentType;
+use crate::error::Error;
+use crate::models::{
+  Api, AuthenticationRequirementGroup, Body, Method, Operation, OperationResult, Parameter,
+  SchemaSource,
+};
+use crate::utils::{sync, NodeLocation};
+use serde_json::{Map, Value};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+/// Write an OpenApi (3.0, 3.1 or 3.2, depending on the target) document that
+/// describes the api model.
+///
+/// The model does not contain schemas, only schema ids. The schemas in the
+/// components of the document of the api are copied and are referenced via
+/// `$ref`. Other schemas in the document of the api are inlined, schemas in
+/// other documents are referenced via their id. The `info` of the document of
+/// the api is copied too.
+///
+/// Schemas that are copied from an OpenApi 3.0 document are upgraded when the
+/// target is 3.1 or 3.2, like `upgrade_to_oas31` does, the issues of the
+/// upgrade are returned with the document. Schemas can not be downgraded, so
+/// writing a 3.0 document from a 3.1 or 3.2 document is an
+/// `Error::UnsupportedVersion`.
+///
+/// Specification extensions of the elements of the model are written too.
+///
+/// Things the target version can not describe are left out, like the `query`
+/// method or `itemSchema` in versions before 3.2.
+///
+pub fn write_openapi_document(
+  api: &Api,
+  target: DocumentType,
+  source: &dyn SchemaSource,
+) -> Result<UpgradedDocument, Error> {
+  let version = match target {
+    DocumentType::OpenApiV30 => "3.0.3",
+    DocumentType::OpenApiV31 => "3.1.0",
+    DocumentType::OpenApiV32 => "3.2.0",
+    _ => return Err(Error::UnsupportedVersion),
+  };
+
+  // a model that is not read from a document has no schemas to convert
+  let source_type = source
+    .get_node(&api.location.set_root())
+    .and_then(|node| DocumentType::try_from(&node).ok())
+    .unwrap_or(target);
+  let upgrade_schemas = match (source_type, target) {
+    (DocumentType::OpenApiV30, DocumentType::OpenApiV31 | DocumentType::OpenApiV32) => true,
+    (DocumentType::OpenApiV31 | DocumentType::OpenApiV32, DocumentType::OpenApiV30) => {
+      return Err(Error::UnsupportedVersion)
+    }
+    _ => false,
+  };
+
+  let writer = Writer {
+    api,
+    target,
+    source,
+    upgrade_schemas,
+    issues: Default::default(),
+  };
+  let node = writer.write_document(version);
+
+  Ok(UpgradedDocument {
+    node,
+    issues: writer.issues.into_inner(),
+  })
+}
+
+struct Writer<'a> {
+  api: &'a Api,
+  target: DocumentType,
+  source: &'a dyn SchemaSource,
+  /**
+   * schemas are copied from an OpenApi 3.0 document into a 3.1 or 3.2 document
+   */
+  upgrade_schemas: bool,
+  issues: RefCell<Vec<UpgradeIssue>>,
+}
+
+impl Writer<'_> {
+  fn get_document_node(&self, pointer: &[&str]) -> Option<Value> {
+    let location = self
+      .api
+      .location
+      .set_pointer(pointer.iter().map(|part| (*part).to_owned()).collect());
+    self.source.get_node(&location)
+  }
+
+  fn write_document(&self, version: &str) -> Value {
+    let mut result = Map::new();
+    result.insert("openapi".to_owned(), version.into());
+
+    if self.target == DocumentType::OpenApiV32 {
+      if let Some(self_location) = &self.api.self_location {
+        result.insert(
+          "$self".to_owned(),
+          self_location
+            .set_root()
+            .to_string()
+            .trim_end_matches('#')
+            .into(),
+        );
+      }
+    }
+
+    let info = self.get_document_node(&["info"]).unwrap_or_else(|| {
+      Map::from_iter([
+        ("title".to_owned(), Value::from("")),
+        ("version".to_owned(), Value::from("")),
+      ])
+      .into()
+    });
+    result.insert("info".to_owned(), info);
+
+    let tags: Vec<_> = self
+      .api
+      .tags
+      .iter()
+      .map(|tag| {
+        let mut result = Map::new();
+        result.insert("name".to_owned(), tag.name.clone().into());
+        insert_optional(&mut result, "description", &tag.description);
+        if self.target == DocumentType::OpenApiV32 {
+          insert_optional(&mut result, "summary", &tag.summary);
+          insert_optional(&mut result, "parent", &tag.parent);
+          insert_optional(&mut result, "kind", &tag.kind);
+        }
+        Value::from(result)
+      })
+      .collect();
+    if !tags.is_empty() {
+      result.insert("tags".to_owned(), tags.into());
+    }
+
+    let paths: Map<_, _> = self
+      .api
+      .paths
+      .iter()
+      .map(|path| {
+        let mut path_item = Map::new();
+        let mut additional_operations = Map::new();
+        for operation in &path.operations {
+          let method = match (&operation.method, self.target) {
+            (Method::Query, DocumentType::OpenApiV32) => operation.method.to_string(),
+            (Method::Query, _) => continue,
+            (Method::Other(name), DocumentType::OpenApiV32) => {
+              additional_operations.insert(name.clone(), self.write_operation(operation));
+              continue;
+            }
+            (Method::Other(_), _) => continue,
+            (method, _) => method.to_string(),
+          };
+          path_item.insert(method, self.write_operation(operation));
+        }
+        if !additional_operations.is_empty() {
+          path_item.insert(
+            "additionalOperations".to_owned(),
+            additional_operations.into(),
+          );
+        }
+        insert_extensions(&mut path_item, &path.extensions);
+        (path.pattern.clone(), Value::from(path_item))
+      })
+      .collect();
+    result.insert("paths".to_owned(), paths.into());
+
+    let mut components = Map::new();
+    if let Some(schemas) = self.get_document_node(&["components", "schemas"]) {
+      let location = self
+        .api
+        .location
+        .set_pointer(vec!["components".to_owned(), "schemas".to_owned()]);
+      let schemas = match self.rewrite_references(&location, schemas) {
+        Value::Object(schemas) => schemas
+          .into_iter()
+          .map(|(name, schema)| {
+            let schema = self.upgrade_schema(&location.push_pointer(vec![name.clone()]), schema);
+            (name, schema)
+          })
+          .collect::<Map<_, _>>()
+          .into(),
+        schemas => schemas,
+      };
+      components.insert("schemas".to_owned(), schemas);
+    }
+    let security_schemes: Map<_, _> = self
+      .api
+      .authentication
+      .iter()
+      .map(|authentication| {
+        let mut result = Map::new();
+        result.insert("type".to_owned(), authentication.r#type.clone().into());
+        insert_optional(&mut result, "description", &authentication.description);
+        insert_optional(&mut result, "name", &authentication.parameter_name);
+        insert_optional(&mut result, "in", &authentication.r#in);
+        insert_optional(&mut result, "scheme", &authentication.scheme);
+
+        let flows: Map<_, _> = authentication
+          .flows
+          .iter()
+          .filter(|flow| {
+            flow.kind != "deviceAuthorization" || self.target == DocumentType::OpenApiV32
+          })
+          .map(|flow| {
+            let mut result = Map::new();
+            insert_optional(&mut result, "authorizationUrl", &flow.authorization_url);
+            if self.target == DocumentType::OpenApiV32 {
+              insert_optional(
+                &mut result,
+                "deviceAuthorizationUrl",
+                &flow.device_authorization_url,
+              );
+            }
+            insert_optional(&mut result, "tokenUrl", &flow.token_url);
+            insert_optional(&mut result, "refreshUrl", &flow.refresh_url);
+            // the descriptions of scopes are not in the model
+            let scopes: Map<_, _> = flow
+              .scopes
+              .iter()
+              .map(|scope| (scope.clone(), Value::from("")))
+              .collect();
+            result.insert("scopes".to_owned(), scopes.into());
+            (flow.kind.clone(), Value::from(result))
+          })
+          .collect();
+        if !flows.is_empty() {
+          result.insert("flows".to_owned(), flows.into());
+        }
+
+        insert_extensions(&mut result, &authentication.extensions);
+        (authentication.name.clone(), Value::from(result))
+      })
+      .collect();
+    if !security_schemes.is_empty() {
+      components.insert("securitySchemes".to_owned(), security_schemes.into());
+    }
+    if !components.is_empty() {
+      result.insert("components".to_owned(), components.into());
+    }
+
+    insert_extensions(&mut result, &self.api.extensions);
+    result.into()
+  }
+
+  fn write_operation(&self, operation: &Operation) -> Value {
+    let mut result = Map::new();
+    result.insert("operationId".to_owned(), operation.name.clone().into());
+    insert_optional(&mut result, "summary", &operation.summary);
+    insert_optional(&mut result, "description", &operation.description);
+    if operation.deprecated {
+      result.insert("deprecated".to_owned(), true.into());
+    }
+    if !operation.tags.is_empty() {
+      result.insert("tags".to_owned(), operation.tags.clone().into());
+    }
+
+    let mut parameters = Vec::new();
+    let mut locations = vec![
+      ("path", &operation.path_parameters),
+      ("query", &operation.query_parameters),
+      ("header", &operation.header_parameters),
+      ("cookie", &operation.cookie_parameters),
+    ];
+    if self.target == DocumentType::OpenApiV32 {
+      locations.push(("querystring", &operation.querystring_parameters));
+    }
+    for (location, location_parameters) in locations {
+      for parameter in location_parameters {
+        let mut result = Map::new();
+        result.insert("name".to_owned(), parameter.name.clone().into());
+        result.insert("in".to_owned(), location.into());
+        if parameter.required || location == "path" {
+          result.insert("required".to_owned(), true.into());
+        }
+        if let Some(schema) = self.write_schema(&parameter.schema_id) {
+          result.insert("schema".to_owned(), schema);
+        }
+        insert_extensions(&mut result, &parameter.extensions);
+        parameters.push(Value::from(result));
+      }
+    }
+    if !parameters.is_empty() {
+      result.insert("parameters".to_owned(), parameters.into());
+    }
+
+    if !operation.bodies.is_empty() {
+      let request_body =
+        Map::from_iter([("content".to_owned(), self.write_content(&operation.bodies))]);
+      result.insert("requestBody".to_owned(), request_body.into());
+    }
+
+    let responses: Map<_, _> = operation
+      .operation_results
+      .iter()
+      .map(|operation_result| {
+        (
+          operation_result.status_kind.to_string(),
+          self.write_operation_result(operation_result),
+        )
+      })
+      .collect();
+    result.insert("responses".to_owned(), responses.into());
+
+    if !operation.authentication_requirements.is_empty() {
+      result.insert(
+        "security".to_owned(),
+        write_security(&operation.authentication_requirements),
+      );
+    }
+
+    insert_extensions(&mut result, &operation.extensions);
+    result.into()
+  }
+
+  fn write_operation_result(&self, operation_result: &OperationResult) -> Value {
+    let mut result = Map::new();
+    result.insert(
+      "description".to_owned(),
+      operation_result
+        .description
+        .clone()
+        .unwrap_or_default()
+        .into(),
+    );
+
+    let headers: Map<_, _> = operation_result
+      .header_parameters
+      .iter()
+      .map(|parameter| (parameter.name.clone(), self.write_header(parameter)))
+      .collect();
+    if !headers.is_empty() {
+      result.insert("headers".to_owned(), headers.into());
+    }
+
+    if !operation_result.bodies.is_empty() {
+      result.insert(
+        "content".to_owned(),
+        self.write_content(&operation_result.bodies),
+      );
+    }
+
+    insert_extensions(&mut result, &operation_result.extensions);
+    result.into()
+  }
+
+  fn write_header(&self, parameter: &Parameter) -> Value {
+    let mut result = Map::new();
+    if parameter.required {
+      result.insert("required".to_owned(), true.into());
+    }
+    if let Some(schema) = self.write_schema(&parameter.schema_id) {
+      result.insert("schema".to_owned(), schema);
+    }
+    insert_extensions(&mut result, &parameter.extensions);
+    result.into()
+  }
+
+  fn write_content(&self, bodies: &[sync::Shared<Body>]) -> Value {
+    bodies
+      .iter()
+      .map(|body| {
+        let mut result = Map::new();
+        if let Some(schema) = self.write_schema(&body.schema_id) {
+          result.insert("schema".to_owned(), schema);
+        }
+        if self.target == DocumentType::OpenApiV32 {
+          if let Some(schema) = self.write_schema(&body.item_schema_id) {
+            result.insert("itemSchema".to_owned(), schema);
+          }
+        }
+        insert_extensions(&mut result, &body.extensions);
+        (body.content_type.clone(), Value::from(result))
+      })
+      .collect::<Map<_, _>>()
+      .into()
+  }
+
+  /// A schema in the components of the document of the api is referenced, a
+  /// schema elsewhere in the document is inlined and a schema in another
+  /// document is referenced via it's id.
+  ///
+  fn write_schema(&self, schema_id: &Option<NodeLocation>) -> Option<Value> {
+    let schema_id = schema_id.as_ref()?;
+    let retrieval_location = self
+      .source
+      .get_retrieval_location(schema_id)
+      .unwrap_or_else(|| schema_id.clone());
+
+    if retrieval_location.set_root() == self.api.location.set_root() {
+      if is_component_schema(&retrieval_location) {
+        return Some(make_reference(
+          self.make_reference_string(&retrieval_location),
+        ));
+      }
+      if let Some(node) = self.source.get_node(&retrieval_location) {
+        let node = self.rewrite_references(&retrieval_location, node);
+        return Some(self.upgrade_schema(&retrieval_location, node));
+      }
+    }
+
+    Some(make_reference(schema_id.to_string()))
+  }
+
+  /// Upgrade a schema that is copied from the document of the api, if the
+  /// versions of the document and the target differ.
+  ///
+  fn upgrade_schema(&self, location: &NodeLocation, schema: Value) -> Value {
+    if !self.upgrade_schemas {
+      return schema;
+    }
+    let (schema, issues) = upgrade_schema_to_oas31(&schema, location);
+    self.issues.borrow_mut().extend(issues);
+    schema
+  }
+
+  /// References in nodes that are copied from the document of the api are
+  /// rewritten so they still resolve in the written document.
+  ///
+  fn rewrite_references(&self, location: &NodeLocation, node: Value) -> Value {
+    match node {
+      Value::Array(items) => items
+        .into_iter()
+        .enumerate()
+        .map(|(index, item)| {
+          self.rewrite_references(&location.push_pointer(vec![index.to_string()]), item)
+        })
+        .collect::<Vec<_>>()
+        .into(),
+      Value::Object(members) => members
+        .into_iter()
+        .map(|(member, value)| {
+          let value = match (member.as_str(), value) {
+            ("$ref", Value::String(reference)) => match reference.parse::<NodeLocation>() {
+              Ok(reference_location) => {
+                let target_location = self.source.resolve_reference(location, &reference_location);
+                self.make_reference_string(&target_location).into()
+              }
+              Err(_) => reference.into(),
+            },
+            (_, value) => {
+              self.rewrite_references(&location.push_pointer(vec![member.clone()]), value)
+            }
+          };
+          (member, value)
+        })
+        .collect::<Map<_, _>>()
+        .into(),
+      node => node,
+    }
+  }
+
+  /// A pointer for locations in the components of the document of the api,
+  /// every other location is absolute because the rest of the document is
+  /// not copied.
+  ///
+  fn make_reference_string(&self, location: &NodeLocation) -> String {
+    if location.set_root() == self.api.location.set_root() && is_component_schema(location) {
+      let pointer = location.get_pointer().unwrap_or_default();
+      return "#"
+        .parse::<NodeLocation>()
+        .unwrap()
+        .set_pointer(pointer)
+        .to_string();
+    }
+    location.to_string()
+  }
+}
+
+fn is_component_schema(location: &NodeLocation) -> bool {
+  let pointer = location.get_pointer().unwrap_or_default();
+  pointer.len() >= 3 && pointer[0] == "components" && pointer[1] == "schemas"
+}
+
+fn write_security(groups: &[sync::Shared<AuthenticationRequirementGroup>]) -> Value {
+  groups
+    .iter()
+    .map(|group| {
+      group
+        .requirements
+        .iter()
+        .map(|requirement| {
+          (
+            requirement.authentication_name.clone(),
+            Value::from(requirement.scopes.clone()),
+          )
+        })
+        .collect::<Map<_, _>>()
+        .into()
+    })
+    .collect::<Vec<Value>>()
+    .into()
+}
+
+fn make_reference(reference: String) -> Value {
+  Map::from_iter([("$ref".to_owned(), Value::from(reference))]).into()
+}
+
+fn insert_extensions(map: &mut Map<String, Value>, extensions: &BTreeMap<String, Value>) {
+  for (name, value) in extensions {
+    map.insert(name.clone(), value.clone());
+  }
+}
+
+fn insert_optional(map: &mut Map<String, Value>, member: &str, value: &Option<String>) {
+  if let Some(value) = value {
+    map.insert(member.to_owned(), value.clone().into());
+  }
+}
+
+#[cfg(not(target_os = "unknown"))]
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::documents::DocumentContext;
+  use crate::utils::MemoryFetcher;
+  use serde_json::json;
+
+  #[tokio::test]
+  async fn test_write_openapi_document() {
+    let location: NodeLocation = "http://example.com/api.yaml".parse().unwrap();
+    let context = sync::Shared::new(DocumentContext::new());
+    context.register_well_known_factories();
+    context.set_fetcher(MemoryFetcher::from_iter([
+      (
+        "http://example.com/api.yaml"
+          .parse::<NodeLocation>()
+          .unwrap(),
+        r##"
+openapi: 3.0.3
+info:
+  title: pets
+  version: "1"
+paths:
+  /pets/{id}:
+    get:
+      operationId: get-pet
+      x-rate-limit: 10
+      deprecated: true
+      tags: [pets]
+      security:
+        - key: []
+      parameters:
+        - name: id
+          in: path
+          required: true
+          schema:
+            type: integer
+            example: 1
+      responses:
+        "200":
+          description: a pet
+          content:
+            application/json:
+              schema:
+                $ref: "#/components/schemas/pet"
+        default:
+          description: an error
+          content:
+            application/json:
+              schema:
+                $ref: schemas.yaml#/error
+components:
+  schemas:
+    pet:
+      type: object
+      nullable: true
+      properties:
+        tag:
+          nullable: true
+  securitySchemes:
+    key:
+      type: apiKey
+      name: api-key
+      in: header
+"##,
+      ),
+      (
+        "http://example.com/schemas.yaml"
+          .parse::<NodeLocation>()
+          .unwrap(),
+        r##"
+error:
+  type: string
+"##,
+      ),
+    ]));
+    context.load_from_location(&location).await.unwrap();
+
+    let written_document = context
+      .write_openapi_document(&location, DocumentType::OpenApiV31)
+      .unwrap();
+    // the 3.0 schemas are upgraded
+    assert_eq!(
+      written_document
+        .issues
+        .iter()
+        .map(|issue| issue.location.to_string())
+        .collect::<Vec<_>>(),
+      vec!["http://example.com/api.yaml#/components/schemas/pet/properties/tag"]
+    );
+    let node = written_document.node;
+    assert_eq!(node["openapi"], "3.1.0");
+    assert_eq!(node["info"], json!({ "title": "pets", "version": "1" }));
+    assert_eq!(
+      node["components"],
+      json!({
+        "schemas": {
+          "pet": { "type": ["object", "null"], "properties": { "tag": {} } },
+        },
+        "securitySchemes": {
+          "key": { "type": "apiKey", "name": "api-key", "in": "header" },
+        },
+      })
+    );
+    assert_eq!(
+      node.pointer("/paths/~1pets~1{id}/get").unwrap(),
+      &json!({
+        "operationId": "get-pet",
+        "x-rate-limit": 10,
+        "deprecated": true,
+        "tags": ["pets"],
+        "security": [{ "key": [] }],
+        "parameters": [{
+          "name": "id",
+          "in": "path",
+          "required": true,
+          "schema": { "type": "integer", "examples": [1] },
+        }],
+        "responses": {
+          "200": {
+            "description": "a pet",
+            "content": {
+              "application/json": {
+                "schema": { "$ref": "#/components/schemas/pet" },
+              },
+            },
+          },
+          "default": {
+            "description": "an error",
+            "content": {
+              "application/json": {
+                "schema": { "$ref": "http://example.com/schemas.yaml#/error" },
+              },
+            },
+          },
+        },
+      })
+    );
+
+    assert_eq!(
+      context.write_openapi_document(&location, DocumentType::Swagger2),
+      Err(Error::UnsupportedVersion)
+    );
+
+    // the written document describes the same api
+    let written_location: NodeLocation = "http://example.com/written.yaml".parse().unwrap();
+    let written_document = context
+      .write_openapi_document(&location, DocumentType::OpenApiV30)
+      .unwrap();
+    assert!(written_document.issues.is_empty());
+    let node = written_document.node;
+    assert_eq!(
+      node["components"]["schemas"]["pet"],
+      json!({ "type": "object", "nullable": true, "properties": { "tag": { "nullable": true } } })
+    );
+    context
+      .load_from_node(&written_location, node)
+      .await
+      .unwrap();
+    let api = context.get_api_model(&location).unwrap();
+    let written_api = context.get_api_model(&written_location).unwrap();
+    let operation = &api.paths[0].operations[0];
+    let written_operation = &written_api.paths[0].operations[0];
+    assert_eq!(operation.extensions["x-rate-limit"], 10);
+    assert_eq!(written_operation.extensions, operation.extensions);
+    assert_eq!(written_api.paths[0].pattern, api.paths[0].pattern);
+    assert_eq!(written_operation.name, operation.name);
+    assert_eq!(written_operation.deprecated, operation.deprecated);
+    assert_eq!(
+      written_operation.authentication_requirements,
+      operation.authentication_requirements
+    );
+    assert_eq!(
+      written_operation.operation_results.len(),
+      operation.operation_results.len()
+    );
+    assert_eq!(written_api.authentication.len(), api.authentication.len());
+
+    // schemas can not be downgraded
+    let location: NodeLocation = "http://example.com/api-32.yaml".parse().unwrap();
+    context
+      .load_from_node(&location, json!({ "openapi": "3.2.0", "paths": {} }))
+      .await
+      .unwrap();
+    assert!(context
+      .write_openapi_document(&location, DocumentType::OpenApiV32)
+      .is_ok());
+    assert_eq!(
+      context.write_openapi_document(&location, DocumentType::OpenApiV30),
+      Err(Error::UnsupportedVersion)
+    );
+  }
+}
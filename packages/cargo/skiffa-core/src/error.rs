@@ -1,5 +1,5 @@
 use crate::documents::{DocumentError, DocumentTypeError};
-use crate::utils::{NodeCacheError, ParseLocationError};
+use crate::utils::{NodeCacheError, ParseLocationError, YamlErrorKind};
 use std::fmt::Display;
 use wasm_bindgen::prelude::*;
 
@@ -12,9 +12,14 @@ pub enum Error {
   ParseLocationFailed,
   ParseMethodFailed,
   ParseStatusKindFailed,
+  ParseOperationActionFailed,
   DocumentTypeError,
+  UnsupportedVersion,
   FetchError,
   SerializationError,
+  IntegrityError,
+  DuplicateKey,
+  NotImplemented,
 }
 
 impl std::error::Error for Error {}
@@ -28,9 +33,14 @@ impl Display for Error {
       Self::ParseLocationFailed => write!(f, "ParseLocationFailed"),
       Self::ParseMethodFailed => write!(f, "ParseMethodFailed"),
       Self::ParseStatusKindFailed => write!(f, "ParseStatusKindFailed"),
+      Self::ParseOperationActionFailed => write!(f, "ParseOperationActionFailed"),
       Self::DocumentTypeError => write!(f, "DocumentTypeError"),
+      Self::UnsupportedVersion => write!(f, "UnsupportedVersion"),
       Self::FetchError => write!(f, "FetchError"),
       Self::SerializationError => write!(f, "SerializationError"),
+      Self::IntegrityError => write!(f, "IntegrityError"),
+      Self::DuplicateKey => write!(f, "DuplicateKey"),
+      Self::NotImplemented => write!(f, "NotImplemented"),
     }
   }
   //
@@ -48,13 +58,23 @@ impl From<NodeCacheError> for Error {
       NodeCacheError::Conflict => Self::Conflict,
       NodeCacheError::FetchError => Self::FetchError,
       NodeCacheError::SerializationError => Self::SerializationError,
+      NodeCacheError::IntegrityError => Self::IntegrityError,
+      NodeCacheError::YamlError(error) => match error.kind {
+        YamlErrorKind::DuplicateKey => Self::DuplicateKey,
+        _ => Self::SerializationError,
+      },
     }
   }
 }
 
 impl From<DocumentTypeError> for Error {
-  fn from(_value: DocumentTypeError) -> Self {
-    Self::DocumentTypeError
+  fn from(value: DocumentTypeError) -> Self {
+    match value {
+      DocumentTypeError::UnsupportedVersion { .. } => Self::UnsupportedVersion,
+      DocumentTypeError::Unknown | DocumentTypeError::InvalidVersion { .. } => {
+        Self::DocumentTypeError
+      }
+    }
   }
 }
 
@@ -66,6 +86,9 @@ impl From<DocumentError> for Error {
       DocumentError::ParseLocationFailed => Self::ParseLocationFailed,
       DocumentError::ParseMethodFailed => Self::ParseMethodFailed,
       DocumentError::ParseStatusKindFailed => Self::ParseStatusKindFailed,
+      DocumentError::ParseOperationActionFailed => Self::ParseOperationActionFailed,
+      DocumentError::ModelNotSupported => Self::NotFound,
+      DocumentError::ModelNotImplemented => Self::NotImplemented,
     }
   }
 }
@@ -1,23 +1,51 @@
 mod api;
+mod api_change;
+mod api_diff;
+mod api_ir;
+mod api_merge;
+mod api_slice;
+mod async_api;
 mod authentication;
+mod authentication_flow;
 mod authentication_requirement;
 mod authentication_requirement_group;
+mod binding;
 mod body;
+mod body_kind;
+mod channel;
+mod channel_operation;
+mod media_type;
+mod message;
 mod method;
 mod operation;
 mod operation_result;
 mod parameter;
 mod path;
 mod status_kind;
+mod tag;
 
 pub use api::*;
+pub use api_change::*;
+pub use api_diff::*;
+pub use api_ir::*;
+pub use api_merge::*;
+pub use api_slice::*;
+pub use async_api::*;
 pub use authentication::*;
+pub use authentication_flow::*;
 pub use authentication_requirement::*;
 pub use authentication_requirement_group::*;
+pub use binding::*;
 pub use body::*;
+pub use body_kind::*;
+pub use channel::*;
+pub use channel_operation::*;
+pub use media_type::*;
+pub use message::*;
 pub use method::*;
 pub use operation::*;
 pub use operation_result::*;
 pub use parameter::*;
 pub use path::*;
 pub use status_kind::*;
+pub use tag::*;
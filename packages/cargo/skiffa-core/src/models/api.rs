@@ -1,17 +1,33 @@
-use super::{Authentication, AuthenticationContainer, Path, PathContainer};
+use super::{
+  ApiIr, Authentication, AuthenticationContainer, Path, PathContainer, Tag, TagContainer,
+};
+use crate::error::Error;
+use crate::utils::extensions_to_js_value;
+use crate::utils::sync;
 use crate::utils::NodeLocation;
-use std::rc;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use wasm_bindgen::prelude::*;
 
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Api {
   pub location: NodeLocation,
-  pub paths: Vec<rc::Rc<Path>>,
-  pub authentication: Vec<rc::Rc<Authentication>>,
+  pub paths: Vec<sync::Shared<Path>>,
+  pub authentication: Vec<sync::Shared<Authentication>>,
+  #[serde(default)]
+  pub tags: Vec<sync::Shared<Tag>>,
+  #[serde(default)]
+  /// The location the api says it has (`$self`), if any
+  pub self_location: Option<NodeLocation>,
+  /// Specification extensions (`x-` members), by name
+  #[serde(default)]
+  pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
 #[derive(Clone)]
 #[wasm_bindgen]
-pub struct ApiContainer(rc::Rc<Api>);
+pub struct ApiContainer(sync::Shared<Api>);
 
 #[wasm_bindgen]
 impl ApiContainer {
@@ -41,10 +57,41 @@ impl ApiContainer {
       .map(|model| model.into())
       .collect()
   }
+
+  #[wasm_bindgen(getter, js_name = "tags")]
+  pub fn tags(&self) -> Vec<TagContainer> {
+    self
+      .0
+      .tags
+      .iter()
+      .cloned()
+      .map(|model| model.into())
+      .collect()
+  }
+
+  #[wasm_bindgen(getter, js_name = "selfLocation")]
+  pub fn self_location(&self) -> Option<String> {
+    Some(self.0.self_location.as_ref()?.to_string())
+  }
+
+  #[wasm_bindgen(js_name = "toIr")]
+  pub fn to_ir(&self) -> Result<String, Error> {
+    ApiIr::new(self.0.clone()).to_json()
+  }
+
+  #[wasm_bindgen(js_name = "fromIr")]
+  pub fn from_ir(text: &str) -> Result<ApiContainer, Error> {
+    Ok(ApiIr::from_json(text)?.api.into())
+  }
+
+  #[wasm_bindgen(getter, js_name = "extensions")]
+  pub fn extensions(&self) -> JsValue {
+    extensions_to_js_value(&self.0.extensions)
+  }
 }
 
-impl From<rc::Rc<Api>> for ApiContainer {
-  fn from(interior: rc::Rc<Api>) -> Self {
+impl From<sync::Shared<Api>> for ApiContainer {
+  fn from(interior: sync::Shared<Api>) -> Self {
     Self(interior)
   }
 }
@@ -0,0 +1,147 @@
+use super::{Api, Method, Operation, Path};
+use crate::utils::sync;
+use crate::utils::NodeLocation;
+use std::collections::BTreeMap;
+use wasm_bindgen::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[wasm_bindgen]
+pub enum ChangeKind {
+  Added,
+  Removed,
+  Changed,
+}
+
+/// A path, or an operation of a path, that changed between two versions of
+/// an api. If there is no method the change is about the path, otherwise it
+/// is about the operation with that method.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiChange {
+  pub kind: ChangeKind,
+  pub api_location: NodeLocation,
+  pub pattern: String,
+  pub method: Option<Method>,
+}
+
+impl ApiChange {
+  /// Compare two versions of an api, a missing version means that the api
+  /// was added or removed. The changes are ordered by pattern, the change of a
+  /// path comes before the changes of its operations.
+  ///
+  pub fn get_changes(
+    api_location: &NodeLocation,
+    old_api: Option<&Api>,
+    new_api: Option<&Api>,
+  ) -> Vec<ApiChange> {
+    let old_paths = Self::get_paths(old_api);
+    let new_paths = Self::get_paths(new_api);
+
+    let mut patterns: Vec<_> = old_paths.keys().chain(new_paths.keys()).collect();
+    patterns.sort();
+    patterns.dedup();
+
+    let mut result = Vec::new();
+    for pattern in patterns {
+      let old_operations = Self::get_operations(old_paths.get(pattern).copied());
+      let new_operations = Self::get_operations(new_paths.get(pattern).copied());
+
+      let mut methods: Vec<_> = old_operations
+        .keys()
+        .chain(new_operations.keys())
+        .cloned()
+        .collect();
+      methods.sort();
+      methods.dedup();
+
+      let operation_changes: Vec<_> = methods
+        .into_iter()
+        .filter_map(|method| {
+          let kind = Self::get_kind(old_operations.get(&method), new_operations.get(&method))?;
+          Some(ApiChange {
+            kind,
+            api_location: api_location.clone(),
+            pattern: pattern.to_string(),
+            method: Some(method),
+          })
+        })
+        .collect();
+
+      let path_kind = match (old_paths.get(pattern), new_paths.get(pattern)) {
+        (None, Some(_)) => Some(ChangeKind::Added),
+        (Some(_), None) => Some(ChangeKind::Removed),
+        _ if !operation_changes.is_empty() => Some(ChangeKind::Changed),
+        _ => None,
+      };
+      if let Some(kind) = path_kind {
+        result.push(ApiChange {
+          kind,
+          api_location: api_location.clone(),
+          pattern: pattern.to_string(),
+          method: None,
+        });
+      }
+      result.extend(operation_changes);
+    }
+
+    result
+  }
+
+  fn get_paths(api: Option<&Api>) -> BTreeMap<&String, &Path> {
+    api
+      .into_iter()
+      .flat_map(|api| api.paths.iter())
+      .map(|path| (&path.pattern, path.as_ref()))
+      .collect()
+  }
+
+  fn get_operations(path: Option<&Path>) -> BTreeMap<Method, &Operation> {
+    path
+      .into_iter()
+      .flat_map(|path| path.operations.iter())
+      .map(|operation| (operation.method.clone(), operation.as_ref()))
+      .collect()
+  }
+
+  fn get_kind<T: PartialEq>(old: Option<T>, new: Option<T>) -> Option<ChangeKind> {
+    match (old, new) {
+      (None, Some(_)) => Some(ChangeKind::Added),
+      (Some(_), None) => Some(ChangeKind::Removed),
+      (Some(old), Some(new)) if old != new => Some(ChangeKind::Changed),
+      _ => None,
+    }
+  }
+}
+
+#[derive(Clone)]
+#[wasm_bindgen]
+pub struct ApiChangeContainer(sync::Shared<ApiChange>);
+
+#[wasm_bindgen]
+impl ApiChangeContainer {
+  #[wasm_bindgen(getter, js_name = "kind")]
+  pub fn kind(&self) -> ChangeKind {
+    self.0.kind
+  }
+
+  #[wasm_bindgen(getter, js_name = "apiLocation")]
+  pub fn api_location(&self) -> String {
+    self.0.api_location.to_string()
+  }
+
+  #[wasm_bindgen(getter, js_name = "pattern")]
+  pub fn pattern(&self) -> String {
+    self.0.pattern.clone()
+  }
+
+  #[wasm_bindgen(getter, js_name = "method")]
+  pub fn method(&self) -> Option<String> {
+    self.0.method.as_ref().map(|method| method.to_string())
+  }
+}
+
+impl From<sync::Shared<ApiChange>> for ApiChangeContainer {
+  fn from(interior: sync::Shared<ApiChange>) -> Self {
+    Self(interior)
+  }
+}
@@ -0,0 +1,957 @@
+use super::{Api, AuthenticationRequirementGroup, Body, Operation, OperationResult, Parameter};
+use crate::utils::sync;
+use crate::utils::NodeLocation;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use wasm_bindgen::prelude::*;
+
+/// How many references we follow before we give up, references could point
+/// to each other.
+///
+const MAXIMUM_REFERENCE_DEPTH: usize = 32;
+
+/// Access to the nodes of the documents the compared apis are in, needed to
+/// compare schemas. Implemented by the `DocumentContext`.
+///
+pub trait SchemaSource {
+  fn get_node(&self, retrieval_location: &NodeLocation) -> Option<serde_json::Value>;
+  fn get_retrieval_location(&self, identity_location: &NodeLocation) -> Option<NodeLocation>;
+  fn resolve_reference(
+    &self,
+    retrieval_location: &NodeLocation,
+    reference_location: &NodeLocation,
+  ) -> NodeLocation;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[wasm_bindgen]
+pub enum DifferenceKind {
+  PathAdded,
+  PathRemoved,
+  OperationAdded,
+  OperationRemoved,
+  ParameterAdded,
+  ParameterRemoved,
+  ParameterBecameRequired,
+  ParameterBecameOptional,
+  RequestContentTypeAdded,
+  RequestContentTypeRemoved,
+  ResponseAdded,
+  ResponseRemoved,
+  ResponseContentTypeAdded,
+  ResponseContentTypeRemoved,
+  AuthenticationAdded,
+  AuthenticationRemoved,
+  AuthenticationChanged,
+  AuthenticationRequirementsChanged,
+  SchemaTypeChanged,
+  SchemaPropertyAdded,
+  SchemaPropertyRemoved,
+  SchemaPropertyBecameRequired,
+  SchemaPropertyBecameOptional,
+  SchemaEnumValueAdded,
+  SchemaEnumValueRemoved,
+}
+
+/// A single difference between two versions of an api. A difference is
+/// breaking if clients of the old version may fail with the new version.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiDifference {
+  pub kind: DifferenceKind,
+  pub breaking: bool,
+  pub old_location: Option<NodeLocation>,
+  pub new_location: Option<NodeLocation>,
+  pub message: String,
+}
+
+/// All differences between two versions of an api
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ApiDiff {
+  pub differences: Vec<ApiDifference>,
+}
+
+impl ApiDiff {
+  /// Compare the apis, schemas of the old api are read from the old source
+  /// and schemas of the new api from the new source. Both may be the same
+  /// source.
+  ///
+  pub fn new(
+    old_api: &Api,
+    new_api: &Api,
+    old_source: &dyn SchemaSource,
+    new_source: &dyn SchemaSource,
+  ) -> Self {
+    let mut differ = Differ {
+      old_source,
+      new_source,
+      differences: Vec::new(),
+      visited: BTreeSet::new(),
+    };
+    differ.diff_api(old_api, new_api);
+
+    Self {
+      differences: differ.differences,
+    }
+  }
+
+  pub fn is_breaking(&self) -> bool {
+    self
+      .differences
+      .iter()
+      .any(|difference| difference.breaking)
+  }
+
+  /// A machine readable report of the diff
+  pub fn to_report(&self) -> serde_json::Value {
+    let breaking_count = self
+      .differences
+      .iter()
+      .filter(|difference| difference.breaking)
+      .count();
+
+    serde_json::json!({
+      "breaking": self.is_breaking(),
+      "summary": {
+        "breaking": breaking_count,
+        "nonBreaking": self.differences.len() - breaking_count,
+      },
+      "differences": self.differences,
+    })
+  }
+}
+
+/// Schemas are compared from the perspective of the client, in a request a
+/// schema may not get stricter, in a response it may not get looser.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Direction {
+  Request,
+  Response,
+}
+
+struct Differ<'a> {
+  old_source: &'a dyn SchemaSource,
+  new_source: &'a dyn SchemaSource,
+  differences: Vec<ApiDifference>,
+  /**
+   * schemas that are compared, a schema that is used in a request and in a
+   * response is compared in both directions
+   */
+  visited: BTreeSet<(NodeLocation, NodeLocation, Direction)>,
+}
+
+impl<'a> Differ<'a> {
+  fn add(
+    &mut self,
+    kind: DifferenceKind,
+    breaking: bool,
+    old_location: Option<&NodeLocation>,
+    new_location: Option<&NodeLocation>,
+    message: String,
+  ) {
+    self.differences.push(ApiDifference {
+      kind,
+      breaking,
+      old_location: old_location.cloned(),
+      new_location: new_location.cloned(),
+      message,
+    });
+  }
+
+  fn diff_api(&mut self, old_api: &Api, new_api: &Api) {
+    let old_authentication: BTreeMap<_, _> = old_api
+      .authentication
+      .iter()
+      .map(|authentication| (authentication.name.clone(), authentication))
+      .collect();
+    let new_authentication: BTreeMap<_, _> = new_api
+      .authentication
+      .iter()
+      .map(|authentication| (authentication.name.clone(), authentication))
+      .collect();
+    for (name, old, new) in join(&old_authentication, &new_authentication) {
+      match (old, new) {
+        (Some(old), None) => self.add(
+          DifferenceKind::AuthenticationRemoved,
+          true,
+          Some(&old.location),
+          None,
+          format!("authentication {} removed", name),
+        ),
+        (None, Some(new)) => self.add(
+          DifferenceKind::AuthenticationAdded,
+          false,
+          None,
+          Some(&new.location),
+          format!("authentication {} added", name),
+        ),
+        (Some(old), Some(new)) => {
+          if old.r#type != new.r#type
+            || old.scheme != new.scheme
+            || old.r#in != new.r#in
+            || old.parameter_name != new.parameter_name
+          {
+            self.add(
+              DifferenceKind::AuthenticationChanged,
+              true,
+              Some(&old.location),
+              Some(&new.location),
+              format!("authentication {} changed", name),
+            )
+          }
+        }
+        (None, None) => unreachable!(),
+      }
+    }
+
+    // paths are matched without the names of the path parameters
+    let old_paths: BTreeMap<_, _> = old_api
+      .paths
+      .iter()
+      .map(|path| (normalize_pattern(&path.pattern), path))
+      .collect();
+    let new_paths: BTreeMap<_, _> = new_api
+      .paths
+      .iter()
+      .map(|path| (normalize_pattern(&path.pattern), path))
+      .collect();
+    for (_pattern, old_path, new_path) in join(&old_paths, &new_paths) {
+      match (old_path, new_path) {
+        (Some(old_path), None) => self.add(
+          DifferenceKind::PathRemoved,
+          true,
+          Some(&old_path.location),
+          None,
+          format!("path {} removed", old_path.pattern),
+        ),
+        (None, Some(new_path)) => self.add(
+          DifferenceKind::PathAdded,
+          false,
+          None,
+          Some(&new_path.location),
+          format!("path {} added", new_path.pattern),
+        ),
+        (Some(old_path), Some(new_path)) => {
+          let old_operations: BTreeMap<_, _> = old_path
+            .operations
+            .iter()
+            .map(|operation| (operation.method.clone(), operation))
+            .collect();
+          let new_operations: BTreeMap<_, _> = new_path
+            .operations
+            .iter()
+            .map(|operation| (operation.method.clone(), operation))
+            .collect();
+          for (method, old_operation, new_operation) in join(&old_operations, &new_operations) {
+            let name = format!("{} {}", method.to_string(), new_path.pattern);
+            match (old_operation, new_operation) {
+              (Some(old_operation), None) => self.add(
+                DifferenceKind::OperationRemoved,
+                true,
+                Some(&old_operation.location),
+                None,
+                format!("operation {} removed", name),
+              ),
+              (None, Some(new_operation)) => self.add(
+                DifferenceKind::OperationAdded,
+                false,
+                None,
+                Some(&new_operation.location),
+                format!("operation {} added", name),
+              ),
+              (Some(old_operation), Some(new_operation)) => {
+                // path parameters are matched by their position in the
+                // pattern, so they get the names of the new pattern
+                let old_operation = rename_path_parameters(
+                  old_operation,
+                  &get_parameter_renames(&old_path.pattern, &new_path.pattern),
+                );
+                self.diff_operation(&name, &old_operation, new_operation)
+              }
+              (None, None) => unreachable!(),
+            }
+          }
+        }
+        (None, None) => unreachable!(),
+      }
+    }
+  }
+
+  fn diff_operation(&mut self, name: &str, old_operation: &Operation, new_operation: &Operation) {
+    let old_groups = get_requirement_groups(&old_operation.authentication_requirements);
+    let new_groups = get_requirement_groups(&new_operation.authentication_requirements);
+    if old_groups != new_groups {
+      // clients that satisfied the old requirements should satisfy the new
+      // ones, no requirements at all is always satisfied
+      let breaking =
+        !new_groups.is_empty() && (old_groups.is_empty() || !old_groups.is_subset(&new_groups));
+      self.add(
+        DifferenceKind::AuthenticationRequirementsChanged,
+        breaking,
+        Some(&old_operation.location),
+        Some(&new_operation.location),
+        format!("authentication requirements of operation {} changed", name),
+      );
+    }
+
+    for (kind, old_parameters, new_parameters) in [
+      (
+        "path",
+        &old_operation.path_parameters,
+        &new_operation.path_parameters,
+      ),
+      (
+        "query",
+        &old_operation.query_parameters,
+        &new_operation.query_parameters,
+      ),
+      (
+        "header",
+        &old_operation.header_parameters,
+        &new_operation.header_parameters,
+      ),
+      (
+        "cookie",
+        &old_operation.cookie_parameters,
+        &new_operation.cookie_parameters,
+      ),
+      (
+        "querystring",
+        &old_operation.querystring_parameters,
+        &new_operation.querystring_parameters,
+      ),
+    ] {
+      self.diff_parameters(
+        kind,
+        old_parameters,
+        new_parameters,
+        kind == "header",
+        Direction::Request,
+      );
+    }
+
+    self.diff_bodies(
+      old_operation.bodies.as_slice(),
+      new_operation.bodies.as_slice(),
+      Direction::Request,
+    );
+
+    let old_results: BTreeMap<_, _> = old_operation
+      .operation_results
+      .iter()
+      .map(|operation_result| (operation_result.status_kind, operation_result))
+      .collect();
+    let new_results: BTreeMap<_, _> = new_operation
+      .operation_results
+      .iter()
+      .map(|operation_result| (operation_result.status_kind, operation_result))
+      .collect();
+    for (status_kind, old_result, new_result) in join(&old_results, &new_results) {
+      match (old_result, new_result) {
+        (Some(old_result), None) => self.add(
+          DifferenceKind::ResponseRemoved,
+          true,
+          Some(&old_result.location),
+          None,
+          format!(
+            "response {} of operation {} removed",
+            status_kind.to_string(),
+            name
+          ),
+        ),
+        (None, Some(new_result)) => self.add(
+          DifferenceKind::ResponseAdded,
+          false,
+          None,
+          Some(&new_result.location),
+          format!(
+            "response {} of operation {} added",
+            status_kind.to_string(),
+            name
+          ),
+        ),
+        (Some(old_result), Some(new_result)) => self.diff_operation_result(old_result, new_result),
+        (None, None) => unreachable!(),
+      }
+    }
+  }
+
+  fn diff_operation_result(&mut self, old_result: &OperationResult, new_result: &OperationResult) {
+    self.diff_parameters(
+      "response header",
+      &old_result.header_parameters,
+      &new_result.header_parameters,
+      true,
+      Direction::Response,
+    );
+    self.diff_bodies(
+      old_result.bodies.as_slice(),
+      new_result.bodies.as_slice(),
+      Direction::Response,
+    );
+  }
+
+  fn diff_parameters(
+    &mut self,
+    kind: &str,
+    old_parameters: &[sync::Shared<Parameter>],
+    new_parameters: &[sync::Shared<Parameter>],
+    case_insensitive: bool,
+    direction: Direction,
+  ) {
+    let get_key = |parameter: &sync::Shared<Parameter>| {
+      if case_insensitive {
+        parameter.name.to_ascii_lowercase()
+      } else {
+        parameter.name.clone()
+      }
+    };
+    let old_parameters: BTreeMap<_, _> = old_parameters
+      .iter()
+      .map(|parameter| (get_key(parameter), parameter))
+      .collect();
+    let new_parameters: BTreeMap<_, _> = new_parameters
+      .iter()
+      .map(|parameter| (get_key(parameter), parameter))
+      .collect();
+
+    for (name, old_parameter, new_parameter) in join(&old_parameters, &new_parameters) {
+      match (old_parameter, new_parameter) {
+        (Some(old_parameter), None) => self.add(
+          DifferenceKind::ParameterRemoved,
+          // a client might depend on a response header
+          direction == Direction::Response,
+          Some(&old_parameter.location),
+          None,
+          format!("{} parameter {} removed", kind, name),
+        ),
+        (None, Some(new_parameter)) => self.add(
+          DifferenceKind::ParameterAdded,
+          direction == Direction::Request && new_parameter.required,
+          None,
+          Some(&new_parameter.location),
+          format!("{} parameter {} added", kind, name),
+        ),
+        (Some(old_parameter), Some(new_parameter)) => {
+          if !old_parameter.required && new_parameter.required {
+            self.add(
+              DifferenceKind::ParameterBecameRequired,
+              direction == Direction::Request,
+              Some(&old_parameter.location),
+              Some(&new_parameter.location),
+              format!("{} parameter {} became required", kind, name),
+            )
+          }
+          if old_parameter.required && !new_parameter.required {
+            self.add(
+              DifferenceKind::ParameterBecameOptional,
+              direction == Direction::Response,
+              Some(&old_parameter.location),
+              Some(&new_parameter.location),
+              format!("{} parameter {} became optional", kind, name),
+            )
+          }
+          if let (Some(old_schema_id), Some(new_schema_id)) =
+            (&old_parameter.schema_id, &new_parameter.schema_id)
+          {
+            self.diff_schema_ids(old_schema_id, new_schema_id, direction);
+          }
+        }
+        (None, None) => unreachable!(),
+      }
+    }
+  }
+
+  fn diff_bodies(
+    &mut self,
+    old_bodies: &[sync::Shared<Body>],
+    new_bodies: &[sync::Shared<Body>],
+    direction: Direction,
+  ) {
+    let (added_kind, removed_kind, prefix) = match direction {
+      Direction::Request => (
+        DifferenceKind::RequestContentTypeAdded,
+        DifferenceKind::RequestContentTypeRemoved,
+        "request",
+      ),
+      Direction::Response => (
+        DifferenceKind::ResponseContentTypeAdded,
+        DifferenceKind::ResponseContentTypeRemoved,
+        "response",
+      ),
+    };
+
+    let old_bodies: BTreeMap<_, _> = old_bodies
+      .iter()
+      .map(|body| (body.content_type.to_ascii_lowercase(), body))
+      .collect();
+    let new_bodies: BTreeMap<_, _> = new_bodies
+      .iter()
+      .map(|body| (body.content_type.to_ascii_lowercase(), body))
+      .collect();
+
+    for (content_type, old_body, new_body) in join(&old_bodies, &new_bodies) {
+      match (old_body, new_body) {
+        (Some(old_body), None) => self.add(
+          removed_kind,
+          true,
+          Some(&old_body.location),
+          None,
+          format!("{} content type {} removed", prefix, content_type),
+        ),
+        (None, Some(new_body)) => self.add(
+          added_kind,
+          false,
+          None,
+          Some(&new_body.location),
+          format!("{} content type {} added", prefix, content_type),
+        ),
+        (Some(old_body), Some(new_body)) => {
+          if let (Some(old_schema_id), Some(new_schema_id)) =
+            (&old_body.schema_id, &new_body.schema_id)
+          {
+            self.diff_schema_ids(old_schema_id, new_schema_id, direction);
+          }
+          if let (Some(old_schema_id), Some(new_schema_id)) =
+            (old_body.get_item_schema_id(), new_body.get_item_schema_id())
+          {
+            if old_body.schema_id.as_ref() != Some(old_schema_id)
+              || new_body.schema_id.as_ref() != Some(new_schema_id)
+            {
+              self.diff_schema_ids(old_schema_id, new_schema_id, direction);
+            }
+          }
+        }
+        (None, None) => unreachable!(),
+      }
+    }
+  }
+
+  fn diff_schema_ids(
+    &mut self,
+    old_schema_id: &NodeLocation,
+    new_schema_id: &NodeLocation,
+    direction: Direction,
+  ) {
+    let old_location = self
+      .old_source
+      .get_retrieval_location(old_schema_id)
+      .unwrap_or_else(|| old_schema_id.clone());
+    let new_location = self
+      .new_source
+      .get_retrieval_location(new_schema_id)
+      .unwrap_or_else(|| new_schema_id.clone());
+    self.diff_schemas(old_location, new_location, direction);
+  }
+
+  fn diff_schemas(
+    &mut self,
+    old_location: NodeLocation,
+    new_location: NodeLocation,
+    direction: Direction,
+  ) {
+    let Some((old_location, old_node)) = dereference(self.old_source, old_location) else {
+      return;
+    };
+    let Some((new_location, new_node)) = dereference(self.new_source, new_location) else {
+      return;
+    };
+    if !self
+      .visited
+      .insert((old_location.clone(), new_location.clone(), direction))
+    {
+      return;
+    }
+    let (Some(old_node), Some(new_node)) = (old_node.as_object(), new_node.as_object()) else {
+      return;
+    };
+
+    let old_types = get_types(old_node);
+    let new_types = get_types(new_node);
+    if old_types != new_types {
+      let breaking = match direction {
+        Direction::Request => !types_cover(new_types.as_ref(), old_types.as_ref()),
+        Direction::Response => !types_cover(old_types.as_ref(), new_types.as_ref()),
+      };
+      self.add(
+        DifferenceKind::SchemaTypeChanged,
+        breaking,
+        Some(&old_location),
+        Some(&new_location),
+        format!(
+          "type changed from {} to {}",
+          format_types(old_types.as_ref()),
+          format_types(new_types.as_ref())
+        ),
+      );
+    }
+
+    if let (Some(old_values), Some(new_values)) = (
+      old_node.get("enum").and_then(|value| value.as_array()),
+      new_node.get("enum").and_then(|value| value.as_array()),
+    ) {
+      for value in old_values {
+        if !new_values.contains(value) {
+          self.add(
+            DifferenceKind::SchemaEnumValueRemoved,
+            direction == Direction::Request,
+            Some(&old_location),
+            Some(&new_location),
+            format!("enum value {} removed", value),
+          );
+        }
+      }
+      for value in new_values {
+        if !old_values.contains(value) {
+          self.add(
+            DifferenceKind::SchemaEnumValueAdded,
+            direction == Direction::Response,
+            Some(&old_location),
+            Some(&new_location),
+            format!("enum value {} added", value),
+          );
+        }
+      }
+    }
+
+    let old_required = get_required(old_node);
+    let new_required = get_required(new_node);
+    for name in new_required.difference(&old_required) {
+      self.add(
+        DifferenceKind::SchemaPropertyBecameRequired,
+        direction == Direction::Request,
+        Some(&old_location),
+        Some(&new_location),
+        format!("property {} became required", name),
+      );
+    }
+    for name in old_required.difference(&new_required) {
+      self.add(
+        DifferenceKind::SchemaPropertyBecameOptional,
+        direction == Direction::Response,
+        Some(&old_location),
+        Some(&new_location),
+        format!("property {} became optional", name),
+      );
+    }
+
+    let empty = serde_json::Map::new();
+    let old_properties = old_node
+      .get("properties")
+      .and_then(|value| value.as_object())
+      .unwrap_or(&empty);
+    let new_properties = new_node
+      .get("properties")
+      .and_then(|value| value.as_object())
+      .unwrap_or(&empty);
+    for name in old_properties.keys() {
+      if !new_properties.contains_key(name) {
+        self.add(
+          DifferenceKind::SchemaPropertyRemoved,
+          direction == Direction::Response,
+          Some(&old_location.push_pointer(vec!["properties".to_owned(), name.clone()])),
+          Some(&new_location),
+          format!("property {} removed", name),
+        );
+      }
+    }
+    for name in new_properties.keys() {
+      if !old_properties.contains_key(name) {
+        self.add(
+          DifferenceKind::SchemaPropertyAdded,
+          false,
+          Some(&old_location),
+          Some(&new_location.push_pointer(vec!["properties".to_owned(), name.clone()])),
+          format!("property {} added", name),
+        );
+      }
+    }
+    for name in old_properties.keys() {
+      if new_properties.contains_key(name) {
+        let pointer = vec!["properties".to_owned(), name.clone()];
+        self.diff_schemas(
+          old_location.push_pointer(pointer.clone()),
+          new_location.push_pointer(pointer),
+          direction,
+        );
+      }
+    }
+
+    if old_node.get("items").is_some_and(|value| value.is_object())
+      && new_node.get("items").is_some_and(|value| value.is_object())
+    {
+      let pointer = vec!["items".to_owned()];
+      self.diff_schemas(
+        old_location.push_pointer(pointer.clone()),
+        new_location.push_pointer(pointer),
+        direction,
+      );
+    }
+  }
+}
+
+/// Follow `$ref`s until we find a schema without one
+fn dereference(
+  source: &dyn SchemaSource,
+  location: NodeLocation,
+) -> Option<(NodeLocation, serde_json::Value)> {
+  let mut location = location;
+  for _ in 0..MAXIMUM_REFERENCE_DEPTH {
+    let node = source.get_node(&location)?;
+    let Some(reference) = node.get("$ref").and_then(|value| value.as_str()) else {
+      return Some((location, node));
+    };
+    let reference_location: NodeLocation = reference.parse().ok()?;
+    location = source.resolve_reference(&location, &reference_location);
+  }
+  None
+}
+
+/// Join two maps on their keys
+fn join<'a, K, V>(
+  old: &'a BTreeMap<K, V>,
+  new: &'a BTreeMap<K, V>,
+) -> Vec<(&'a K, Option<&'a V>, Option<&'a V>)>
+where
+  K: Ord,
+{
+  let keys: BTreeSet<_> = old.keys().chain(new.keys()).collect();
+  keys
+    .into_iter()
+    .map(|key| (key, old.get(key), new.get(key)))
+    .collect()
+}
+
+/// Replace the names of path parameters, so `/pets/{id}` and `/pets/{petId}`
+/// are the same path.
+///
+pub(super) fn normalize_pattern(pattern: &str) -> String {
+  let mut result = String::new();
+  let mut in_parameter = false;
+  for c in pattern.chars() {
+    match c {
+      '{' => {
+        in_parameter = true;
+        result.push_str("{}");
+      }
+      '}' => in_parameter = false,
+      _ if in_parameter => {}
+      c => result.push(c),
+    }
+  }
+  result
+}
+
+/// The names of the parameters in the pattern, in order
+fn get_parameter_names(pattern: &str) -> Vec<String> {
+  pattern
+    .split('{')
+    .skip(1)
+    .filter_map(|part| part.split_once('}'))
+    .map(|(name, _)| name.to_owned())
+    .collect()
+}
+
+/// The operation with the path parameters renamed, the names are a map of
+/// old names to new names.
+///
+pub(super) fn rename_path_parameters(
+  operation: &sync::Shared<Operation>,
+  names: &BTreeMap<String, String>,
+) -> sync::Shared<Operation> {
+  if names.is_empty() {
+    return operation.clone();
+  }
+
+  let path_parameters = operation
+    .path_parameters
+    .iter()
+    .map(|parameter| match names.get(&parameter.name) {
+      Some(name) => sync::Shared::new(Parameter {
+        name: name.clone(),
+        ..parameter.as_ref().clone()
+      }),
+      None => parameter.clone(),
+    })
+    .collect();
+  sync::Shared::new(Operation {
+    path_parameters,
+    ..operation.as_ref().clone()
+  })
+}
+
+/// The names of the path parameters of the pattern, by position, mapped to
+/// the names in the target pattern. Only names that differ are included.
+///
+pub(super) fn get_parameter_renames(
+  pattern: &str,
+  target_pattern: &str,
+) -> BTreeMap<String, String> {
+  get_parameter_names(pattern)
+    .into_iter()
+    .zip(get_parameter_names(target_pattern))
+    .filter(|(name, target_name)| name != target_name)
+    .collect()
+}
+
+fn get_requirement_groups(
+  groups: &[sync::Shared<AuthenticationRequirementGroup>],
+) -> BTreeSet<BTreeMap<String, BTreeSet<String>>> {
+  groups
+    .iter()
+    .map(|group| {
+      group
+        .requirements
+        .iter()
+        .map(|requirement| {
+          (
+            requirement.authentication_name.clone(),
+            requirement.scopes.iter().cloned().collect(),
+          )
+        })
+        .collect()
+    })
+    .collect()
+}
+
+/// The types of a schema, nothing means any type
+fn get_types(node: &serde_json::Map<String, serde_json::Value>) -> Option<BTreeSet<String>> {
+  match node.get("type")? {
+    serde_json::Value::String(value) => Some(BTreeSet::from([value.clone()])),
+    serde_json::Value::Array(values) => Some(
+      values
+        .iter()
+        .filter_map(|value| Some(value.as_str()?.to_owned()))
+        .collect(),
+    ),
+    _ => None,
+  }
+}
+
+/// Are all values of the narrow types also valid for the wide types? An
+/// integer is also a number.
+///
+fn types_cover(wide: Option<&BTreeSet<String>>, narrow: Option<&BTreeSet<String>>) -> bool {
+  let Some(wide) = wide else {
+    return true;
+  };
+  let Some(narrow) = narrow else {
+    return false;
+  };
+  narrow
+    .iter()
+    .all(|value| wide.contains(value) || (value == "integer" && wide.contains("number")))
+}
+
+fn format_types(types: Option<&BTreeSet<String>>) -> String {
+  match types {
+    None => "any".to_owned(),
+    Some(types) => types.iter().cloned().collect::<Vec<_>>().join(" | "),
+  }
+}
+
+fn get_required(node: &serde_json::Map<String, serde_json::Value>) -> BTreeSet<String> {
+  node
+    .get("required")
+    .and_then(|value| value.as_array())
+    .into_iter()
+    .flatten()
+    .filter_map(|value| Some(value.as_str()?.to_owned()))
+    .collect()
+}
+
+#[derive(Clone)]
+#[wasm_bindgen]
+pub struct ApiDiffContainer(sync::Shared<ApiDiff>);
+
+#[wasm_bindgen]
+impl ApiDiffContainer {
+  #[wasm_bindgen(getter, js_name = "breaking")]
+  pub fn breaking(&self) -> bool {
+    self.0.is_breaking()
+  }
+
+  #[wasm_bindgen(getter, js_name = "differences")]
+  pub fn differences(&self) -> Vec<ApiDifferenceContainer> {
+    self
+      .0
+      .differences
+      .iter()
+      .cloned()
+      .map(|model| sync::Shared::new(model).into())
+      .collect()
+  }
+
+  #[wasm_bindgen(js_name = "toReport")]
+  pub fn to_report(&self) -> String {
+    serde_json::to_string_pretty(&self.0.to_report()).unwrap()
+  }
+}
+
+impl From<sync::Shared<ApiDiff>> for ApiDiffContainer {
+  fn from(interior: sync::Shared<ApiDiff>) -> Self {
+    Self(interior)
+  }
+}
+
+#[derive(Clone)]
+#[wasm_bindgen]
+pub struct ApiDifferenceContainer(sync::Shared<ApiDifference>);
+
+#[wasm_bindgen]
+impl ApiDifferenceContainer {
+  #[wasm_bindgen(getter, js_name = "kind")]
+  pub fn kind(&self) -> DifferenceKind {
+    self.0.kind
+  }
+
+  #[wasm_bindgen(getter, js_name = "breaking")]
+  pub fn breaking(&self) -> bool {
+    self.0.breaking
+  }
+
+  #[wasm_bindgen(getter, js_name = "oldLocation")]
+  pub fn old_location(&self) -> Option<String> {
+    Some(self.0.old_location.as_ref()?.to_string())
+  }
+
+  #[wasm_bindgen(getter, js_name = "newLocation")]
+  pub fn new_location(&self) -> Option<String> {
+    Some(self.0.new_location.as_ref()?.to_string())
+  }
+
+  #[wasm_bindgen(getter, js_name = "message")]
+  pub fn message(&self) -> String {
+    self.0.message.clone()
+  }
+}
+
+impl From<sync::Shared<ApiDifference>> for ApiDifferenceContainer {
+  fn from(interior: sync::Shared<ApiDifference>) -> Self {
+    Self(interior)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_normalize_pattern() {
+    assert_eq!(normalize_pattern("/pets/{petId}/toys"), "/pets/{}/toys");
+    assert_eq!(normalize_pattern("/pets"), "/pets");
+  }
+
+  #[test]
+  fn test_types_cover() {
+    let number = BTreeSet::from(["number".to_owned()]);
+    let integer = BTreeSet::from(["integer".to_owned()]);
+    assert!(types_cover(Some(&number), Some(&integer)));
+    assert!(!types_cover(Some(&integer), Some(&number)));
+    assert!(types_cover(None, Some(&integer)));
+    assert!(!types_cover(Some(&integer), None));
+  }
+}
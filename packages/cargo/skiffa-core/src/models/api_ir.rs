@@ -0,0 +1,188 @@
+use super::Api;
+use crate::error::Error;
+use crate::utils::sync;
+use serde::{Deserialize, Serialize};
+
+/// Version of the json intermediate representation (IR) of the api model. The
+/// major version changes when the representation changes in an incompatible
+/// way, the minor version changes when fields are added.
+///
+pub const API_IR_VERSION: &str = "1.1";
+
+/// The api model as a json document, so it can be consumed by generators that
+/// are not written in rust or TypeScript. The document has a `version` and an
+/// `api` member, the api is the `Api` model where every field is in camel case.
+/// Locations are uris with a json pointer, methods are http method names and
+/// status kinds are `default`, a class (`2XX`) or a code (`200`).
+///
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiIr {
+  pub version: String,
+  pub api: sync::Shared<Api>,
+}
+
+impl ApiIr {
+  pub fn new(api: sync::Shared<Api>) -> Self {
+    Self {
+      version: API_IR_VERSION.to_owned(),
+      api,
+    }
+  }
+
+  pub fn to_json(&self) -> Result<String, Error> {
+    serde_json::to_string_pretty(self).map_err(|_error| Error::SerializationError)
+  }
+
+  /// Read the IR, only IR with the same major version can be read
+  pub fn from_json(text: &str) -> Result<Self, Error> {
+    let api_ir: Self = serde_json::from_str(text).map_err(|_error| Error::SerializationError)?;
+    if get_major_version(&api_ir.version) != get_major_version(API_IR_VERSION) {
+      return Err(Error::UnsupportedVersion);
+    }
+    Ok(api_ir)
+  }
+}
+
+fn get_major_version(version: &str) -> &str {
+  version.split('.').next().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::models::{Method, Operation, OperationResult, Path, StatusKind};
+  use std::collections::BTreeMap;
+
+  #[test]
+  fn test_api_ir() {
+    let location = "http://example.com/api.yaml#".parse().unwrap();
+    let operation_location = "http://example.com/api.yaml#/paths/~1pets/get"
+      .parse()
+      .unwrap();
+    let result_location = "http://example.com/api.yaml#/paths/~1pets/get/responses/2XX"
+      .parse()
+      .unwrap();
+    let api = Api {
+      location,
+      paths: vec![sync::Shared::new(Path {
+        id: 1,
+        location: "http://example.com/api.yaml#/paths/~1pets".parse().unwrap(),
+        pattern: "/pets".to_owned(),
+        operations: vec![sync::Shared::new(Operation {
+          location: operation_location,
+          method: Method::Other("LINK".to_owned()),
+          name: "link-pets".to_owned(),
+          summary: None,
+          description: None,
+          deprecated: false,
+          tags: Vec::new(),
+          authentication_requirements: Vec::new(),
+          query_parameters: Vec::new(),
+          header_parameters: Vec::new(),
+          path_parameters: Vec::new(),
+          cookie_parameters: Vec::new(),
+          querystring_parameters: Vec::new(),
+          bodies: Vec::new(),
+          operation_results: vec![sync::Shared::new(OperationResult {
+            location: result_location,
+            description: None,
+            status_kind: StatusKind::Class(2),
+            status_codes: vec![200, 201],
+            header_parameters: Vec::new(),
+            bodies: Vec::new(),
+            extensions: Default::default(),
+          })],
+          extensions: BTreeMap::from_iter([("x-internal".to_owned(), true.into())]),
+        })],
+        extensions: Default::default(),
+      })],
+      authentication: Vec::new(),
+      tags: Vec::new(),
+      self_location: None,
+      extensions: Default::default(),
+    };
+    let api_ir = ApiIr::new(sync::Shared::new(api));
+
+    let text = api_ir.to_json().unwrap();
+    let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(value["version"], API_IR_VERSION);
+    let operation = &value["api"]["paths"][0]["operations"][0];
+    assert_eq!(operation["method"], "LINK");
+    assert_eq!(operation["operationResults"][0]["statusKind"], "2XX");
+    assert_eq!(operation["extensions"]["x-internal"], true);
+    assert_eq!(
+      operation["location"],
+      "http://example.com/api.yaml#/paths/~1pets/get"
+    );
+
+    assert_eq!(ApiIr::from_json(&text).unwrap(), api_ir);
+
+    let text = text.replace(
+      &format!(r#""version": "{}""#, API_IR_VERSION),
+      r#""version": "2.0""#,
+    );
+    assert_eq!(ApiIr::from_json(&text), Err(Error::UnsupportedVersion));
+  }
+
+  #[test]
+  fn test_api_ir_1_0() {
+    // members that were added later are missing
+    let text = r#"{
+      "version": "1.0",
+      "api": {
+        "location": "http://example.com/api.yaml#",
+        "paths": [
+          {
+            "id": 1,
+            "location": "http://example.com/api.yaml#/paths/~1pets",
+            "pattern": "/pets",
+            "operations": [
+              {
+                "location": "http://example.com/api.yaml#/paths/~1pets/post",
+                "method": "post",
+                "name": "add-pet",
+                "summary": null,
+                "description": null,
+                "deprecated": false,
+                "authenticationRequirements": [],
+                "queryParameters": [],
+                "headerParameters": [],
+                "pathParameters": [],
+                "cookieParameters": [],
+                "bodies": [
+                  {
+                    "location": "http://example.com/api.yaml#/paths/~1pets/post/requestBody/content/application~1json",
+                    "contentType": "application/json",
+                    "schemaId": null
+                  }
+                ],
+                "operationResults": []
+              }
+            ]
+          }
+        ],
+        "authentication": [
+          {
+            "location": "http://example.com/api.yaml#/components/securitySchemes/key",
+            "name": "key",
+            "description": null,
+            "type": "apiKey",
+            "parameterName": "key",
+            "in": "header",
+            "scheme": null
+          }
+        ]
+      }
+    }"#;
+
+    let api_ir = ApiIr::from_json(text).unwrap();
+    let api = &api_ir.api;
+    assert!(api.tags.is_empty());
+    assert_eq!(api.self_location, None);
+    let operation = &api.paths[0].operations[0];
+    assert!(operation.tags.is_empty());
+    assert!(operation.querystring_parameters.is_empty());
+    assert_eq!(operation.bodies[0].item_schema_id, None);
+    assert!(api.authentication[0].flows.is_empty());
+  }
+}
@@ -0,0 +1,586 @@
+use super::api_diff::{get_parameter_renames, normalize_pattern, rename_path_parameters};
+use super::{Api, ApiContainer, Authentication, Operation, Path, Tag};
+use crate::utils::sync;
+use crate::utils::NodeLocation;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use wasm_bindgen::prelude::*;
+
+/// The pattern (with the prefix) of a merged path, the first path with that
+/// pattern and the operations of all paths with the pattern.
+///
+type MergedPath = (String, sync::Shared<Path>, Vec<sync::Shared<Operation>>);
+
+/// The urls and scopes of an authentication flow, used to compare flows.
+///
+type AuthenticationFlowKey<'a> = (
+  Option<&'a str>,
+  Option<&'a str>,
+  Option<&'a str>,
+  Option<&'a str>,
+  Vec<&'a str>,
+);
+
+/// An api that is merged with other apis, the patterns of the paths of the
+/// api get the prefix.
+///
+#[derive(Debug, Clone)]
+pub struct ApiMergeSource {
+  pub api: sync::Shared<Api>,
+  pub path_prefix: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[wasm_bindgen]
+pub enum MergeConflictKind {
+  /// Two apis have an operation with the same method on the same path
+  Operation,
+  /// Two operations have the same name
+  OperationName,
+  /// Two apis have different authentication with the same name
+  Authentication,
+}
+
+/// Something two merged apis both define. The location is of the element
+/// that conflicts with the element at the existing location, which was
+/// merged before.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiMergeConflict {
+  pub kind: MergeConflictKind,
+  pub location: NodeLocation,
+  pub existing_location: NodeLocation,
+  pub message: String,
+}
+
+/// Multiple apis merged into one. The apis are merged in order, when an
+/// operation or authentication conflicts with one that is already merged the
+/// first one is kept. Operations with a name that is already used are kept,
+/// the conflict is only reported.
+///
+/// The location and extensions of the merged api are those of the first api,
+/// a merged path has the location and extensions of the first path with that
+/// pattern. The locations of operations and authentication are not changed.
+///
+/// Paths that only differ in the names of their parameters, like
+/// `/pets/{id}` and `/pets/{petId}`, are merged. The path parameters of the
+/// operations of a path are renamed to the names in the pattern of the merged
+/// path.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiMerge {
+  pub api: sync::Shared<Api>,
+  pub conflicts: Vec<ApiMergeConflict>,
+}
+
+impl ApiMerge {
+  /// Merge the apis, returns `None` if there are no apis
+  pub fn new(sources: &[ApiMergeSource]) -> Option<Self> {
+    let first_source = sources.first()?;
+
+    let mut conflicts = Vec::new();
+    // by normalized pattern, so `/pets/{id}` and `/pets/{petId}` are merged
+    let mut paths: BTreeMap<String, MergedPath> = BTreeMap::new();
+    let mut path_order = Vec::new();
+    let mut operation_names: BTreeMap<String, NodeLocation> = BTreeMap::new();
+    let mut authentication: BTreeMap<String, sync::Shared<Authentication>> = BTreeMap::new();
+    let mut authentication_order = Vec::new();
+    let mut tags: BTreeMap<String, sync::Shared<Tag>> = BTreeMap::new();
+    let mut tag_order = Vec::new();
+
+    for source in sources {
+      for path in &source.api.paths {
+        let pattern = join_pattern(&source.path_prefix, &path.pattern);
+        let key = normalize_pattern(&pattern);
+        let (merged_pattern, _, operations) = paths.entry(key.clone()).or_insert_with(|| {
+          path_order.push(key.clone());
+          (pattern.clone(), path.clone(), Vec::new())
+        });
+        let parameter_names = get_parameter_renames(&pattern, merged_pattern);
+
+        for operation in &path.operations {
+          let operation = &rename_path_parameters(operation, &parameter_names);
+          if let Some(existing) = operations
+            .iter()
+            .find(|existing| existing.method == operation.method)
+          {
+            conflicts.push(ApiMergeConflict {
+              kind: MergeConflictKind::Operation,
+              location: operation.location.clone(),
+              existing_location: existing.location.clone(),
+              message: format!(
+                "operation {} {} is already defined",
+                operation.method.to_string(),
+                pattern
+              ),
+            });
+            continue;
+          }
+
+          if let Some(existing_location) = operation_names.get(&operation.name) {
+            conflicts.push(ApiMergeConflict {
+              kind: MergeConflictKind::OperationName,
+              location: operation.location.clone(),
+              existing_location: existing_location.clone(),
+              message: format!("operation name {} is already used", operation.name),
+            });
+          } else {
+            operation_names.insert(operation.name.clone(), operation.location.clone());
+          }
+
+          operations.push(operation.clone());
+        }
+      }
+
+      for source_authentication in &source.api.authentication {
+        match authentication.get(&source_authentication.name) {
+          Some(existing) => {
+            if !is_same_authentication(existing, source_authentication) {
+              conflicts.push(ApiMergeConflict {
+                kind: MergeConflictKind::Authentication,
+                location: source_authentication.location.clone(),
+                existing_location: existing.location.clone(),
+                message: format!(
+                  "authentication {} is already defined differently",
+                  source_authentication.name
+                ),
+              });
+            }
+          }
+          None => {
+            authentication_order.push(source_authentication.name.clone());
+            authentication.insert(
+              source_authentication.name.clone(),
+              source_authentication.clone(),
+            );
+          }
+        }
+      }
+
+      for tag in &source.api.tags {
+        if !tags.contains_key(&tag.name) {
+          tag_order.push(tag.name.clone());
+          tags.insert(tag.name.clone(), tag.clone());
+        }
+      }
+    }
+
+    let paths = path_order
+      .into_iter()
+      .enumerate()
+      .map(|(index, key)| {
+        let (pattern, first_path, operations) = paths.remove(&key).unwrap();
+        // like the readers, paths are numbered from 1
+        sync::Shared::new(Path {
+          id: index + 1,
+          location: first_path.location.clone(),
+          pattern,
+          operations,
+          extensions: first_path.extensions.clone(),
+        })
+      })
+      .collect();
+
+    let api = Api {
+      location: first_source.api.location.clone(),
+      paths,
+      authentication: authentication_order
+        .into_iter()
+        .map(|name| authentication.remove(&name).unwrap())
+        .collect(),
+      tags: tag_order
+        .into_iter()
+        .map(|name| tags.remove(&name).unwrap())
+        .collect(),
+      self_location: None,
+      extensions: first_source.api.extensions.clone(),
+    };
+
+    Some(Self {
+      api: sync::Shared::new(api),
+      conflicts,
+    })
+  }
+
+  pub fn has_conflicts(&self) -> bool {
+    !self.conflicts.is_empty()
+  }
+}
+
+/// The prefix is joined with a single `/`, an empty prefix leaves the pattern
+/// as it is.
+///
+fn join_pattern(prefix: &str, pattern: &str) -> String {
+  let prefix = prefix.trim_end_matches('/');
+  if prefix.is_empty() {
+    return pattern.to_owned();
+  }
+  let prefix = prefix.strip_prefix('/').unwrap_or(prefix);
+  let pattern = pattern.strip_prefix('/').unwrap_or(pattern);
+  if pattern.is_empty() {
+    format!("/{}", prefix)
+  } else {
+    format!("/{}/{}", prefix, pattern)
+  }
+}
+
+fn is_same_authentication(left: &Authentication, right: &Authentication) -> bool {
+  left.r#type == right.r#type
+    && left.scheme == right.scheme
+    && left.r#in == right.r#in
+    && left.parameter_name == right.parameter_name
+    && get_flows(left) == get_flows(right)
+}
+
+/// The flows of the authentication without their locations, by kind. Scopes
+/// are sorted so the order they are defined in does not matter.
+///
+fn get_flows(authentication: &Authentication) -> BTreeMap<&str, AuthenticationFlowKey<'_>> {
+  authentication
+    .flows
+    .iter()
+    .map(|flow| {
+      let mut scopes: Vec<_> = flow.scopes.iter().map(String::as_str).collect();
+      scopes.sort();
+      (
+        flow.kind.as_str(),
+        (
+          flow.authorization_url.as_deref(),
+          flow.device_authorization_url.as_deref(),
+          flow.token_url.as_deref(),
+          flow.refresh_url.as_deref(),
+          scopes,
+        ),
+      )
+    })
+    .collect()
+}
+
+#[derive(Clone)]
+#[wasm_bindgen]
+pub struct ApiMergeContainer(sync::Shared<ApiMerge>);
+
+#[wasm_bindgen]
+impl ApiMergeContainer {
+  #[wasm_bindgen(getter, js_name = "api")]
+  pub fn api(&self) -> ApiContainer {
+    self.0.api.clone().into()
+  }
+
+  #[wasm_bindgen(getter, js_name = "conflicts")]
+  pub fn conflicts(&self) -> Vec<ApiMergeConflictContainer> {
+    self
+      .0
+      .conflicts
+      .iter()
+      .cloned()
+      .map(|model| sync::Shared::new(model).into())
+      .collect()
+  }
+
+  #[wasm_bindgen(getter, js_name = "hasConflicts")]
+  pub fn has_conflicts(&self) -> bool {
+    self.0.has_conflicts()
+  }
+}
+
+impl From<sync::Shared<ApiMerge>> for ApiMergeContainer {
+  fn from(interior: sync::Shared<ApiMerge>) -> Self {
+    Self(interior)
+  }
+}
+
+#[derive(Clone)]
+#[wasm_bindgen]
+pub struct ApiMergeConflictContainer(sync::Shared<ApiMergeConflict>);
+
+#[wasm_bindgen]
+impl ApiMergeConflictContainer {
+  #[wasm_bindgen(getter, js_name = "kind")]
+  pub fn kind(&self) -> MergeConflictKind {
+    self.0.kind
+  }
+
+  #[wasm_bindgen(getter, js_name = "location")]
+  pub fn location(&self) -> String {
+    self.0.location.to_string()
+  }
+
+  #[wasm_bindgen(getter, js_name = "existingLocation")]
+  pub fn existing_location(&self) -> String {
+    self.0.existing_location.to_string()
+  }
+
+  #[wasm_bindgen(getter, js_name = "message")]
+  pub fn message(&self) -> String {
+    self.0.message.clone()
+  }
+}
+
+impl From<sync::Shared<ApiMergeConflict>> for ApiMergeConflictContainer {
+  fn from(interior: sync::Shared<ApiMergeConflict>) -> Self {
+    Self(interior)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::models::{AuthenticationFlow, Method, Parameter};
+
+  fn make_operation(location: &str, method: Method, name: &str) -> sync::Shared<Operation> {
+    sync::Shared::new(Operation {
+      location: location.parse().unwrap(),
+      method,
+      name: name.to_owned(),
+      summary: None,
+      description: None,
+      deprecated: false,
+      tags: Vec::new(),
+      authentication_requirements: Vec::new(),
+      query_parameters: Vec::new(),
+      header_parameters: Vec::new(),
+      path_parameters: Vec::new(),
+      cookie_parameters: Vec::new(),
+      querystring_parameters: Vec::new(),
+      bodies: Vec::new(),
+      operation_results: Vec::new(),
+      extensions: Default::default(),
+    })
+  }
+
+  fn make_authentication(location: &str, r#type: &str) -> sync::Shared<Authentication> {
+    sync::Shared::new(Authentication {
+      location: location.parse().unwrap(),
+      name: "key".to_owned(),
+      description: None,
+      r#type: r#type.to_owned(),
+      parameter_name: None,
+      r#in: None,
+      scheme: None,
+      flows: Vec::new(),
+      extensions: Default::default(),
+    })
+  }
+
+  fn make_api(
+    document: &str,
+    operations: Vec<(&str, sync::Shared<Operation>)>,
+    authentication: &str,
+  ) -> sync::Shared<Api> {
+    sync::Shared::new(Api {
+      location: format!("{}#", document).parse().unwrap(),
+      paths: operations
+        .into_iter()
+        .enumerate()
+        .map(|(id, (pattern, operation))| {
+          sync::Shared::new(Path {
+            id,
+            location: format!("{}#/paths/{}", document, pattern.replace('/', "~1"))
+              .parse()
+              .unwrap(),
+            pattern: pattern.to_owned(),
+            operations: vec![operation],
+            extensions: Default::default(),
+          })
+        })
+        .collect(),
+      authentication: vec![make_authentication(
+        &format!("{}#/components/securitySchemes/key", document),
+        authentication,
+      )],
+      tags: Vec::new(),
+      self_location: None,
+      extensions: Default::default(),
+    })
+  }
+
+  #[test]
+  fn test_api_merge() {
+    let pets = make_api(
+      "http://example.com/pets.yaml",
+      vec![(
+        "/pets/{id}",
+        make_operation(
+          "http://example.com/pets.yaml#/paths/~1pets~1{id}/get",
+          Method::Get,
+          "get",
+        ),
+      )],
+      "apiKey",
+    );
+    let toys = make_api(
+      "http://example.com/toys.yaml",
+      vec![
+        (
+          "/{toyId}",
+          make_operation(
+            "http://example.com/toys.yaml#/paths/~1{toyId}/get",
+            Method::Get,
+            "get",
+          ),
+        ),
+        (
+          "/",
+          make_operation(
+            "http://example.com/toys.yaml#/paths/~1/post",
+            Method::Post,
+            "add-toy",
+          ),
+        ),
+      ],
+      "http",
+    );
+
+    let merge = ApiMerge::new(&[
+      ApiMergeSource {
+        api: pets.clone(),
+        path_prefix: String::new(),
+      },
+      ApiMergeSource {
+        api: toys.clone(),
+        path_prefix: "/toys/".to_owned(),
+      },
+    ])
+    .unwrap();
+    let patterns: Vec<_> = merge
+      .api
+      .paths
+      .iter()
+      .map(|path| (path.id, path.pattern.as_str()))
+      .collect();
+    assert_eq!(
+      patterns,
+      vec![(1, "/pets/{id}"), (2, "/toys/{toyId}"), (3, "/toys")]
+    );
+    assert_eq!(merge.api.location, pets.location);
+    assert_eq!(merge.api.authentication, pets.authentication);
+    let kinds: Vec<_> = merge
+      .conflicts
+      .iter()
+      .map(|conflict| conflict.kind)
+      .collect();
+    assert_eq!(
+      kinds,
+      vec![
+        MergeConflictKind::OperationName,
+        MergeConflictKind::Authentication
+      ]
+    );
+    assert_eq!(
+      merge.conflicts[0].existing_location,
+      pets.paths[0].operations[0].location
+    );
+
+    // the same path and method in both apis
+    let merge = ApiMerge::new(&[
+      ApiMergeSource {
+        api: pets.clone(),
+        path_prefix: String::new(),
+      },
+      ApiMergeSource {
+        api: toys.clone(),
+        path_prefix: "/pets".to_owned(),
+      },
+    ])
+    .unwrap();
+    assert_eq!(merge.api.paths.len(), 2);
+    assert_eq!(merge.api.paths[0].operations.len(), 1);
+    assert_eq!(merge.conflicts[0].kind, MergeConflictKind::Operation);
+    assert_eq!(
+      merge.conflicts[0].location,
+      toys.paths[0].operations[0].location
+    );
+
+    // path parameters are renamed to the names of the merged path
+    let mut update_pet = make_operation(
+      "http://example.com/owners.yaml#/paths/~1pets~1{petId}/put",
+      Method::Put,
+      "update-pet",
+    );
+    sync::Shared::get_mut(&mut update_pet)
+      .unwrap()
+      .path_parameters
+      .push(sync::Shared::new(Parameter {
+        location: "http://example.com/owners.yaml#/paths/~1pets~1{petId}/put/parameters/0"
+          .parse()
+          .unwrap(),
+        name: "petId".to_owned(),
+        required: true,
+        schema_id: None,
+        extensions: Default::default(),
+      }));
+    let owners = make_api(
+      "http://example.com/owners.yaml",
+      vec![("/pets/{petId}", update_pet)],
+      "apiKey",
+    );
+    let merge = ApiMerge::new(&[
+      ApiMergeSource {
+        api: pets.clone(),
+        path_prefix: String::new(),
+      },
+      ApiMergeSource {
+        api: owners.clone(),
+        path_prefix: String::new(),
+      },
+    ])
+    .unwrap();
+    assert!(!merge.has_conflicts());
+    assert_eq!(merge.api.paths.len(), 1);
+    assert_eq!(merge.api.paths[0].id, 1);
+    assert_eq!(merge.api.paths[0].pattern, "/pets/{id}");
+    let operation = &merge.api.paths[0].operations[1];
+    assert_eq!(operation.name, "update-pet");
+    assert_eq!(operation.path_parameters[0].name, "id");
+    assert_eq!(
+      operation.path_parameters[0].location,
+      owners.paths[0].operations[0].path_parameters[0].location
+    );
+
+    // oauth2 authentication with the same name but different scopes
+    let make_oauth2_api = |document: &str, scopes: Vec<&str>| {
+      let mut api = make_api(document, Vec::new(), "oauth2");
+      let api_mut = sync::Shared::get_mut(&mut api).unwrap();
+      let authentication = sync::Shared::get_mut(&mut api_mut.authentication[0]).unwrap();
+      authentication
+        .flows
+        .push(sync::Shared::new(AuthenticationFlow {
+          location: format!(
+            "{}#/components/securitySchemes/key/flows/implicit",
+            document
+          )
+          .parse()
+          .unwrap(),
+          kind: "implicit".to_owned(),
+          authorization_url: Some("https://example.com/authorize".to_owned()),
+          device_authorization_url: None,
+          token_url: None,
+          refresh_url: None,
+          scopes: scopes.into_iter().map(str::to_owned).collect(),
+        }));
+      api
+    };
+    let read = make_oauth2_api("http://example.com/read.yaml", vec!["read", "write"]);
+    let same = make_oauth2_api("http://example.com/same.yaml", vec!["write", "read"]);
+    let write = make_oauth2_api("http://example.com/write.yaml", vec!["write"]);
+    let sources: Vec<_> = [read, same, write]
+      .into_iter()
+      .map(|api| ApiMergeSource {
+        api,
+        path_prefix: String::new(),
+      })
+      .collect();
+    let merge = ApiMerge::new(&sources).unwrap();
+    assert_eq!(merge.conflicts.len(), 1);
+    assert_eq!(merge.conflicts[0].kind, MergeConflictKind::Authentication);
+    assert_eq!(
+      merge.conflicts[0].location,
+      sources[2].api.authentication[0].location
+    );
+
+    assert_eq!(ApiMerge::new(&[]), None);
+  }
+}
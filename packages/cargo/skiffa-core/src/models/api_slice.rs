@@ -0,0 +1,286 @@
+use super::{Api, ApiContainer, Operation, Path, SchemaSource};
+use crate::utils::sync;
+use crate::utils::{collect_references, NodeLocation};
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use wasm_bindgen::prelude::*;
+
+/// Which operations to keep in a slice of an api. An operation is kept when
+/// it matches every include criterion that is set (at least one of the tags,
+/// path globs or names in it) and none of the exclude criteria.
+///
+/// Path globs match the pattern of a path, a `*` matches anything but a `/`
+/// and a `**` matches anything.
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ApiFilter {
+  pub include_tags: Vec<String>,
+  pub exclude_tags: Vec<String>,
+  pub include_paths: Vec<String>,
+  pub exclude_paths: Vec<String>,
+  pub include_operation_names: Vec<String>,
+  pub exclude_operation_names: Vec<String>,
+  /// Exclude operations (or paths) that have `x-internal: true`
+  pub exclude_internal: bool,
+  pub exclude_deprecated: bool,
+}
+
+/// A reduced api with only the operations that pass a filter, and the
+/// retrieval locations of the schemas those operations use, directly or via
+/// references. Paths without operations are left out, like tags and
+/// authentication that none of the operations use. Paths keep their id.
+///
+#[derive(Debug, PartialEq, Eq)]
+pub struct ApiSlice {
+  pub api: sync::Shared<Api>,
+  pub schema_locations: Vec<NodeLocation>,
+}
+
+impl ApiSlice {
+  pub fn new(api: &Api, filter: &ApiFilter, source: &dyn SchemaSource) -> Self {
+    let paths: Vec<_> = api
+      .paths
+      .iter()
+      .filter_map(|path| {
+        let operations: Vec<_> = path
+          .operations
+          .iter()
+          .filter(|operation| is_included(filter, path, operation))
+          .cloned()
+          .collect();
+        if operations.is_empty() {
+          return None;
+        }
+        Some(sync::Shared::new(Path {
+          id: path.id,
+          location: path.location.clone(),
+          pattern: path.pattern.clone(),
+          operations,
+          extensions: path.extensions.clone(),
+        }))
+      })
+      .collect();
+
+    let operations: Vec<_> = paths
+      .iter()
+      .flat_map(|path| path.operations.iter())
+      .collect();
+
+    let mut tag_names: BTreeSet<_> = operations
+      .iter()
+      .flat_map(|operation| operation.tags.iter().cloned())
+      .collect();
+    // keep the parents of tags too
+    let mut queue: VecDeque<_> = tag_names.iter().cloned().collect();
+    while let Some(tag_name) = queue.pop_front() {
+      let parent = api
+        .tags
+        .iter()
+        .find(|tag| tag.name == tag_name)
+        .and_then(|tag| tag.parent.clone());
+      if let Some(parent) = parent {
+        if tag_names.insert(parent.clone()) {
+          queue.push_back(parent);
+        }
+      }
+    }
+
+    let authentication_names: BTreeSet<_> = operations
+      .iter()
+      .flat_map(|operation| operation.authentication_requirements.iter())
+      .flat_map(|group| group.requirements.iter())
+      .map(|requirement| requirement.authentication_name.clone())
+      .collect();
+
+    let schema_ids = operations.iter().flat_map(|operation| {
+      let parameters = operation
+        .query_parameters
+        .iter()
+        .chain(&operation.header_parameters)
+        .chain(&operation.path_parameters)
+        .chain(&operation.cookie_parameters)
+        .chain(&operation.querystring_parameters)
+        .chain(
+          operation
+            .operation_results
+            .iter()
+            .flat_map(|operation_result| operation_result.header_parameters.iter()),
+        )
+        .map(|parameter| &parameter.schema_id);
+      let bodies = operation
+        .bodies
+        .iter()
+        .chain(
+          operation
+            .operation_results
+            .iter()
+            .flat_map(|operation_result| operation_result.bodies.iter()),
+        )
+        .flat_map(|body| [&body.schema_id, &body.item_schema_id]);
+      parameters.chain(bodies).flatten()
+    });
+    let schema_locations = collect_schema_locations(schema_ids, source);
+
+    let api = Api {
+      location: api.location.clone(),
+      paths,
+      authentication: api
+        .authentication
+        .iter()
+        .filter(|authentication| authentication_names.contains(&authentication.name))
+        .cloned()
+        .collect(),
+      tags: api
+        .tags
+        .iter()
+        .filter(|tag| tag_names.contains(&tag.name))
+        .cloned()
+        .collect(),
+      self_location: api.self_location.clone(),
+      extensions: api.extensions.clone(),
+    };
+
+    Self {
+      api: sync::Shared::new(api),
+      schema_locations,
+    }
+  }
+}
+
+fn is_included(filter: &ApiFilter, path: &Path, operation: &Operation) -> bool {
+  let has_tag = |tags: &[String]| operation.tags.iter().any(|tag| tags.contains(tag));
+  let matches_path = |globs: &[String]| {
+    globs
+      .iter()
+      .any(|glob| matches_glob(glob.as_bytes(), path.pattern.as_bytes()))
+  };
+  let has_name = |names: &[String]| names.contains(&operation.name);
+
+  if !filter.include_tags.is_empty() && !has_tag(&filter.include_tags) {
+    return false;
+  }
+  if !filter.include_paths.is_empty() && !matches_path(&filter.include_paths) {
+    return false;
+  }
+  if !filter.include_operation_names.is_empty() && !has_name(&filter.include_operation_names) {
+    return false;
+  }
+  if has_tag(&filter.exclude_tags)
+    || matches_path(&filter.exclude_paths)
+    || has_name(&filter.exclude_operation_names)
+  {
+    return false;
+  }
+  if filter.exclude_deprecated && operation.deprecated {
+    return false;
+  }
+  if filter.exclude_internal
+    && (is_internal(&path.extensions) || is_internal(&operation.extensions))
+  {
+    return false;
+  }
+  true
+}
+
+fn is_internal(extensions: &BTreeMap<String, serde_json::Value>) -> bool {
+  extensions
+    .get("x-internal")
+    .and_then(|value| value.as_bool())
+    .unwrap_or_default()
+}
+
+/// A `*` matches anything but a `/`, a `**` matches anything
+fn matches_glob(glob: &[u8], value: &[u8]) -> bool {
+  match glob {
+    [] => value.is_empty(),
+    [b'*', b'*', rest @ ..] => (0..=value.len()).any(|index| matches_glob(rest, &value[index..])),
+    [b'*', rest @ ..] => {
+      let segment_length = value.iter().position(|c| *c == b'/').unwrap_or(value.len());
+      (0..=segment_length).any(|index| matches_glob(rest, &value[index..]))
+    }
+    [c, rest @ ..] => value.first() == Some(c) && matches_glob(rest, &value[1..]),
+  }
+}
+
+/// The retrieval locations of the schemas and of every node they reference,
+/// transitively.
+///
+fn collect_schema_locations<'a>(
+  schema_ids: impl Iterator<Item = &'a NodeLocation>,
+  source: &dyn SchemaSource,
+) -> Vec<NodeLocation> {
+  let mut visited = BTreeSet::new();
+  let mut queue: VecDeque<_> = schema_ids
+    .map(|schema_id| {
+      source
+        .get_retrieval_location(schema_id)
+        .unwrap_or_else(|| schema_id.clone())
+    })
+    .collect();
+
+  while let Some(location) = queue.pop_front() {
+    if !visited.insert(location.clone()) {
+      continue;
+    }
+    let Some(node) = source.get_node(&location) else {
+      continue;
+    };
+    let mut references = Vec::new();
+    collect_references(&location, &node, &mut references);
+    for (reference_location, reference) in references {
+      let Ok(reference) = reference.parse() else {
+        continue;
+      };
+      queue.push_back(source.resolve_reference(&reference_location, &reference));
+    }
+  }
+
+  visited.into_iter().collect()
+}
+
+#[derive(Clone)]
+#[wasm_bindgen]
+pub struct ApiSliceContainer(sync::Shared<ApiSlice>);
+
+#[wasm_bindgen]
+impl ApiSliceContainer {
+  #[wasm_bindgen(getter, js_name = "api")]
+  pub fn api(&self) -> ApiContainer {
+    self.0.api.clone().into()
+  }
+
+  #[wasm_bindgen(getter, js_name = "schemaLocations")]
+  pub fn schema_locations(&self) -> Vec<String> {
+    self
+      .0
+      .schema_locations
+      .iter()
+      .map(|location| location.to_string())
+      .collect()
+  }
+}
+
+impl From<sync::Shared<ApiSlice>> for ApiSliceContainer {
+  fn from(interior: sync::Shared<ApiSlice>) -> Self {
+    Self(interior)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_matches_glob() {
+    let matches = |glob: &str, value: &str| matches_glob(glob.as_bytes(), value.as_bytes());
+    assert!(matches("/pets", "/pets"));
+    assert!(!matches("/pets", "/pets/{id}"));
+    assert!(matches("/pets/*", "/pets/{id}"));
+    assert!(!matches("/pets/*", "/pets/{id}/toys"));
+    assert!(matches("/pets/**", "/pets/{id}/toys"));
+    assert!(matches("/**/toys", "/pets/{id}/toys"));
+    assert!(matches("/*s", "/pets"));
+    assert!(!matches("/*s", "/toy"));
+  }
+}
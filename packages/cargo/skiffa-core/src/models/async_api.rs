@@ -0,0 +1,52 @@
+use super::{Channel, ChannelContainer, ChannelOperation, ChannelOperationContainer};
+use crate::utils::sync;
+use crate::utils::NodeLocation;
+use wasm_bindgen::prelude::*;
+
+/// The model of an event driven (AsyncAPI) interface
+#[derive(Debug, PartialEq, Eq)]
+pub struct AsyncApi {
+  pub location: NodeLocation,
+  pub channels: Vec<sync::Shared<Channel>>,
+  pub operations: Vec<sync::Shared<ChannelOperation>>,
+}
+
+#[derive(Clone)]
+#[wasm_bindgen]
+pub struct AsyncApiContainer(sync::Shared<AsyncApi>);
+
+#[wasm_bindgen]
+impl AsyncApiContainer {
+  #[wasm_bindgen(getter, js_name = "location")]
+  pub fn location(&self) -> String {
+    self.0.location.to_string()
+  }
+
+  #[wasm_bindgen(getter, js_name = "channels")]
+  pub fn channels(&self) -> Vec<ChannelContainer> {
+    self
+      .0
+      .channels
+      .iter()
+      .cloned()
+      .map(|model| model.into())
+      .collect()
+  }
+
+  #[wasm_bindgen(getter, js_name = "operations")]
+  pub fn operations(&self) -> Vec<ChannelOperationContainer> {
+    self
+      .0
+      .operations
+      .iter()
+      .cloned()
+      .map(|model| model.into())
+      .collect()
+  }
+}
+
+impl From<sync::Shared<AsyncApi>> for AsyncApiContainer {
+  fn from(interior: sync::Shared<AsyncApi>) -> Self {
+    Self(interior)
+  }
+}
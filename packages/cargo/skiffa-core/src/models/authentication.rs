@@ -1,8 +1,14 @@
-use std::rc;
+use super::{AuthenticationFlow, AuthenticationFlowContainer};
+use crate::utils::extensions_to_js_value;
+use crate::utils::sync;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use wasm_bindgen::prelude::*;
 
 use crate::utils::NodeLocation;
 
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Authentication {
   pub location: NodeLocation,
   pub name: String,
@@ -11,11 +17,16 @@ pub struct Authentication {
   pub parameter_name: Option<String>,
   pub r#in: Option<String>,
   pub scheme: Option<String>,
+  #[serde(default)]
+  pub flows: Vec<sync::Shared<AuthenticationFlow>>,
+  /// Specification extensions (`x-` members), by name
+  #[serde(default)]
+  pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
 #[derive(Clone)]
 #[wasm_bindgen]
-pub struct AuthenticationContainer(rc::Rc<Authentication>);
+pub struct AuthenticationContainer(sync::Shared<Authentication>);
 
 #[wasm_bindgen]
 impl AuthenticationContainer {
@@ -53,10 +64,26 @@ impl AuthenticationContainer {
   pub fn scheme(&self) -> Option<String> {
     self.0.scheme.clone()
   }
+
+  #[wasm_bindgen(getter, js_name = "flows")]
+  pub fn flows(&self) -> Vec<AuthenticationFlowContainer> {
+    self
+      .0
+      .flows
+      .iter()
+      .cloned()
+      .map(|model| model.into())
+      .collect()
+  }
+
+  #[wasm_bindgen(getter, js_name = "extensions")]
+  pub fn extensions(&self) -> JsValue {
+    extensions_to_js_value(&self.0.extensions)
+  }
 }
 
-impl From<rc::Rc<Authentication>> for AuthenticationContainer {
-  fn from(interior: rc::Rc<Authentication>) -> Self {
+impl From<sync::Shared<Authentication>> for AuthenticationContainer {
+  fn from(interior: sync::Shared<Authentication>) -> Self {
     Self(interior)
   }
 }
@@ -0,0 +1,68 @@
+use crate::utils::sync;
+use crate::utils::NodeLocation;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+/// An oauth2 flow, the kind is the name of the flow like `authorizationCode`
+/// or `deviceAuthorization`.
+///
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticationFlow {
+  pub location: NodeLocation,
+  pub kind: String,
+  pub authorization_url: Option<String>,
+  #[serde(default)]
+  pub device_authorization_url: Option<String>,
+  pub token_url: Option<String>,
+  pub refresh_url: Option<String>,
+  pub scopes: Vec<String>,
+}
+
+#[derive(Clone)]
+#[wasm_bindgen]
+pub struct AuthenticationFlowContainer(sync::Shared<AuthenticationFlow>);
+
+#[wasm_bindgen]
+impl AuthenticationFlowContainer {
+  #[wasm_bindgen(getter, js_name = "location")]
+  pub fn location(&self) -> String {
+    self.0.location.to_string()
+  }
+
+  #[wasm_bindgen(getter, js_name = "kind")]
+  pub fn kind(&self) -> String {
+    self.0.kind.clone()
+  }
+
+  #[wasm_bindgen(getter, js_name = "authorizationUrl")]
+  pub fn authorization_url(&self) -> Option<String> {
+    self.0.authorization_url.clone()
+  }
+
+  #[wasm_bindgen(getter, js_name = "deviceAuthorizationUrl")]
+  pub fn device_authorization_url(&self) -> Option<String> {
+    self.0.device_authorization_url.clone()
+  }
+
+  #[wasm_bindgen(getter, js_name = "tokenUrl")]
+  pub fn token_url(&self) -> Option<String> {
+    self.0.token_url.clone()
+  }
+
+  #[wasm_bindgen(getter, js_name = "refreshUrl")]
+  pub fn refresh_url(&self) -> Option<String> {
+    self.0.refresh_url.clone()
+  }
+
+  #[wasm_bindgen(getter, js_name = "scopes")]
+  pub fn scopes(&self) -> Vec<String> {
+    self.0.scopes.clone()
+  }
+}
+
+impl From<sync::Shared<AuthenticationFlow>> for AuthenticationFlowContainer {
+  fn from(interior: sync::Shared<AuthenticationFlow>) -> Self {
+    Self(interior)
+  }
+}
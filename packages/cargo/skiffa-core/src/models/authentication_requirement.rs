@@ -1,6 +1,9 @@
-use std::rc;
+use crate::utils::sync;
+use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct AuthenticationRequirement {
   pub authentication_name: String,
   pub scopes: Vec<String>,
@@ -8,7 +11,7 @@ pub struct AuthenticationRequirement {
 
 #[derive(Clone)]
 #[wasm_bindgen]
-pub struct AuthenticationRequirementContainer(rc::Rc<AuthenticationRequirement>);
+pub struct AuthenticationRequirementContainer(sync::Shared<AuthenticationRequirement>);
 
 #[wasm_bindgen]
 impl AuthenticationRequirementContainer {
@@ -23,8 +26,8 @@ impl AuthenticationRequirementContainer {
   }
 }
 
-impl From<rc::Rc<AuthenticationRequirement>> for AuthenticationRequirementContainer {
-  fn from(interior: rc::Rc<AuthenticationRequirement>) -> Self {
+impl From<sync::Shared<AuthenticationRequirement>> for AuthenticationRequirementContainer {
+  fn from(interior: sync::Shared<AuthenticationRequirement>) -> Self {
     Self(interior)
   }
 }
@@ -1,14 +1,17 @@
 use super::{AuthenticationRequirement, AuthenticationRequirementContainer};
-use std::rc;
+use crate::utils::sync;
+use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct AuthenticationRequirementGroup {
-  pub requirements: Vec<rc::Rc<AuthenticationRequirement>>,
+  pub requirements: Vec<sync::Shared<AuthenticationRequirement>>,
 }
 
 #[derive(Clone)]
 #[wasm_bindgen]
-pub struct AuthenticationRequirementGroupContainer(rc::Rc<AuthenticationRequirementGroup>);
+pub struct AuthenticationRequirementGroupContainer(sync::Shared<AuthenticationRequirementGroup>);
 
 #[wasm_bindgen]
 impl AuthenticationRequirementGroupContainer {
@@ -24,8 +27,10 @@ impl AuthenticationRequirementGroupContainer {
   }
 }
 
-impl From<rc::Rc<AuthenticationRequirementGroup>> for AuthenticationRequirementGroupContainer {
-  fn from(interior: rc::Rc<AuthenticationRequirementGroup>) -> Self {
+impl From<sync::Shared<AuthenticationRequirementGroup>>
+  for AuthenticationRequirementGroupContainer
+{
+  fn from(interior: sync::Shared<AuthenticationRequirementGroup>) -> Self {
     Self(interior)
   }
 }
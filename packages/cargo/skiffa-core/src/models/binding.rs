@@ -0,0 +1,35 @@
+use crate::utils::sync;
+use crate::utils::NodeLocation;
+use wasm_bindgen::prelude::*;
+
+/// A protocol specific binding (like `kafka` or `ws`). The content of the
+/// binding is not modelled, it can be read from the location.
+///
+#[derive(Debug, PartialEq, Eq)]
+pub struct Binding {
+  pub location: NodeLocation,
+  pub protocol: String,
+}
+
+#[derive(Clone)]
+#[wasm_bindgen]
+pub struct BindingContainer(sync::Shared<Binding>);
+
+#[wasm_bindgen]
+impl BindingContainer {
+  #[wasm_bindgen(getter, js_name = "location")]
+  pub fn location(&self) -> String {
+    self.0.location.to_string()
+  }
+
+  #[wasm_bindgen(getter, js_name = "protocol")]
+  pub fn protocol(&self) -> String {
+    self.0.protocol.clone()
+  }
+}
+
+impl From<sync::Shared<Binding>> for BindingContainer {
+  fn from(interior: sync::Shared<Binding>) -> Self {
+    Self(interior)
+  }
+}
@@ -1,16 +1,68 @@
+use super::{negotiate_media_type, BodyKind, MediaType};
+use crate::utils::extensions_to_js_value;
+use crate::utils::sync;
 use crate::utils::NodeLocation;
-use std::rc;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use wasm_bindgen::prelude::*;
 
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Body {
   pub location: NodeLocation,
   pub content_type: String,
   pub schema_id: Option<NodeLocation>,
+  #[serde(default)]
+  /// Schema of every item in a streaming (sequential) media type
+  pub item_schema_id: Option<NodeLocation>,
+  /// Specification extensions (`x-` members), by name
+  #[serde(default)]
+  pub extensions: BTreeMap<String, serde_json::Value>,
+}
+
+impl Body {
+  /// The parsed content type, if it is a valid media type
+  pub fn media_type(&self) -> Option<MediaType> {
+    self.content_type.parse().ok()
+  }
+
+  pub fn kind(&self) -> BodyKind {
+    self
+      .media_type()
+      .map(|media_type| BodyKind::from(&media_type))
+      .unwrap_or(BodyKind::Single)
+  }
+
+  /// The schema of every item of a streaming body. Before `itemSchema` was a
+  /// thing, the schema of a streaming body described the items, so we use
+  /// that if there is no item schema.
+  ///
+  pub fn get_item_schema_id(&self) -> Option<&NodeLocation> {
+    self.item_schema_id.as_ref().or_else(|| {
+      if self.kind().is_streaming() {
+        self.schema_id.as_ref()
+      } else {
+        None
+      }
+    })
+  }
+}
+
+/// The body with the content type that best matches the `Accept` header,
+/// bodies with an invalid content type are never chosen.
+///
+pub fn negotiate_bodies(accept: &str, bodies: &[sync::Shared<Body>]) -> Option<sync::Shared<Body>> {
+  let (bodies, media_types): (Vec<_>, Vec<_>) = bodies
+    .iter()
+    .filter_map(|body| Some((body, body.media_type()?)))
+    .unzip();
+  let index = negotiate_media_type(accept, &media_types)?;
+  Some(bodies[index].clone())
 }
 
 #[derive(Clone)]
 #[wasm_bindgen]
-pub struct BodyContainer(rc::Rc<Body>);
+pub struct BodyContainer(sync::Shared<Body>);
 
 #[wasm_bindgen]
 impl BodyContainer {
@@ -28,10 +80,45 @@ impl BodyContainer {
   pub fn schema_id(&self) -> Option<String> {
     Some(self.0.schema_id.as_ref()?.to_string())
   }
+
+  #[wasm_bindgen(getter, js_name = "itemSchemaId")]
+  pub fn item_schema_id(&self) -> Option<String> {
+    Some(self.0.get_item_schema_id()?.to_string())
+  }
+
+  #[wasm_bindgen(getter, js_name = "kind")]
+  pub fn kind(&self) -> BodyKind {
+    self.0.kind()
+  }
+
+  #[wasm_bindgen(getter, js_name = "isStreaming")]
+  pub fn is_streaming(&self) -> bool {
+    self.0.kind().is_streaming()
+  }
+
+  #[wasm_bindgen(getter, js_name = "mediaTypeEssence")]
+  pub fn media_type_essence(&self) -> Option<String> {
+    Some(self.0.media_type()?.essence())
+  }
+
+  #[wasm_bindgen(getter, js_name = "charset")]
+  pub fn charset(&self) -> Option<String> {
+    self.0.media_type()?.charset().map(Into::into)
+  }
+
+  #[wasm_bindgen(getter, js_name = "boundary")]
+  pub fn boundary(&self) -> Option<String> {
+    self.0.media_type()?.boundary().map(Into::into)
+  }
+
+  #[wasm_bindgen(getter, js_name = "extensions")]
+  pub fn extensions(&self) -> JsValue {
+    extensions_to_js_value(&self.0.extensions)
+  }
 }
 
-impl From<rc::Rc<Body>> for BodyContainer {
-  fn from(interior: rc::Rc<Body>) -> Self {
+impl From<sync::Shared<Body>> for BodyContainer {
+  fn from(interior: sync::Shared<Body>) -> Self {
     Self(interior)
   }
 }
@@ -0,0 +1,77 @@
+use super::MediaType;
+use wasm_bindgen::prelude::*;
+
+/// Media types of which the body is a stream of json documents, one per line.
+const LINE_DELIMITED_ESSENCES: [&str; 6] = [
+  "application/x-ndjson",
+  "application/ndjson",
+  "application/jsonl",
+  "application/x-jsonlines",
+  "application/jsonlines",
+  "application/x-ldjson",
+];
+
+/// How the body of a media type is structured, this tells how a body should
+/// be (de)serialized.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[wasm_bindgen]
+pub enum BodyKind {
+  /// A single document, like `application/json`
+  Single,
+  /// Documents separated by newlines, like `application/x-ndjson`
+  LineDelimited,
+  /// Server sent events, `text/event-stream`
+  EventStream,
+  /// Documents prefixed with a record separator, `application/json-seq`
+  JsonSequence,
+  /// Parts separated by a boundary, like `multipart/form-data`
+  Multipart,
+}
+
+impl BodyKind {
+  pub fn is_streaming(&self) -> bool {
+    matches!(
+      self,
+      Self::LineDelimited | Self::EventStream | Self::JsonSequence
+    )
+  }
+}
+
+impl From<&MediaType> for BodyKind {
+  fn from(media_type: &MediaType) -> Self {
+    let essence = media_type.essence();
+    if media_type.r#type == "multipart" {
+      Self::Multipart
+    } else if essence == "text/event-stream" {
+      Self::EventStream
+    } else if essence == "application/json-seq" {
+      Self::JsonSequence
+    } else if LINE_DELIMITED_ESSENCES.contains(&essence.as_str()) {
+      Self::LineDelimited
+    } else {
+      Self::Single
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_from_media_type() {
+    do_test("application/json", BodyKind::Single);
+    do_test("application/problem+json", BodyKind::Single);
+    do_test("application/x-ndjson", BodyKind::LineDelimited);
+    do_test("application/jsonl; charset=utf-8", BodyKind::LineDelimited);
+    do_test("text/event-stream", BodyKind::EventStream);
+    do_test("application/json-seq", BodyKind::JsonSequence);
+    do_test("multipart/mixed; boundary=abc", BodyKind::Multipart);
+
+    fn do_test(media_type: &str, expected: BodyKind) {
+      let media_type: MediaType = media_type.parse().unwrap();
+      assert_eq!(BodyKind::from(&media_type), expected);
+    }
+  }
+}
@@ -0,0 +1,70 @@
+use super::{Binding, BindingContainer, Message, MessageContainer};
+use crate::utils::sync;
+use crate::utils::NodeLocation;
+use wasm_bindgen::prelude::*;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Channel {
+  pub location: NodeLocation,
+  pub name: String,
+  /// The address (topic, queue, path) of the channel, unknown if it is not set
+  pub address: Option<String>,
+  pub description: Option<String>,
+  pub messages: Vec<sync::Shared<Message>>,
+  pub bindings: Vec<sync::Shared<Binding>>,
+}
+
+#[derive(Clone)]
+#[wasm_bindgen]
+pub struct ChannelContainer(sync::Shared<Channel>);
+
+#[wasm_bindgen]
+impl ChannelContainer {
+  #[wasm_bindgen(getter, js_name = "location")]
+  pub fn location(&self) -> String {
+    self.0.location.to_string()
+  }
+
+  #[wasm_bindgen(getter, js_name = "name")]
+  pub fn name(&self) -> String {
+    self.0.name.clone()
+  }
+
+  #[wasm_bindgen(getter, js_name = "address")]
+  pub fn address(&self) -> Option<String> {
+    self.0.address.clone()
+  }
+
+  #[wasm_bindgen(getter, js_name = "description")]
+  pub fn description(&self) -> Option<String> {
+    self.0.description.clone()
+  }
+
+  #[wasm_bindgen(getter, js_name = "messages")]
+  pub fn messages(&self) -> Vec<MessageContainer> {
+    self
+      .0
+      .messages
+      .iter()
+      .cloned()
+      .map(|model| model.into())
+      .collect()
+  }
+
+  #[wasm_bindgen(getter, js_name = "bindings")]
+  pub fn bindings(&self) -> Vec<BindingContainer> {
+    self
+      .0
+      .bindings
+      .iter()
+      .cloned()
+      .map(|model| model.into())
+      .collect()
+  }
+}
+
+impl From<sync::Shared<Channel>> for ChannelContainer {
+  fn from(interior: sync::Shared<Channel>) -> Self {
+    Self(interior)
+  }
+}
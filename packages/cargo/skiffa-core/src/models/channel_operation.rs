@@ -0,0 +1,89 @@
+use super::{Binding, BindingContainer, Message, MessageContainer};
+use crate::utils::sync;
+use crate::utils::NodeLocation;
+use wasm_bindgen::prelude::*;
+
+/// What the application does with the messages of an operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[wasm_bindgen]
+pub enum OperationAction {
+  Send,
+  Receive,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ChannelOperation {
+  pub location: NodeLocation,
+  pub name: Option<String>,
+  pub action: OperationAction,
+  pub channel_name: String,
+  pub summary: Option<String>,
+  pub description: Option<String>,
+  pub messages: Vec<sync::Shared<Message>>,
+  pub bindings: Vec<sync::Shared<Binding>>,
+}
+
+#[derive(Clone)]
+#[wasm_bindgen]
+pub struct ChannelOperationContainer(sync::Shared<ChannelOperation>);
+
+#[wasm_bindgen]
+impl ChannelOperationContainer {
+  #[wasm_bindgen(getter, js_name = "location")]
+  pub fn location(&self) -> String {
+    self.0.location.to_string()
+  }
+
+  #[wasm_bindgen(getter, js_name = "name")]
+  pub fn name(&self) -> Option<String> {
+    self.0.name.clone()
+  }
+
+  #[wasm_bindgen(getter, js_name = "action")]
+  pub fn action(&self) -> OperationAction {
+    self.0.action
+  }
+
+  #[wasm_bindgen(getter, js_name = "channelName")]
+  pub fn channel_name(&self) -> String {
+    self.0.channel_name.clone()
+  }
+
+  #[wasm_bindgen(getter, js_name = "summary")]
+  pub fn summary(&self) -> Option<String> {
+    self.0.summary.clone()
+  }
+
+  #[wasm_bindgen(getter, js_name = "description")]
+  pub fn description(&self) -> Option<String> {
+    self.0.description.clone()
+  }
+
+  #[wasm_bindgen(getter, js_name = "messages")]
+  pub fn messages(&self) -> Vec<MessageContainer> {
+    self
+      .0
+      .messages
+      .iter()
+      .cloned()
+      .map(|model| model.into())
+      .collect()
+  }
+
+  #[wasm_bindgen(getter, js_name = "bindings")]
+  pub fn bindings(&self) -> Vec<BindingContainer> {
+    self
+      .0
+      .bindings
+      .iter()
+      .cloned()
+      .map(|model| model.into())
+      .collect()
+  }
+}
+
+impl From<sync::Shared<ChannelOperation>> for ChannelOperationContainer {
+  fn from(interior: sync::Shared<ChannelOperation>) -> Self {
+    Self(interior)
+  }
+}
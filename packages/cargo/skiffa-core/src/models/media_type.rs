@@ -0,0 +1,490 @@
+use crate::utils::sync;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::str::FromStr;
+use wasm_bindgen::prelude::*;
+
+/// A parsed media type (like `application/json; charset=utf-8`). Type, subtype
+/// and parameter names are case insensitive and stored in lower case,
+/// parameter values are stored as is, without quotes.
+///
+/// A media type may also be a range (like `application/*` or `*/*`), the
+/// subtype may be a structured suffix range (like `*+json`).
+///
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MediaType {
+  pub r#type: String,
+  pub subtype: String,
+  pub parameters: BTreeMap<String, String>,
+}
+
+impl MediaType {
+  /// The media type without parameters, like `application/json`
+  pub fn essence(&self) -> String {
+    format!("{}/{}", self.r#type, self.subtype)
+  }
+
+  pub fn parameter(&self, name: &str) -> Option<&str> {
+    self
+      .parameters
+      .get(&name.to_ascii_lowercase())
+      .map(|value| value.as_str())
+  }
+
+  /// The structured syntax suffix, `json` for `application/problem+json`
+  pub fn suffix(&self) -> Option<&str> {
+    let (_, suffix) = self.subtype.rsplit_once('+')?;
+    Some(suffix)
+  }
+
+  pub fn is_wildcard(&self) -> bool {
+    self.r#type == "*" || self.subtype == "*" || self.subtype.starts_with("*+")
+  }
+
+  /// Does the (range) media type overlap with the other (range) media type?
+  /// Wildcards on either side match. Parameters of either side (other than
+  /// `q`) need to be equal if they are in both media types, the charset is
+  /// compared case insensitive.
+  ///
+  pub fn matches(&self, other: &Self) -> bool {
+    if self.r#type != "*" && other.r#type != "*" && self.r#type != other.r#type {
+      return false;
+    }
+
+    if !subtype_matches(&self.subtype, &other.subtype)
+      && !subtype_matches(&other.subtype, &self.subtype)
+    {
+      return false;
+    }
+
+    self
+      .parameters
+      .iter()
+      .filter(|(name, _value)| *name != "q")
+      .all(|(name, value)| match other.parameters.get(name) {
+        None => true,
+        Some(other_value) if name == "charset" => value.eq_ignore_ascii_case(other_value),
+        Some(other_value) => value == other_value,
+      })
+  }
+
+  /// How specific this media type is, a more specific media range takes
+  /// precedence over a less specific one. Concrete types are more specific
+  /// than suffix ranges, these are more specific than subtype ranges. More
+  /// parameters make a media type more specific.
+  ///
+  pub fn specificity(&self) -> (usize, usize, usize) {
+    let type_specificity = if self.r#type == "*" { 0 } else { 1 };
+    let subtype_specificity = if self.subtype == "*" {
+      0
+    } else if self.subtype.starts_with("*+") {
+      1
+    } else {
+      2
+    };
+    let parameter_specificity = self.parameters.keys().filter(|name| *name != "q").count();
+
+    (type_specificity, subtype_specificity, parameter_specificity)
+  }
+
+  pub fn charset(&self) -> Option<&str> {
+    self.parameter("charset")
+  }
+
+  pub fn boundary(&self) -> Option<&str> {
+    self.parameter("boundary")
+  }
+}
+
+impl FromStr for MediaType {
+  type Err = MediaTypeParseError;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    let (essence, mut rest) = value.split_once(';').unwrap_or((value, ""));
+    let (r#type, subtype) = essence.trim().split_once('/').ok_or(MediaTypeParseError)?;
+    if !is_token(r#type) || !is_token(subtype) {
+      Err(MediaTypeParseError)?
+    }
+
+    let mut parameters = BTreeMap::new();
+    loop {
+      rest = rest.trim_start_matches([' ', '\t', ';']);
+      if rest.is_empty() {
+        break;
+      }
+
+      let (name, after_name) = rest.split_once('=').ok_or(MediaTypeParseError)?;
+      let name = name.trim();
+      if !is_token(name) {
+        Err(MediaTypeParseError)?
+      }
+
+      let (value, after_value) = if let Some(quoted) = after_name.strip_prefix('"') {
+        parse_quoted_string(quoted)?
+      } else {
+        let (value, after_value) = after_name.split_once(';').unwrap_or((after_name, ""));
+        (value.trim().to_owned(), after_value)
+      };
+
+      // the first occurrence of a parameter wins
+      parameters.entry(name.to_ascii_lowercase()).or_insert(value);
+      rest = after_value;
+    }
+
+    Ok(Self {
+      r#type: r#type.to_ascii_lowercase(),
+      subtype: subtype.to_ascii_lowercase(),
+      parameters,
+    })
+  }
+}
+
+impl Display for MediaType {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.essence())?;
+    for (name, value) in &self.parameters {
+      if is_token(value) {
+        write!(f, "; {}={}", name, value)?;
+      } else {
+        write!(
+          f,
+          "; {}=\"{}\"",
+          name,
+          value.replace('\\', "\\\\").replace('"', "\\\"")
+        )?;
+      }
+    }
+    Ok(())
+  }
+}
+
+#[derive(Debug)]
+pub struct MediaTypeParseError;
+
+/// A media range from an `Accept` header with it's quality (the `q`
+/// parameter) in thousandths.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AcceptEntry {
+  pub media_type: MediaType,
+  pub quality: u16,
+}
+
+impl AcceptEntry {
+  /// Parse an `Accept` header, invalid entries are ignored. An empty header
+  /// accepts everything.
+  ///
+  pub fn parse_header(accept: &str) -> Vec<Self> {
+    if accept.trim().is_empty() {
+      return vec![Self {
+        media_type: MediaType {
+          r#type: "*".to_owned(),
+          subtype: "*".to_owned(),
+          parameters: Default::default(),
+        },
+        quality: 1000,
+      }];
+    }
+
+    split_header(accept)
+      .into_iter()
+      .filter_map(|value| {
+        let mut media_type: MediaType = value.parse().ok()?;
+        let quality = match media_type.parameters.remove("q") {
+          None => 1000,
+          Some(quality) => parse_quality(&quality)?,
+        };
+        Some(Self {
+          media_type,
+          quality,
+        })
+      })
+      .collect()
+  }
+}
+
+/// Choose the best media type for an `Accept` header. Every media type gets
+/// the quality of the most specific matching media range. The media type with
+/// the highest quality wins, then the one matched by the most specific range,
+/// then the first one. Returns the index of the chosen media type, or nothing
+/// if none of the media types are acceptable.
+///
+pub fn negotiate_media_type(accept: &str, media_types: &[MediaType]) -> Option<usize> {
+  let entries = AcceptEntry::parse_header(accept);
+
+  media_types
+    .iter()
+    .enumerate()
+    .filter_map(|(index, media_type)| {
+      let entry = entries
+        .iter()
+        .filter(|entry| entry.media_type.matches(media_type))
+        .max_by_key(|entry| entry.media_type.specificity())?;
+      if entry.quality == 0 {
+        return None;
+      }
+      Some((index, entry.quality, entry.media_type.specificity()))
+    })
+    .max_by(
+      |(index, quality, specificity), (other_index, other_quality, other_specificity)| {
+        quality
+          .cmp(other_quality)
+          .then(specificity.cmp(other_specificity))
+          // the first one wins
+          .then(other_index.cmp(index))
+      },
+    )
+    .map(|(index, _quality, _specificity)| index)
+}
+
+#[wasm_bindgen(js_name = "negotiateMediaType")]
+pub fn negotiate_media_type_container(accept: &str, media_types: Vec<String>) -> Option<String> {
+  let parsed = media_types
+    .iter()
+    .map(|media_type| media_type.parse())
+    .collect::<Result<Vec<MediaType>, _>>()
+    .ok()?;
+  let index = negotiate_media_type(accept, &parsed)?;
+  media_types.into_iter().nth(index)
+}
+
+#[derive(Clone)]
+#[wasm_bindgen]
+pub struct MediaTypeContainer(sync::Shared<MediaType>);
+
+#[wasm_bindgen]
+impl MediaTypeContainer {
+  #[wasm_bindgen(js_name = "parse")]
+  pub fn parse(value: &str) -> Option<MediaTypeContainer> {
+    let media_type: MediaType = value.parse().ok()?;
+    Some(sync::Shared::new(media_type).into())
+  }
+
+  #[wasm_bindgen(getter, js_name = "type")]
+  pub fn r#type(&self) -> String {
+    self.0.r#type.clone()
+  }
+
+  #[wasm_bindgen(getter, js_name = "subtype")]
+  pub fn subtype(&self) -> String {
+    self.0.subtype.clone()
+  }
+
+  #[wasm_bindgen(getter, js_name = "suffix")]
+  pub fn suffix(&self) -> Option<String> {
+    self.0.suffix().map(Into::into)
+  }
+
+  #[wasm_bindgen(getter, js_name = "essence")]
+  pub fn essence(&self) -> String {
+    self.0.essence()
+  }
+
+  #[wasm_bindgen(getter, js_name = "isWildcard")]
+  pub fn is_wildcard(&self) -> bool {
+    self.0.is_wildcard()
+  }
+
+  #[wasm_bindgen(getter, js_name = "parameterNames")]
+  pub fn parameter_names(&self) -> Vec<String> {
+    self.0.parameters.keys().cloned().collect()
+  }
+
+  #[wasm_bindgen(js_name = "parameter")]
+  pub fn parameter(&self, name: &str) -> Option<String> {
+    self.0.parameter(name).map(Into::into)
+  }
+
+  #[wasm_bindgen(js_name = "matches")]
+  pub fn matches(&self, other: &MediaTypeContainer) -> bool {
+    self.0.matches(&other.0)
+  }
+
+  /// Compare the specificity of two media types, for sorting from least to
+  /// most specific.
+  ///
+  #[wasm_bindgen(js_name = "compareSpecificity")]
+  pub fn compare_specificity(&self, other: &MediaTypeContainer) -> i32 {
+    match self.0.specificity().cmp(&other.0.specificity()) {
+      Ordering::Less => -1,
+      Ordering::Equal => 0,
+      Ordering::Greater => 1,
+    }
+  }
+
+  #[wasm_bindgen(js_name = "toString")]
+  pub fn to_string_js(&self) -> String {
+    self.0.to_string()
+  }
+}
+
+impl From<sync::Shared<MediaType>> for MediaTypeContainer {
+  fn from(interior: sync::Shared<MediaType>) -> Self {
+    Self(interior)
+  }
+}
+
+/// Does the subtype (range) match the other subtype? `*` matches any subtype,
+/// `*+json` matches any subtype with the `json` suffix.
+///
+fn subtype_matches(range: &str, subtype: &str) -> bool {
+  if range == "*" || range == subtype {
+    return true;
+  }
+  let Some(suffix) = range.strip_prefix("*+") else {
+    return false;
+  };
+  subtype
+    .rsplit_once('+')
+    .is_some_and(|(_, subtype_suffix)| subtype_suffix == suffix)
+}
+
+/// Split a header on commas that are not in a quoted string
+fn split_header(value: &str) -> Vec<&str> {
+  let mut result = Vec::new();
+  let mut start = 0;
+  let mut quoted = false;
+  let mut escaped = false;
+  for (index, c) in value.char_indices() {
+    match c {
+      _ if escaped => escaped = false,
+      '\\' if quoted => escaped = true,
+      '"' => quoted = !quoted,
+      ',' if !quoted => {
+        result.push(value[start..index].trim());
+        start = index + 1;
+      }
+      _ => {}
+    }
+  }
+  result.push(value[start..].trim());
+  result.into_iter().filter(|part| !part.is_empty()).collect()
+}
+
+/// Parse a quality value (`0`, `0.5`, `1.000`) into thousandths
+fn parse_quality(value: &str) -> Option<u16> {
+  let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+  if fraction.len() > 3 || !fraction.chars().all(|c| c.is_ascii_digit()) {
+    return None;
+  }
+  let fraction: u16 = format!("{:0<3}", fraction).parse().ok()?;
+  match whole {
+    "0" => Some(fraction),
+    "1" if fraction == 0 => Some(1000),
+    _ => None,
+  }
+}
+
+fn is_token(value: &str) -> bool {
+  !value.is_empty()
+    && value
+      .chars()
+      .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c))
+}
+
+/// Parse a quoted string, starting after the opening quote. Returns the
+/// unescaped value and the text after the closing quote.
+///
+fn parse_quoted_string(value: &str) -> Result<(String, &str), MediaTypeParseError> {
+  let mut result = String::new();
+  let mut chars = value.char_indices();
+  while let Some((index, c)) = chars.next() {
+    match c {
+      '"' => return Ok((result, &value[index + 1..])),
+      '\\' => result.push(chars.next().ok_or(MediaTypeParseError)?.1),
+      c => result.push(c),
+    }
+  }
+  Err(MediaTypeParseError)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse() {
+    let media_type: MediaType = "Application/JSON".parse().unwrap();
+    assert_eq!(media_type.essence(), "application/json");
+    assert!(media_type.parameters.is_empty());
+
+    let media_type: MediaType = "text/plain; Charset=UTF-8".parse().unwrap();
+    assert_eq!(media_type.charset(), Some("UTF-8"));
+
+    let media_type: MediaType = r#"multipart/form-data; boundary="a; \"b\""; charset=utf-8;"#
+      .parse()
+      .unwrap();
+    assert_eq!(media_type.boundary(), Some(r#"a; "b""#));
+    assert_eq!(media_type.charset(), Some("utf-8"));
+    assert_eq!(
+      media_type.to_string(),
+      r#"multipart/form-data; boundary="a; \"b\""; charset=utf-8"#
+    );
+
+    assert!("json".parse::<MediaType>().is_err());
+    assert!("application/".parse::<MediaType>().is_err());
+    assert!("text/plain; charset".parse::<MediaType>().is_err());
+    assert!(r#"text/plain; charset="utf-8"#.parse::<MediaType>().is_err());
+
+    let media_type: MediaType = "application/problem+json".parse().unwrap();
+    assert_eq!(media_type.suffix(), Some("json"));
+  }
+
+  #[test]
+  fn test_matches() {
+    do_test("*/*", "application/json", true);
+    do_test("application/*", "application/json", true);
+    do_test("application/*", "text/plain", false);
+    do_test("application/*+json", "application/problem+json", true);
+    do_test("application/*+json", "application/json", false);
+    do_test("application/json", "application/json; charset=utf-8", true);
+    do_test(
+      "text/plain; charset=UTF-8",
+      "text/plain; charset=utf-8",
+      true,
+    );
+    do_test(
+      "text/plain; charset=utf-8",
+      "text/plain; charset=latin1",
+      false,
+    );
+    // body content types may be ranges too
+    do_test("application/json", "application/*", true);
+
+    fn do_test(range: &str, media_type: &str, expected: bool) {
+      let range: MediaType = range.parse().unwrap();
+      let media_type: MediaType = media_type.parse().unwrap();
+      assert_eq!(range.matches(&media_type), expected);
+    }
+  }
+
+  #[test]
+  fn test_negotiate_media_type() {
+    let media_types = ["application/json", "application/x-ndjson", "text/plain"];
+
+    do_test("", &media_types, Some(0));
+    do_test("*/*", &media_types, Some(0));
+    do_test("application/x-ndjson", &media_types, Some(1));
+    do_test(
+      "application/json;q=0.5, application/x-ndjson",
+      &media_types,
+      Some(1),
+    );
+    do_test("text/*, application/*;q=0.9", &media_types, Some(2));
+    // the most specific range wins, even if it has a lower quality
+    do_test("*/*, application/json;q=0", &media_types, Some(1));
+    do_test("image/png", &media_types, None);
+    do_test("application/problem+json", &["application/*+json"], Some(0));
+    do_test("invalid, text/plain;q=2, text/plain", &media_types, Some(2));
+    do_test(r#"text/plain; a="x,y", image/*"#, &media_types, Some(2));
+
+    fn do_test(accept: &str, media_types: &[&str], expected: Option<usize>) {
+      let media_types: Vec<MediaType> = media_types
+        .iter()
+        .map(|media_type| media_type.parse().unwrap())
+        .collect();
+      assert_eq!(negotiate_media_type(accept, &media_types), expected);
+    }
+  }
+}
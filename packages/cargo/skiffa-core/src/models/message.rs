@@ -0,0 +1,71 @@
+use super::{Binding, BindingContainer};
+use crate::utils::sync;
+use crate::utils::NodeLocation;
+use wasm_bindgen::prelude::*;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Message {
+  pub location: NodeLocation,
+  pub name: Option<String>,
+  pub title: Option<String>,
+  /// The content type of the message, or the default content type of the
+  /// document
+  pub content_type: Option<String>,
+  pub payload_schema_id: Option<NodeLocation>,
+  pub headers_schema_id: Option<NodeLocation>,
+  pub bindings: Vec<sync::Shared<Binding>>,
+}
+
+#[derive(Clone)]
+#[wasm_bindgen]
+pub struct MessageContainer(sync::Shared<Message>);
+
+#[wasm_bindgen]
+impl MessageContainer {
+  #[wasm_bindgen(getter, js_name = "location")]
+  pub fn location(&self) -> String {
+    self.0.location.to_string()
+  }
+
+  #[wasm_bindgen(getter, js_name = "name")]
+  pub fn name(&self) -> Option<String> {
+    self.0.name.clone()
+  }
+
+  #[wasm_bindgen(getter, js_name = "title")]
+  pub fn title(&self) -> Option<String> {
+    self.0.title.clone()
+  }
+
+  #[wasm_bindgen(getter, js_name = "contentType")]
+  pub fn content_type(&self) -> Option<String> {
+    self.0.content_type.clone()
+  }
+
+  #[wasm_bindgen(getter, js_name = "payloadSchemaId")]
+  pub fn payload_schema_id(&self) -> Option<String> {
+    Some(self.0.payload_schema_id.as_ref()?.to_string())
+  }
+
+  #[wasm_bindgen(getter, js_name = "headersSchemaId")]
+  pub fn headers_schema_id(&self) -> Option<String> {
+    Some(self.0.headers_schema_id.as_ref()?.to_string())
+  }
+
+  #[wasm_bindgen(getter, js_name = "bindings")]
+  pub fn bindings(&self) -> Vec<BindingContainer> {
+    self
+      .0
+      .bindings
+      .iter()
+      .cloned()
+      .map(|model| model.into())
+      .collect()
+  }
+}
+
+impl From<sync::Shared<Message>> for MessageContainer {
+  fn from(interior: sync::Shared<Message>) -> Self {
+    Self(interior)
+  }
+}
@@ -1,6 +1,11 @@
+use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The http method of an operation. Apart from the well known methods, an api
+/// may define operations for any other method (like `COPY` or `PURGE`).
+///
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(into = "String", from = "String")]
 pub enum Method {
   Get,
   Put,
@@ -10,8 +15,25 @@ pub enum Method {
   Head,
   Patch,
   Trace,
+  Query,
+  Other(String),
 }
 
+impl Method {
+  /// Get the method from a http method name like `GET` or `COPY`, the name is
+  /// case insensitive for well known methods. Other methods keep their name.
+  ///
+  pub fn from_http_name(name: &str) -> Self {
+    name
+      .to_lowercase()
+      .parse()
+      .unwrap_or_else(|_error| Self::Other(name.to_owned()))
+  }
+}
+
+/// Parses the (lower case) well known method names that are used as fields of
+/// a path item, other methods are not parsed.
+///
 impl FromStr for Method {
   type Err = MethodParseError;
 
@@ -25,6 +47,7 @@ impl FromStr for Method {
       "head" => Self::Head,
       "patch" => Self::Patch,
       "trace" => Self::Trace,
+      "query" => Self::Query,
       _ => Err(MethodParseError)?,
     })
   }
@@ -42,8 +65,39 @@ impl ToString for Method {
       Self::Head => "head".to_owned(),
       Self::Patch => "patch".to_owned(),
       Self::Trace => "trace".to_owned(),
+      Self::Query => "query".to_owned(),
+      Self::Other(name) => name.clone(),
     }
   }
 }
 
+impl From<Method> for String {
+  fn from(value: Method) -> Self {
+    value.to_string()
+  }
+}
+
+impl From<String> for Method {
+  fn from(value: String) -> Self {
+    Self::from_http_name(&value)
+  }
+}
+
+#[derive(Debug)]
 pub struct MethodParseError;
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_from_http_name() {
+    assert_eq!(Method::from_http_name("GET"), Method::Get);
+    assert_eq!(Method::from_http_name("query"), Method::Query);
+    assert_eq!(
+      Method::from_http_name("COPY"),
+      Method::Other("COPY".to_owned())
+    );
+    assert!("summary".parse::<Method>().is_err());
+  }
+}
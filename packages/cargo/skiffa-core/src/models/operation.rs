@@ -1,11 +1,16 @@
 use super::{
-  AuthenticationRequirementGroup, AuthenticationRequirementGroupContainer, Body, BodyContainer,
-  Method, OperationResult, OperationResultContainer, Parameter, ParameterContainer,
+  negotiate_bodies, AuthenticationRequirementGroup, AuthenticationRequirementGroupContainer, Body,
+  BodyContainer, Method, OperationResult, OperationResultContainer, Parameter, ParameterContainer,
 };
+use crate::utils::extensions_to_js_value;
+use crate::utils::sync;
 use crate::utils::NodeLocation;
-use std::rc;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use wasm_bindgen::prelude::*;
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Operation {
   pub location: NodeLocation,
   pub method: Method,
@@ -13,18 +18,48 @@ pub struct Operation {
   pub summary: Option<String>,
   pub description: Option<String>,
   pub deprecated: bool,
-  pub authentication_requirements: Vec<rc::Rc<AuthenticationRequirementGroup>>,
-  pub query_parameters: Vec<rc::Rc<Parameter>>,
-  pub header_parameters: Vec<rc::Rc<Parameter>>,
-  pub path_parameters: Vec<rc::Rc<Parameter>>,
-  pub cookie_parameters: Vec<rc::Rc<Parameter>>,
-  pub bodies: Vec<rc::Rc<Body>>,
-  pub operation_results: Vec<rc::Rc<OperationResult>>,
+  #[serde(default)]
+  pub tags: Vec<String>,
+  pub authentication_requirements: Vec<sync::Shared<AuthenticationRequirementGroup>>,
+  pub query_parameters: Vec<sync::Shared<Parameter>>,
+  pub header_parameters: Vec<sync::Shared<Parameter>>,
+  pub path_parameters: Vec<sync::Shared<Parameter>>,
+  pub cookie_parameters: Vec<sync::Shared<Parameter>>,
+  #[serde(default)]
+  /// Parameters that describe the whole query string
+  pub querystring_parameters: Vec<sync::Shared<Parameter>>,
+  pub bodies: Vec<sync::Shared<Body>>,
+  pub operation_results: Vec<sync::Shared<OperationResult>>,
+  /// Specification extensions (`x-` members), by name
+  #[serde(default)]
+  pub extensions: BTreeMap<String, serde_json::Value>,
+}
+
+impl Operation {
+  /// The request body that matches the `Content-Type` of a request
+  pub fn negotiate_request_body(&self, content_type: &str) -> Option<sync::Shared<Body>> {
+    negotiate_bodies(content_type, &self.bodies)
+  }
+
+  /// The response body for a status code that best matches the `Accept`
+  /// header
+  ///
+  pub fn negotiate_response_body(
+    &self,
+    accept: &str,
+    status_code: usize,
+  ) -> Option<sync::Shared<Body>> {
+    self
+      .operation_results
+      .iter()
+      .find(|operation_result| operation_result.status_codes.contains(&status_code))?
+      .negotiate_body(accept)
+  }
 }
 
 #[derive(Clone)]
 #[wasm_bindgen]
-pub struct OperationContainer(rc::Rc<Operation>);
+pub struct OperationContainer(sync::Shared<Operation>);
 
 #[wasm_bindgen]
 impl OperationContainer {
@@ -58,6 +93,11 @@ impl OperationContainer {
     self.0.deprecated
   }
 
+  #[wasm_bindgen(getter, js_name = "tags")]
+  pub fn tags(&self) -> Vec<String> {
+    self.0.tags.clone()
+  }
+
   #[wasm_bindgen(getter, js_name = "authenticationRequirements")]
   pub fn authentication_requirements(&self) -> Vec<AuthenticationRequirementGroupContainer> {
     self
@@ -113,6 +153,17 @@ impl OperationContainer {
       .collect()
   }
 
+  #[wasm_bindgen(getter, js_name = "querystringParameters")]
+  pub fn querystring_parameters(&self) -> Vec<ParameterContainer> {
+    self
+      .0
+      .querystring_parameters
+      .iter()
+      .cloned()
+      .map(|model| model.into())
+      .collect()
+  }
+
   #[wasm_bindgen(getter, js_name = "bodies")]
   pub fn bodies(&self) -> Vec<BodyContainer> {
     self
@@ -134,10 +185,28 @@ impl OperationContainer {
       .map(|model| model.into())
       .collect()
   }
+
+  #[wasm_bindgen(js_name = "negotiateRequestBody")]
+  pub fn negotiate_request_body(&self, content_type: &str) -> Option<BodyContainer> {
+    self.0.negotiate_request_body(content_type).map(Into::into)
+  }
+
+  #[wasm_bindgen(js_name = "negotiateResponseBody")]
+  pub fn negotiate_response_body(&self, accept: &str, status_code: usize) -> Option<BodyContainer> {
+    self
+      .0
+      .negotiate_response_body(accept, status_code)
+      .map(Into::into)
+  }
+
+  #[wasm_bindgen(getter, js_name = "extensions")]
+  pub fn extensions(&self) -> JsValue {
+    extensions_to_js_value(&self.0.extensions)
+  }
 }
 
-impl From<rc::Rc<Operation>> for OperationContainer {
-  fn from(interior: rc::Rc<Operation>) -> Self {
+impl From<sync::Shared<Operation>> for OperationContainer {
+  fn from(interior: sync::Shared<Operation>) -> Self {
     Self(interior)
   }
 }
@@ -1,20 +1,35 @@
-use super::{Body, BodyContainer, Parameter, ParameterContainer, StatusKind};
+use super::{negotiate_bodies, Body, BodyContainer, Parameter, ParameterContainer, StatusKind};
+use crate::utils::extensions_to_js_value;
+use crate::utils::sync;
 use crate::utils::NodeLocation;
-use std::rc;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use wasm_bindgen::prelude::*;
 
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct OperationResult {
   pub location: NodeLocation,
   pub description: Option<String>,
   pub status_kind: StatusKind,
   pub status_codes: Vec<usize>,
-  pub header_parameters: Vec<rc::Rc<Parameter>>,
-  pub bodies: Vec<rc::Rc<Body>>,
+  pub header_parameters: Vec<sync::Shared<Parameter>>,
+  pub bodies: Vec<sync::Shared<Body>>,
+  /// Specification extensions (`x-` members), by name
+  #[serde(default)]
+  pub extensions: BTreeMap<String, serde_json::Value>,
+}
+
+impl OperationResult {
+  /// The body that best matches the `Accept` header
+  pub fn negotiate_body(&self, accept: &str) -> Option<sync::Shared<Body>> {
+    negotiate_bodies(accept, &self.bodies)
+  }
 }
 
 #[derive(Clone)]
 #[wasm_bindgen]
-pub struct OperationResultContainer(rc::Rc<OperationResult>);
+pub struct OperationResultContainer(sync::Shared<OperationResult>);
 
 #[wasm_bindgen]
 impl OperationResultContainer {
@@ -59,10 +74,20 @@ impl OperationResultContainer {
       .map(|model| model.into())
       .collect()
   }
+
+  #[wasm_bindgen(js_name = "negotiateBody")]
+  pub fn negotiate_body(&self, accept: &str) -> Option<BodyContainer> {
+    self.0.negotiate_body(accept).map(Into::into)
+  }
+
+  #[wasm_bindgen(getter, js_name = "extensions")]
+  pub fn extensions(&self) -> JsValue {
+    extensions_to_js_value(&self.0.extensions)
+  }
 }
 
-impl From<rc::Rc<OperationResult>> for OperationResultContainer {
-  fn from(interior: rc::Rc<OperationResult>) -> Self {
+impl From<sync::Shared<OperationResult>> for OperationResultContainer {
+  fn from(interior: sync::Shared<OperationResult>) -> Self {
     Self(interior)
   }
 }
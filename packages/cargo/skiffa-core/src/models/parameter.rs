@@ -1,17 +1,25 @@
+use crate::utils::extensions_to_js_value;
+use crate::utils::sync;
 use crate::utils::NodeLocation;
-use std::rc;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use wasm_bindgen::prelude::*;
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Parameter {
   pub location: NodeLocation,
   pub name: String,
   pub required: bool,
   pub schema_id: Option<NodeLocation>,
+  /// Specification extensions (`x-` members), by name
+  #[serde(default)]
+  pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
 #[derive(Clone)]
 #[wasm_bindgen]
-pub struct ParameterContainer(rc::Rc<Parameter>);
+pub struct ParameterContainer(sync::Shared<Parameter>);
 
 #[wasm_bindgen]
 impl ParameterContainer {
@@ -34,10 +42,15 @@ impl ParameterContainer {
   pub fn schema_id(&self) -> Option<String> {
     Some(self.0.schema_id.as_ref()?.to_string())
   }
+
+  #[wasm_bindgen(getter, js_name = "extensions")]
+  pub fn extensions(&self) -> JsValue {
+    extensions_to_js_value(&self.0.extensions)
+  }
 }
 
-impl From<rc::Rc<Parameter>> for ParameterContainer {
-  fn from(interior: rc::Rc<Parameter>) -> Self {
+impl From<sync::Shared<Parameter>> for ParameterContainer {
+  fn from(interior: sync::Shared<Parameter>) -> Self {
     Self(interior)
   }
 }
@@ -1,18 +1,26 @@
 use super::{Operation, OperationContainer};
+use crate::utils::extensions_to_js_value;
+use crate::utils::sync;
 use crate::utils::NodeLocation;
-use std::rc;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use wasm_bindgen::prelude::*;
 
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Path {
   pub id: usize,
   pub location: NodeLocation,
   pub pattern: String,
-  pub operations: Vec<rc::Rc<Operation>>,
+  pub operations: Vec<sync::Shared<Operation>>,
+  /// Specification extensions (`x-` members), by name
+  #[serde(default)]
+  pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
 #[derive(Clone)]
 #[wasm_bindgen]
-pub struct PathContainer(rc::Rc<Path>);
+pub struct PathContainer(sync::Shared<Path>);
 
 #[wasm_bindgen]
 impl PathContainer {
@@ -41,10 +49,15 @@ impl PathContainer {
       .map(|model| model.into())
       .collect()
   }
+
+  #[wasm_bindgen(getter, js_name = "extensions")]
+  pub fn extensions(&self) -> JsValue {
+    extensions_to_js_value(&self.0.extensions)
+  }
 }
 
-impl From<rc::Rc<Path>> for PathContainer {
-  fn from(interior: rc::Rc<Path>) -> Self {
+impl From<sync::Shared<Path>> for PathContainer {
+  fn from(interior: sync::Shared<Path>) -> Self {
     Self(interior)
   }
 }
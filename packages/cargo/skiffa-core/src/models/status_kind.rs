@@ -1,6 +1,8 @@
-use std::{num, ops, str::FromStr};
+use serde::{Deserialize, Serialize};
+use std::{fmt::Display, num, ops, str::FromStr};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
 pub enum StatusKind {
   Default,
   Class(usize),
@@ -53,8 +55,29 @@ impl ToString for StatusKind {
   }
 }
 
+impl From<StatusKind> for String {
+  fn from(value: StatusKind) -> Self {
+    value.to_string()
+  }
+}
+
+impl TryFrom<String> for StatusKind {
+  type Error = StatusKindParseError;
+
+  fn try_from(value: String) -> Result<Self, Self::Error> {
+    value.parse()
+  }
+}
+
+#[derive(Debug)]
 pub struct StatusKindParseError;
 
+impl Display for StatusKindParseError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "invalid status kind")
+  }
+}
+
 impl From<num::ParseIntError> for StatusKindParseError {
   fn from(_value: num::ParseIntError) -> Self {
     Self
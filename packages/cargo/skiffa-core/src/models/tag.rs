@@ -0,0 +1,64 @@
+use crate::utils::sync;
+use crate::utils::NodeLocation;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+/// A tag to group operations. Tags may be nested by referring to the name of
+/// their parent tag.
+///
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Tag {
+  pub location: NodeLocation,
+  pub name: String,
+  #[serde(default)]
+  pub summary: Option<String>,
+  pub description: Option<String>,
+  #[serde(default)]
+  pub parent: Option<String>,
+  #[serde(default)]
+  pub kind: Option<String>,
+}
+
+#[derive(Clone)]
+#[wasm_bindgen]
+pub struct TagContainer(sync::Shared<Tag>);
+
+#[wasm_bindgen]
+impl TagContainer {
+  #[wasm_bindgen(getter, js_name = "location")]
+  pub fn location(&self) -> String {
+    self.0.location.to_string()
+  }
+
+  #[wasm_bindgen(getter, js_name = "name")]
+  pub fn name(&self) -> String {
+    self.0.name.clone()
+  }
+
+  #[wasm_bindgen(getter, js_name = "summary")]
+  pub fn summary(&self) -> Option<String> {
+    self.0.summary.clone()
+  }
+
+  #[wasm_bindgen(getter, js_name = "description")]
+  pub fn description(&self) -> Option<String> {
+    self.0.description.clone()
+  }
+
+  #[wasm_bindgen(getter, js_name = "parent")]
+  pub fn parent(&self) -> Option<String> {
+    self.0.parent.clone()
+  }
+
+  #[wasm_bindgen(getter, js_name = "kind")]
+  pub fn kind(&self) -> Option<String> {
+    self.0.kind.clone()
+  }
+}
+
+impl From<sync::Shared<Tag>> for TagContainer {
+  fn from(interior: sync::Shared<Tag>) -> Self {
+    Self(interior)
+  }
+}
@@ -1,9 +1,21 @@
 mod banner;
+mod extensions;
+mod fetch_lock;
 mod fetch_text;
+mod fetchers;
 mod node_cache;
 mod node_location;
+mod references;
+mod yaml;
+
+pub mod sync;
 
 pub use banner::*;
+pub use extensions::*;
+pub use fetch_lock::*;
 pub use fetch_text::*;
+pub use fetchers::*;
 pub use node_cache::*;
 pub use node_location::*;
+pub use references::*;
+pub use yaml::*;
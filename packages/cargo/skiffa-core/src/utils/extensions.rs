@@ -0,0 +1,21 @@
+use std::collections::BTreeMap;
+use wasm_bindgen::JsValue;
+
+/// The specification extensions (members that start with `x-`) of an object
+/// node, by name.
+///
+pub fn get_extensions(node: &serde_json::Value) -> BTreeMap<String, serde_json::Value> {
+  node
+    .as_object()
+    .into_iter()
+    .flatten()
+    .filter(|(member, _value)| member.starts_with("x-"))
+    .map(|(member, value)| (member.clone(), value.clone()))
+    .collect()
+}
+
+/// The extensions as a javascript object
+pub fn extensions_to_js_value(extensions: &BTreeMap<String, serde_json::Value>) -> JsValue {
+  let text = serde_json::to_string(extensions).unwrap();
+  js_sys::JSON::parse(&text).unwrap()
+}
@@ -0,0 +1,150 @@
+use super::{fetch_text_file, write_text_file, FetchTextError};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+/// Name of the lock file in the fetch lock directory.
+///
+pub const FETCH_LOCK_FILE_NAME: &str = "fetch-lock.json";
+
+/// Where to keep the fetch lock and if we should only use what is in there.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FetchLockOptions {
+  pub directory: String,
+  pub offline: bool,
+}
+
+/// A pinned remote document.
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FetchLockEntry {
+  /// Hash of the text of the document, like `sha256-<hex>`
+  pub integrity: String,
+  /// Time of the first retrieval in seconds since the unix epoch
+  pub retrieved: u64,
+}
+
+/// Records the hash of every remote document that was fetched, keyed by the
+/// location of the document. The text of the documents is stored next to
+/// the lock file, named after the hash, so it can be served offline.
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FetchLock {
+  pub entries: BTreeMap<String, FetchLockEntry>,
+}
+
+impl FetchLock {
+  /// Load the lock from the directory, an empty lock is returned if there is
+  /// no lock file yet.
+  ///
+  pub async fn load(directory: &str) -> Result<Self, FetchTextError> {
+    let location = Self::get_file_location(directory, FETCH_LOCK_FILE_NAME);
+    match fetch_text_file(&location).await {
+      Ok(text) => serde_json::from_str(&text).map_err(|_error| FetchTextError::SerializationError),
+      Err(FetchTextError::NotFound) => Ok(Default::default()),
+      Err(error) => Err(error),
+    }
+  }
+
+  pub async fn save(&self, directory: &str) -> Result<(), FetchTextError> {
+    let location = Self::get_file_location(directory, FETCH_LOCK_FILE_NAME);
+    let text =
+      serde_json::to_string_pretty(self).map_err(|_error| FetchTextError::SerializationError)?;
+    write_text_file(&location, &text).await
+  }
+
+  /// Read the stored text for the entry, the text is verified against the
+  /// hash in the entry.
+  ///
+  pub async fn read_text(
+    directory: &str,
+    entry: &FetchLockEntry,
+  ) -> Result<String, FetchTextError> {
+    let location = Self::get_text_location(directory, &entry.integrity)?;
+    let text = fetch_text_file(&location).await?;
+    if Self::get_integrity(&text) != entry.integrity {
+      return Err(FetchTextError::IntegrityError);
+    }
+    Ok(text)
+  }
+
+  /// Store the text, returns the integrity of the stored text.
+  ///
+  pub async fn write_text(directory: &str, text: &str) -> Result<String, FetchTextError> {
+    let integrity = Self::get_integrity(text);
+    let location = Self::get_text_location(directory, &integrity)?;
+    write_text_file(&location, text).await?;
+    Ok(integrity)
+  }
+
+  pub fn get_integrity(text: &str) -> String {
+    let hash = Sha256::digest(text.as_bytes());
+    format!("sha256-{:x}", hash)
+  }
+
+  fn get_text_location(directory: &str, integrity: &str) -> Result<String, FetchTextError> {
+    let hash = integrity
+      .strip_prefix("sha256-")
+      .filter(|hash| hash.chars().all(|c| c.is_ascii_hexdigit()))
+      .ok_or(FetchTextError::IntegrityError)?;
+    Ok(Self::get_file_location(directory, hash))
+  }
+
+  fn get_file_location(directory: &str, name: &str) -> String {
+    format!("{}/{}", directory.trim_end_matches('/'), name)
+  }
+}
+
+/// Seconds since the unix epoch
+///
+#[cfg(target_os = "unknown")]
+pub fn get_unix_time() -> u64 {
+  (js_sys::Date::now() / 1000.0) as u64
+}
+
+/// Seconds since the unix epoch
+///
+#[cfg(not(target_os = "unknown"))]
+pub fn get_unix_time() -> u64 {
+  std::time::SystemTime::now()
+    .duration_since(std::time::UNIX_EPOCH)
+    .map(|duration| duration.as_secs())
+    .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[cfg(not(target_os = "unknown"))]
+  #[tokio::test]
+  async fn test_load_malformed() {
+    let directory = std::env::temp_dir()
+      .join(format!("skiffa-fetch-lock-{}", std::process::id()))
+      .to_string_lossy()
+      .into_owned();
+
+    assert_eq!(
+      FetchLock::load(&directory).await.unwrap(),
+      Default::default()
+    );
+
+    let location = FetchLock::get_file_location(&directory, FETCH_LOCK_FILE_NAME);
+    write_text_file(&location, "{ not json").await.unwrap();
+    assert!(matches!(
+      FetchLock::load(&directory).await,
+      Err(FetchTextError::SerializationError)
+    ));
+
+    std::fs::remove_dir_all(&directory).unwrap();
+  }
+
+  #[test]
+  fn test_get_integrity() {
+    assert_eq!(
+      FetchLock::get_integrity(""),
+      "sha256-e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    );
+  }
+}
@@ -1,4 +1,5 @@
 const fs = require("fs/promises");
+const path = require("path");
 
 async function fetchText(location) {
   const locationLower = location.toLowerCase();
@@ -12,4 +13,47 @@ async function fetchText(location) {
   return text;
 }
 
+async function readText(location) {
+  try {
+    const text = await fs.readFile(location, "utf-8");
+    return text;
+  } catch (error) {
+    if (error.code === "ENOENT") {
+      return null;
+    }
+    throw error;
+  }
+}
+
+async function writeText(location, text) {
+  await fs.mkdir(path.dirname(location), { recursive: true });
+  await fs.writeFile(location, text, "utf-8");
+}
+
+async function fetchHttp(location, headers, timeout, canRedirect) {
+  const signal = timeout == null ? undefined : AbortSignal.timeout(timeout);
+  // redirects are followed by the caller, so headers are only sent to the
+  // origin they are meant for
+  let result = await fetch(location, { headers, signal, redirect: "manual" });
+  if (result.type === "opaqueredirect") {
+    // browsers don't expose the location of a redirect, so the browser follows
+    // it. The headers are not sent, they might not be meant for the origin we
+    // are redirected to.
+    if (!canRedirect) {
+      throw new Error(`${location} redirects and no more redirects are allowed`);
+    }
+    result = await fetch(location, { signal, redirect: "follow" });
+  }
+  const text = await result.text();
+  return {
+    status: result.status,
+    contentType: result.headers.get("content-type"),
+    location: result.headers.get("location"),
+    text,
+  };
+}
+
 module.exports.fetchText = fetchText;
+module.exports.fetchHttp = fetchHttp;
+module.exports.readText = readText;
+module.exports.writeText = writeText;
@@ -1,13 +1,26 @@
+use super::{HttpFetchOptions, NodeLocation};
 use wasm_bindgen::prelude::*;
 
+#[derive(Debug)]
 pub enum FetchTextError {
   IoError,
   HttpError,
+  HttpStatus(u16),
+  UnexpectedContentType,
+  NotFound,
+  IntegrityError,
+  /// An option was set that is not supported on this platform
+  UnsupportedOption,
+  /// A file we read, like a lock file, could not be parsed
+  SerializationError,
 }
 
 impl From<std::io::Error> for FetchTextError {
-  fn from(_value: std::io::Error) -> Self {
-    Self::IoError
+  fn from(value: std::io::Error) -> Self {
+    match value.kind() {
+      std::io::ErrorKind::NotFound => Self::NotFound,
+      _ => Self::IoError,
+    }
   }
 }
 
@@ -18,8 +31,8 @@ impl From<JsValue> for FetchTextError {
 }
 
 #[cfg(not(target_os = "unknown"))]
-impl From<surf::Error> for FetchTextError {
-  fn from(_value: surf::Error) -> Self {
+impl From<reqwest::Error> for FetchTextError {
+  fn from(_value: reqwest::Error) -> Self {
     Self::HttpError
   }
 }
@@ -28,6 +41,20 @@ impl From<surf::Error> for FetchTextError {
 extern "C" {
   #[wasm_bindgen(catch, js_name = "fetchText")]
   async fn fetch_text_js(location: &str) -> Result<JsValue, JsValue>;
+
+  #[wasm_bindgen(catch, js_name = "readText")]
+  async fn read_text_js(location: &str) -> Result<JsValue, JsValue>;
+
+  #[wasm_bindgen(catch, js_name = "writeText")]
+  async fn write_text_js(location: &str, text: &str) -> Result<JsValue, JsValue>;
+
+  #[wasm_bindgen(catch, js_name = "fetchHttp")]
+  async fn fetch_http_js(
+    location: &str,
+    headers: js_sys::Object,
+    timeout: Option<f64>,
+    can_redirect: bool,
+  ) -> Result<JsValue, JsValue>;
 }
 
 #[cfg(target_os = "unknown")]
@@ -40,18 +67,255 @@ pub async fn fetch_text(location: &str) -> Result<String, FetchTextError> {
 
 #[cfg(not(target_os = "unknown"))]
 pub async fn fetch_text(location: &str) -> Result<String, FetchTextError> {
+  if location.starts_with("http://") || location.starts_with("https://") {
+    fetch_text_http(location, &Default::default()).await
+  } else {
+    fetch_text_file(location).await
+  }
+}
+
+/// Sends http requests. On native platforms the connections of the client
+/// are reused, so use one client for many fetches.
+///
+#[derive(Clone)]
+pub struct HttpClient {
+  #[cfg(not(target_os = "unknown"))]
+  client: reqwest::Client,
+}
+
+impl HttpClient {
+  #[cfg(target_os = "unknown")]
+  pub fn new(options: &HttpFetchOptions) -> Result<Self, FetchTextError> {
+    // fetch in javascript does not let us configure a proxy
+    if options.proxy.is_some() {
+      return Err(FetchTextError::UnsupportedOption);
+    }
+
+    Ok(Self {})
+  }
+
+  #[cfg(not(target_os = "unknown"))]
+  pub fn new(options: &HttpFetchOptions) -> Result<Self, FetchTextError> {
+    let mut builder = reqwest::Client::builder().redirect(reqwest::redirect::Policy::none());
+    if let Some(timeout) = options.timeout {
+      builder = builder.timeout(timeout);
+    }
+    if let Some(proxy) = &options.proxy {
+      builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+    }
+    let client = builder.build()?;
+
+    Ok(Self { client })
+  }
+}
+
+/// Fetch a document over http(s) with a new client.
+///
+pub async fn fetch_text_http(
+  location: &str,
+  options: &HttpFetchOptions,
+) -> Result<String, FetchTextError> {
+  let client = HttpClient::new(options)?;
+  fetch_text_http_with_client(&client, location, options).await
+}
+
+/// Fetch a document over http(s). Redirects are followed here, and not by
+/// the http client, so the headers for every request are the headers of the
+/// origin of that request. This way credentials are never sent to another
+/// origin.
+///
+pub async fn fetch_text_http_with_client(
+  client: &HttpClient,
+  location: &str,
+  options: &HttpFetchOptions,
+) -> Result<String, FetchTextError> {
+  let mut location = location.to_owned();
+  let mut redirects = 0;
+
+  loop {
+    let can_redirect = redirects < options.maximum_redirects;
+    let response = fetch_http_response(client, &location, options, can_redirect).await?;
+
+    if (300..400).contains(&response.status) && can_redirect {
+      if let Some(redirect_location) = &response.redirect_location {
+        location = get_redirect_location(&location, redirect_location)?;
+        redirects += 1;
+        continue;
+      }
+    }
+
+    check_http_response(
+      response.status,
+      response.content_type.as_deref(),
+      &response.text,
+    )?;
+
+    return Ok(response.text);
+  }
+}
+
+/// The response of a single http request, redirects are not followed.
+///
+struct HttpResponse {
+  status: u16,
+  content_type: Option<String>,
+  redirect_location: Option<String>,
+  text: String,
+}
+
+/// Browsers don't let us see a redirect, when we can redirect the browser
+/// follows the redirect without any of the headers.
+///
+#[cfg(target_os = "unknown")]
+async fn fetch_http_response(
+  _client: &HttpClient,
+  location: &str,
+  options: &HttpFetchOptions,
+  can_redirect: bool,
+) -> Result<HttpResponse, FetchTextError> {
+  let headers = js_sys::Object::new();
+  for (name, value) in options.get_headers(location) {
+    js_sys::Reflect::set(&headers, &name.into(), &value.into())?;
+  }
+  let timeout = options.timeout.map(|timeout| timeout.as_millis() as f64);
+
+  let response = fetch_http_js(location, headers, timeout, can_redirect).await?;
+  let status = js_sys::Reflect::get(&response, &"status".into())?
+    .as_f64()
+    .unwrap_or_default() as u16;
+  let content_type = js_sys::Reflect::get(&response, &"contentType".into())?.as_string();
+  let redirect_location = js_sys::Reflect::get(&response, &"location".into())?.as_string();
+  let text = js_sys::Reflect::get(&response, &"text".into())?
+    .as_string()
+    .unwrap_or_default();
+
+  Ok(HttpResponse {
+    status,
+    content_type,
+    redirect_location,
+    text,
+  })
+}
+
+#[cfg(not(target_os = "unknown"))]
+async fn fetch_http_response(
+  client: &HttpClient,
+  location: &str,
+  options: &HttpFetchOptions,
+  _can_redirect: bool,
+) -> Result<HttpResponse, FetchTextError> {
+  let mut request = client.client.get(location);
+  for (name, value) in options.get_headers(location) {
+    request = request.header(name, value);
+  }
+
+  let response = request.send().await?;
+  let status = response.status().as_u16();
+  let get_header = |name: &str| {
+    response
+      .headers()
+      .get(name)
+      .and_then(|value| value.to_str().ok())
+      .map(|value| value.to_owned())
+  };
+  let content_type = get_header("content-type");
+  let redirect_location = get_header("location");
+  let text = response.text().await?;
+
+  Ok(HttpResponse {
+    status,
+    content_type,
+    redirect_location,
+    text,
+  })
+}
+
+/// Resolve the location header of a redirect against the location that was
+/// requested.
+///
+fn get_redirect_location(
+  location: &str,
+  redirect_location: &str,
+) -> Result<String, FetchTextError> {
+  let location: NodeLocation = location
+    .parse()
+    .map_err(|_error| FetchTextError::HttpError)?;
+  let redirect_location: NodeLocation = redirect_location
+    .parse()
+    .map_err(|_error| FetchTextError::HttpError)?;
+  let redirect_location = location.join(&redirect_location);
+  if redirect_location.is_file() {
+    return Err(FetchTextError::HttpError);
+  }
+
+  Ok(redirect_location.to_fetch_string())
+}
+
+/// Make sure that we got a successful response that is not a html page, we
+/// don't want to parse an error page as yaml.
+///
+fn check_http_response(
+  status: u16,
+  content_type: Option<&str>,
+  text: &str,
+) -> Result<(), FetchTextError> {
+  if !(200..300).contains(&status) {
+    return Err(FetchTextError::HttpStatus(status));
+  }
+
+  let essence = content_type
+    .and_then(|content_type| content_type.split(';').next())
+    .map(|essence| essence.trim().to_lowercase());
+  if let Some("text/html" | "application/xhtml+xml") = essence.as_deref() {
+    return Err(FetchTextError::UnexpectedContentType);
+  }
+
+  let start = text
+    .trim_start()
+    .chars()
+    .take(14)
+    .collect::<String>()
+    .to_lowercase();
+  if start.starts_with("<!doctype html") || start.starts_with("<html") {
+    return Err(FetchTextError::UnexpectedContentType);
+  }
+
+  Ok(())
+}
+
+#[cfg(target_os = "unknown")]
+pub async fn fetch_text_file(location: &str) -> Result<String, FetchTextError> {
+  let text = read_text_js(location).await?;
+  text.as_string().ok_or(FetchTextError::NotFound)
+}
+
+#[cfg(not(target_os = "unknown"))]
+pub async fn fetch_text_file(location: &str) -> Result<String, FetchTextError> {
   use tokio::fs::File;
   use tokio::io::AsyncReadExt;
 
-  if location.starts_with("http://") || location.starts_with("https://") {
-    let mut response = surf::get(location).await?;
-    let data = response.body_string().await?;
-    Ok(data)
-  } else {
-    let mut file = File::open(location).await?;
-    let metadata = file.metadata().await?;
-    let mut data = String::with_capacity(metadata.len() as usize);
-    file.read_to_string(&mut data).await?;
-    Ok(data)
+  let mut file = File::open(location).await?;
+  let metadata = file.metadata().await?;
+  let mut data = String::with_capacity(metadata.len() as usize);
+  file.read_to_string(&mut data).await?;
+  Ok(data)
+}
+
+#[cfg(target_os = "unknown")]
+pub async fn write_text_file(location: &str, text: &str) -> Result<(), FetchTextError> {
+  write_text_js(location, text)
+    .await
+    .map_err(|_error| FetchTextError::IoError)?;
+  Ok(())
+}
+
+/// Write text to a file, parent directories are created when needed.
+///
+#[cfg(not(target_os = "unknown"))]
+pub async fn write_text_file(location: &str, text: &str) -> Result<(), FetchTextError> {
+  if let Some(parent) = std::path::Path::new(location).parent() {
+    tokio::fs::create_dir_all(parent).await?;
   }
+  tokio::fs::write(location, text).await?;
+  Ok(())
 }
@@ -0,0 +1,17 @@
+mod archive;
+mod default;
+mod file;
+mod http;
+mod http_options;
+mod interface;
+mod locked;
+mod memory;
+
+pub use archive::*;
+pub use default::*;
+pub use file::*;
+pub use http::*;
+pub use http_options::*;
+pub use interface::*;
+pub use locked::*;
+pub use memory::*;
@@ -0,0 +1,110 @@
+use super::{FetchTextFuture, Fetcher, MemoryFetcher};
+use crate::utils::{FetchTextError, NodeLocation};
+use std::io::Read;
+use std::path::Component;
+
+/// Fetches documents from a (tar) archive. The archive is mounted on a base
+/// location, every file in the archive is available on it's path relative to
+/// that location. Archives with entries that are outside of the base
+/// location, like `../a.yaml` or `/a.yaml`, are rejected.
+///
+pub struct ArchiveFetcher {
+  memory: MemoryFetcher,
+}
+
+impl ArchiveFetcher {
+  pub fn from_tar(
+    base_location: &NodeLocation,
+    archive: impl Read,
+  ) -> Result<Self, FetchTextError> {
+    let mut memory = MemoryFetcher::new();
+    let mut archive = tar::Archive::new(archive);
+
+    for entry in archive.entries()? {
+      let mut entry = entry?;
+      if !entry.header().entry_type().is_file() {
+        continue;
+      }
+
+      let path = entry
+        .path()?
+        .components()
+        .filter(|component| *component != Component::CurDir)
+        .map(|component| match component {
+          Component::Normal(member) => Ok(member.to_string_lossy().into_owned()),
+          _ => Err(FetchTextError::IoError),
+        })
+        .collect::<Result<Vec<_>, _>>()?
+        .join("/");
+      let path: NodeLocation = path.parse().map_err(|_error| FetchTextError::IoError)?;
+      let location = base_location.join(&path);
+
+      let mut text = String::new();
+      entry.read_to_string(&mut text)?;
+
+      memory.insert(&location, text);
+    }
+
+    Ok(Self { memory })
+  }
+}
+
+impl Fetcher for ArchiveFetcher {
+  fn fetch_text<'a>(&'a self, location: &'a NodeLocation) -> FetchTextFuture<'a> {
+    self.memory.fetch_text(location)
+  }
+}
+
+#[cfg(not(target_os = "unknown"))]
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn test_from_tar() {
+    let mut builder = tar::Builder::new(Vec::new());
+    for (path, text) in [
+      ("api.yaml", "openapi: 3.0.3"),
+      ("models/a.yaml", "type: string"),
+    ] {
+      let mut header = tar::Header::new_gnu();
+      header.set_size(text.len() as u64);
+      header.set_cksum();
+      builder
+        .append_data(&mut header, path, text.as_bytes())
+        .unwrap();
+    }
+    let archive = builder.into_inner().unwrap();
+
+    let base_location: NodeLocation = "http://example.com/bundle/".parse().unwrap();
+    let fetcher = ArchiveFetcher::from_tar(&base_location, archive.as_slice()).unwrap();
+
+    let location = "http://example.com/bundle/models/a.yaml#/type"
+      .parse()
+      .unwrap();
+    let text = fetcher.fetch_text(&location).await.unwrap();
+    assert_eq!(text, "type: string");
+
+    let location = "http://example.com/bundle/b.yaml".parse().unwrap();
+    assert!(fetcher.fetch_text(&location).await.is_err());
+  }
+
+  #[test]
+  fn test_from_tar_outside_base() {
+    for path in ["../api.yaml", "models/../../api.yaml", "/api.yaml"] {
+      let text = "openapi: 3.0.3";
+      // the builder refuses these paths, so we write them in the header
+      let mut header = tar::Header::new_gnu();
+      header.as_gnu_mut().unwrap().name[..path.len()].copy_from_slice(path.as_bytes());
+      header.set_size(text.len() as u64);
+      header.set_cksum();
+      let mut builder = tar::Builder::new(Vec::new());
+      builder.append(&header, text.as_bytes()).unwrap();
+      let archive = builder.into_inner().unwrap();
+
+      let base_location: NodeLocation = "http://example.com/bundle/".parse().unwrap();
+      let result = ArchiveFetcher::from_tar(&base_location, archive.as_slice());
+      assert!(result.is_err(), "{}", path);
+    }
+  }
+}
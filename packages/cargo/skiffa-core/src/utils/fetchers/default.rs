@@ -0,0 +1,34 @@
+use super::{FetchTextFuture, Fetcher, FileFetcher, HttpFetchOptions, HttpFetcher};
+use crate::utils::NodeLocation;
+
+/// Fetches documents from the file system or a http(s) server, depending on
+/// the location. This is the fetcher the `NodeCache` uses by default.
+///
+#[derive(Default)]
+pub struct DefaultFetcher {
+  file: FileFetcher,
+  http: HttpFetcher,
+}
+
+impl DefaultFetcher {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn new_with_http_options(options: HttpFetchOptions) -> Self {
+    Self {
+      file: FileFetcher::new(),
+      http: HttpFetcher::new_with_options(options),
+    }
+  }
+}
+
+impl Fetcher for DefaultFetcher {
+  fn fetch_text<'a>(&'a self, location: &'a NodeLocation) -> FetchTextFuture<'a> {
+    if location.is_file() {
+      self.file.fetch_text(location)
+    } else {
+      self.http.fetch_text(location)
+    }
+  }
+}
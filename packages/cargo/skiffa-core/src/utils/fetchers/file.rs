@@ -0,0 +1,25 @@
+use super::{FetchTextFuture, Fetcher};
+use crate::utils::{fetch_text_file, FetchTextError, NodeLocation};
+
+/// Fetches documents from the file system.
+///
+#[derive(Default)]
+pub struct FileFetcher;
+
+impl FileFetcher {
+  pub fn new() -> Self {
+    Self
+  }
+}
+
+impl Fetcher for FileFetcher {
+  fn fetch_text<'a>(&'a self, location: &'a NodeLocation) -> FetchTextFuture<'a> {
+    Box::pin(async move {
+      if !location.is_file() {
+        return Err(FetchTextError::NotFound);
+      }
+
+      fetch_text_file(&location.to_fetch_string()).await
+    })
+  }
+}
@@ -0,0 +1,214 @@
+use super::{FetchTextFuture, Fetcher, HttpFetchOptions};
+use crate::utils::{fetch_text_http_with_client, FetchTextError, HttpClient, NodeLocation};
+use once_cell::sync::OnceCell;
+
+/// Fetches documents from a http(s) server. The http client is created on
+/// the first fetch and reused for every fetch after that.
+///
+#[derive(Default)]
+pub struct HttpFetcher {
+  options: HttpFetchOptions,
+  client: OnceCell<HttpClient>,
+}
+
+impl HttpFetcher {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn new_with_options(options: HttpFetchOptions) -> Self {
+    Self {
+      options,
+      client: OnceCell::new(),
+    }
+  }
+}
+
+impl Fetcher for HttpFetcher {
+  fn fetch_text<'a>(&'a self, location: &'a NodeLocation) -> FetchTextFuture<'a> {
+    Box::pin(async move {
+      if location.is_file() {
+        return Err(FetchTextError::NotFound);
+      }
+
+      let client = self
+        .client
+        .get_or_try_init(|| HttpClient::new(&self.options))?;
+      fetch_text_http_with_client(client, &location.to_fetch_string(), &self.options).await
+    })
+  }
+}
+
+#[cfg(not(target_os = "unknown"))]
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::sync::{Arc, Mutex};
+  use tokio::io::{AsyncReadExt, AsyncWriteExt};
+  use tokio::net::TcpListener;
+
+  /// A very simple http server that serves a few fixed responses and records
+  /// the requests it receives.
+  ///
+  async fn start_stub_server() -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let origin = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+
+    {
+      let requests = requests.clone();
+      tokio::spawn(async move {
+        loop {
+          let (mut stream, _address) = listener.accept().await.unwrap();
+          let mut buffer = Vec::new();
+          while !buffer.ends_with(b"\r\n\r\n") {
+            let mut byte = [0; 1];
+            if stream.read(&mut byte).await.unwrap_or_default() == 0 {
+              break;
+            }
+            buffer.push(byte[0]);
+          }
+          // header values may or may not be preceded by whitespace
+          let request = String::from_utf8(buffer)
+            .unwrap()
+            .to_lowercase()
+            .replace(": ", ":");
+          requests.lock().unwrap().push(request.clone());
+
+          let path = request.split(' ').nth(1).unwrap_or_default().to_owned();
+          let authorized = request.contains("authorization:bearer secret");
+          let (status, headers, body) = match path.as_str() {
+            "/public.yaml" => (
+              "200 OK",
+              "content-type: application/yaml\r\n".to_owned(),
+              "openapi: 3.0.3",
+            ),
+            _ if !authorized => ("401 Unauthorized", "".to_owned(), ""),
+            "/api.yaml" => (
+              "200 OK",
+              "content-type: application/yaml\r\n".to_owned(),
+              "openapi: 3.0.3",
+            ),
+            "/redirect.yaml" => ("302 Found", "location: /api.yaml\r\n".to_owned(), ""),
+            // redirects to another origin, like `/redirect/127.0.0.1:8080/public.yaml`
+            path if path.starts_with("/redirect/") => (
+              "302 Found",
+              format!("location: http://{}\r\n", &path["/redirect/".len()..]),
+              "",
+            ),
+            "/page.yaml" => (
+              "200 OK",
+              "content-type: text/html\r\n".to_owned(),
+              "<html></html>",
+            ),
+            "/sniff.yaml" => ("200 OK", "".to_owned(), "<!DOCTYPE html><html></html>"),
+            _ => ("404 Not Found", "".to_owned(), ""),
+          };
+          let response = format!(
+            "HTTP/1.1 {}\r\n{}content-length: {}\r\nconnection: close\r\n\r\n{}",
+            status,
+            headers,
+            body.len(),
+            body
+          );
+          // the client may already have closed the connection
+          let _ = stream.write_all(response.as_bytes()).await;
+          let _ = stream.shutdown().await;
+        }
+      });
+    }
+
+    (origin, requests)
+  }
+
+  #[tokio::test]
+  async fn test_fetch_text() {
+    let (origin, requests) = start_stub_server().await;
+
+    let mut options = HttpFetchOptions::new();
+    options.set_bearer_token(&origin, "secret");
+    options.set_header(&origin, "x-custom", "value");
+    options.set_header("http://other.example.com", "x-other", "value");
+    let fetcher = HttpFetcher::new_with_options(options.clone());
+
+    let location = format!("{}/api.yaml", origin).parse().unwrap();
+    let text = fetcher.fetch_text(&location).await.unwrap();
+    assert_eq!(text, "openapi: 3.0.3");
+    {
+      let requests = requests.lock().unwrap();
+      let request = requests.last().unwrap();
+      assert!(request.contains("x-custom:value"));
+      assert!(!request.contains("x-other"));
+    }
+
+    let location = format!("{}/redirect.yaml", origin).parse().unwrap();
+    let text = fetcher.fetch_text(&location).await.unwrap();
+    assert_eq!(text, "openapi: 3.0.3");
+
+    let location = format!("{}/missing.yaml", origin).parse().unwrap();
+    let error = fetcher.fetch_text(&location).await.unwrap_err();
+    assert!(matches!(error, FetchTextError::HttpStatus(404)));
+
+    let location = format!("{}/page.yaml", origin).parse().unwrap();
+    let error = fetcher.fetch_text(&location).await.unwrap_err();
+    assert!(matches!(error, FetchTextError::UnexpectedContentType));
+
+    let location = format!("{}/sniff.yaml", origin).parse().unwrap();
+    let error = fetcher.fetch_text(&location).await.unwrap_err();
+    assert!(matches!(error, FetchTextError::UnexpectedContentType));
+
+    let fetcher = HttpFetcher::new_with_options(HttpFetchOptions {
+      maximum_redirects: 0,
+      ..options.clone()
+    });
+    let location = format!("{}/redirect.yaml", origin).parse().unwrap();
+    let error = fetcher.fetch_text(&location).await.unwrap_err();
+    assert!(matches!(error, FetchTextError::HttpStatus(302)));
+
+    let fetcher = HttpFetcher::new();
+    let location = format!("{}/api.yaml", origin).parse().unwrap();
+    let error = fetcher.fetch_text(&location).await.unwrap_err();
+    assert!(matches!(error, FetchTextError::HttpStatus(401)));
+  }
+
+  #[tokio::test]
+  async fn test_fetch_text_cross_origin_redirect() {
+    let (origin, requests) = start_stub_server().await;
+    let (other_origin, other_requests) = start_stub_server().await;
+
+    let mut options = HttpFetchOptions::new();
+    options.set_bearer_token(&origin, "secret");
+    let fetcher = HttpFetcher::new_with_options(options);
+
+    let location = format!(
+      "{}/redirect/{}/public.yaml",
+      origin,
+      other_origin.trim_start_matches("http://")
+    )
+    .parse()
+    .unwrap();
+    let text = fetcher.fetch_text(&location).await.unwrap();
+    assert_eq!(text, "openapi: 3.0.3");
+
+    assert!(requests
+      .lock()
+      .unwrap()
+      .last()
+      .unwrap()
+      .contains("authorization:bearer secret"));
+    assert!(!other_requests
+      .lock()
+      .unwrap()
+      .last()
+      .unwrap()
+      .contains("authorization"));
+  }
+
+  #[test]
+  fn test_basic_authentication() {
+    let mut options = HttpFetchOptions::new();
+    options.set_basic_authentication("https://Example.com:443", "user", "pass");
+    let headers = options.get_headers("https://example.com/a/b.yaml");
+    assert_eq!(headers["authorization"], "Basic dXNlcjpwYXNz");
+  }
+}
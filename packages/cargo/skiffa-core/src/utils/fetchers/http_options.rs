@@ -0,0 +1,70 @@
+use crate::utils::NodeLocation;
+use base64::Engine;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Options for fetching documents over http(s). Headers are configured per
+/// origin (like `https://example.com`) so credentials are only sent to the
+/// server they are meant for.
+///
+#[derive(Clone, Debug)]
+pub struct HttpFetchOptions {
+  pub headers: BTreeMap<String, BTreeMap<String, String>>,
+  pub timeout: Option<Duration>,
+  pub maximum_redirects: usize,
+  /// Proxies are not supported when running as wasm, fetching fails with
+  /// `FetchTextError::UnsupportedOption` when a proxy is set.
+  pub proxy: Option<String>,
+}
+
+impl Default for HttpFetchOptions {
+  fn default() -> Self {
+    Self {
+      headers: Default::default(),
+      timeout: None,
+      maximum_redirects: 10,
+      proxy: None,
+    }
+  }
+}
+
+impl HttpFetchOptions {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn set_header(&mut self, origin: &str, name: &str, value: &str) {
+    self
+      .headers
+      .entry(normalize_origin(origin))
+      .or_default()
+      .insert(name.to_lowercase(), value.to_owned());
+  }
+
+  pub fn set_bearer_token(&mut self, origin: &str, token: &str) {
+    self.set_header(origin, "authorization", &format!("Bearer {}", token));
+  }
+
+  pub fn set_basic_authentication(&mut self, origin: &str, username: &str, password: &str) {
+    let credentials = base64::engine::general_purpose::STANDARD
+      .encode(format!("{}:{}", username, password).as_bytes());
+    self.set_header(origin, "authorization", &format!("Basic {}", credentials));
+  }
+
+  /// Get the headers that should be sent when fetching this location
+  ///
+  pub fn get_headers(&self, location: &str) -> BTreeMap<String, String> {
+    self
+      .headers
+      .get(&normalize_origin(location))
+      .cloned()
+      .unwrap_or_default()
+  }
+}
+
+fn normalize_origin(location: &str) -> String {
+  location
+    .parse::<NodeLocation>()
+    .map(|location| location.get_origin())
+    .unwrap_or_else(|_error| location.to_owned())
+}
@@ -0,0 +1,22 @@
+use crate::utils::sync::MaybeSendSync;
+use crate::utils::{FetchTextError, NodeLocation};
+
+/// The future that is returned when fetching, it is `Send` in native builds.
+///
+#[cfg(not(target_os = "unknown"))]
+pub type FetchTextFuture<'a> = futures::future::BoxFuture<'a, Result<String, FetchTextError>>;
+
+/// The future that is returned when fetching, it is `Send` in native builds.
+///
+#[cfg(target_os = "unknown")]
+pub type FetchTextFuture<'a> = futures::future::LocalBoxFuture<'a, Result<String, FetchTextError>>;
+
+/// Retrieves the text of a document. The `NodeCache` uses a fetcher to load
+/// every document, so by implementing this trait documents can be loaded from
+/// any source.
+///
+pub trait Fetcher: MaybeSendSync {
+  /// Fetch the text of the document at the (root) location.
+  ///
+  fn fetch_text<'a>(&'a self, location: &'a NodeLocation) -> FetchTextFuture<'a>;
+}
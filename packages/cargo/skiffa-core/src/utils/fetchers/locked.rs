@@ -0,0 +1,161 @@
+use super::{FetchTextFuture, Fetcher};
+use crate::utils::sync::Lock;
+use crate::utils::{get_unix_time, FetchLock, FetchLockEntry, FetchTextError, NodeLocation};
+use futures::lock::Mutex;
+
+/// Pins remote documents in a `FetchLock`. The first time a remote document is
+/// fetched it's hash is recorded and the text is stored in the lock
+/// directory, when fetched again the hash must not change. When offline the
+/// documents are served from the lock directory only. Local files are passed
+/// through to the inner fetcher.
+///
+pub struct LockedFetcher {
+  inner: Box<dyn Fetcher>,
+  directory: String,
+  offline: bool,
+  /**
+   * lazily loaded lock
+   */
+  lock: Lock<Option<FetchLock>>,
+  /**
+   * documents may be fetched concurrently, only one may save the lock at a
+   * time so the last save always has all entries
+   */
+  saving: Mutex<()>,
+}
+
+impl LockedFetcher {
+  pub fn new(inner: impl Fetcher + 'static, directory: impl Into<String>, offline: bool) -> Self {
+    Self {
+      inner: Box::new(inner),
+      directory: directory.into(),
+      offline,
+      lock: Default::default(),
+      saving: Default::default(),
+    }
+  }
+
+  async fn get_entry(&self, key: &str) -> Result<Option<FetchLockEntry>, FetchTextError> {
+    if self.lock.borrow().is_none() {
+      let lock = FetchLock::load(&self.directory).await?;
+      self.lock.borrow_mut().get_or_insert(lock);
+    }
+
+    let lock = self.lock.borrow();
+    Ok(
+      lock
+        .as_ref()
+        .and_then(|lock| lock.entries.get(key))
+        .cloned(),
+    )
+  }
+
+  async fn fetch_text_locked(&self, location: &NodeLocation) -> Result<String, FetchTextError> {
+    let key = location.to_fetch_string();
+    let entry = self.get_entry(&key).await?;
+
+    if self.offline {
+      let entry = entry.ok_or(FetchTextError::NotFound)?;
+      return FetchLock::read_text(&self.directory, &entry).await;
+    }
+
+    let text = self.inner.fetch_text(location).await?;
+    let integrity = FetchLock::get_integrity(&text);
+    if let Some(entry) = entry {
+      if entry.integrity != integrity {
+        return Err(FetchTextError::IntegrityError);
+      }
+      FetchLock::write_text(&self.directory, &text).await?;
+      return Ok(text);
+    }
+
+    FetchLock::write_text(&self.directory, &text).await?;
+    self
+      .lock
+      .borrow_mut()
+      .get_or_insert_with(Default::default)
+      .entries
+      .insert(
+        key,
+        FetchLockEntry {
+          integrity,
+          retrieved: get_unix_time(),
+        },
+      );
+
+    let _saving = self.saving.lock().await;
+    let lock = self.lock.borrow().clone().unwrap_or_default();
+    lock.save(&self.directory).await?;
+
+    Ok(text)
+  }
+}
+
+impl Fetcher for LockedFetcher {
+  fn fetch_text<'a>(&'a self, location: &'a NodeLocation) -> FetchTextFuture<'a> {
+    if location.is_file() {
+      return self.inner.fetch_text(location);
+    }
+
+    Box::pin(self.fetch_text_locked(location))
+  }
+}
+
+#[cfg(not(target_os = "unknown"))]
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::utils::MemoryFetcher;
+
+  fn get_directory(name: &str) -> String {
+    let directory =
+      std::env::temp_dir().join(format!("skiffa-fetch-lock-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&directory);
+    directory.to_string_lossy().into_owned()
+  }
+
+  #[tokio::test]
+  async fn test_locked_fetcher() {
+    let directory = get_directory("pin");
+    let location: NodeLocation = "http://example.com/api.yaml".parse().unwrap();
+    let other_location: NodeLocation = "http://example.com/other.yaml".parse().unwrap();
+
+    // first retrieval records the hash
+    let fetcher = LockedFetcher::new(
+      MemoryFetcher::from_iter([(location.clone(), "a: 1")]),
+      &directory,
+      false,
+    );
+    let text = fetcher.fetch_text(&location).await.unwrap();
+    assert_eq!(text, "a: 1");
+
+    let lock = FetchLock::load(&directory).await.unwrap();
+    let entry = lock.entries.get("http://example.com/api.yaml").unwrap();
+    assert_eq!(entry.integrity, FetchLock::get_integrity("a: 1"));
+
+    // offline, served from the lock directory
+    let fetcher = LockedFetcher::new(MemoryFetcher::new(), &directory, true);
+    let text = fetcher.fetch_text(&location).await.unwrap();
+    assert_eq!(text, "a: 1");
+    let error = fetcher.fetch_text(&other_location).await.unwrap_err();
+    assert!(matches!(error, FetchTextError::NotFound));
+
+    // the remote document changed
+    let fetcher = LockedFetcher::new(
+      MemoryFetcher::from_iter([(location.clone(), "a: 2")]),
+      &directory,
+      false,
+    );
+    let error = fetcher.fetch_text(&location).await.unwrap_err();
+    assert!(matches!(error, FetchTextError::IntegrityError));
+
+    // the stored document was tampered with
+    let text_location = format!("{}/{}", directory, &entry.integrity[7..]);
+    std::fs::write(text_location, "a: 3").unwrap();
+    let fetcher = LockedFetcher::new(MemoryFetcher::new(), &directory, true);
+    let error = fetcher.fetch_text(&location).await.unwrap_err();
+    assert!(matches!(error, FetchTextError::IntegrityError));
+
+    std::fs::remove_dir_all(&directory).unwrap();
+  }
+}
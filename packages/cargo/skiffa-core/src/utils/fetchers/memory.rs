@@ -0,0 +1,43 @@
+use super::{FetchTextFuture, Fetcher};
+use crate::utils::{FetchTextError, NodeLocation};
+use std::collections::BTreeMap;
+
+/// Fetches documents from memory, useful for embedded bundles and tests.
+///
+#[derive(Default)]
+pub struct MemoryFetcher {
+  texts: BTreeMap<NodeLocation, String>,
+}
+
+impl MemoryFetcher {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Add a document to the fetcher, existing documents are replaced.
+  ///
+  pub fn insert(&mut self, location: &NodeLocation, text: impl Into<String>) {
+    self.texts.insert(location.set_root(), text.into());
+  }
+}
+
+impl<L, T> FromIterator<(L, T)> for MemoryFetcher
+where
+  L: Into<NodeLocation>,
+  T: Into<String>,
+{
+  fn from_iter<I: IntoIterator<Item = (L, T)>>(iter: I) -> Self {
+    let mut fetcher = Self::new();
+    for (location, text) in iter {
+      fetcher.insert(&location.into(), text);
+    }
+    fetcher
+  }
+}
+
+impl Fetcher for MemoryFetcher {
+  fn fetch_text<'a>(&'a self, location: &'a NodeLocation) -> FetchTextFuture<'a> {
+    let text = self.texts.get(&location.set_root()).cloned();
+    Box::pin(async move { text.ok_or(FetchTextError::NotFound) })
+  }
+}
@@ -1,14 +1,22 @@
-use super::{fetch_text, FetchTextError, NodeLocation};
+use super::sync;
+use super::{parse_yaml, DefaultFetcher, FetchTextError, Fetcher, NodeLocation, YamlError};
 use std::collections::{btree_map, BTreeMap};
 use std::iter;
+use wasm_bindgen::prelude::*;
 
 /// Caches nodes (json / yaml) and indexes the nodes by their location.
 /// Nodes have a retrieval location that is the physical (possibly globally
-/// unique) location of the node.
+/// unique) location of the node. Documents are retrieved via a `Fetcher`.
 ///
-#[derive(Default)]
 pub struct NodeCache {
   root_nodes: BTreeMap<NodeLocation, serde_json::Value>,
+  fetcher: sync::Shared<dyn Fetcher>,
+}
+
+impl Default for NodeCache {
+  fn default() -> Self {
+    Self::new_with_fetcher(DefaultFetcher::new())
+  }
 }
 
 impl NodeCache {
@@ -16,6 +24,40 @@ impl NodeCache {
     Default::default()
   }
 
+  pub fn new_with_fetcher(fetcher: impl Fetcher + 'static) -> Self {
+    Self {
+      root_nodes: Default::default(),
+      fetcher: sync::Shared::new(fetcher),
+    }
+  }
+
+  /// Replace the fetcher that is used to load documents that are not yet in
+  /// the cache.
+  ///
+  pub fn set_fetcher(&mut self, fetcher: impl Fetcher + 'static) {
+    self.fetcher = sync::Shared::new(fetcher);
+  }
+
+  /// The fetcher that is used to load documents. Use this together with
+  /// `fetch_node` and `load_from_node` to load documents without borrowing
+  /// the cache while fetching.
+  ///
+  pub fn get_fetcher(&self) -> sync::Shared<dyn Fetcher> {
+    self.fetcher.clone()
+  }
+
+  /// Is the document at this location in the cache?
+  ///
+  pub fn contains_location(&self, retrieval_location: &NodeLocation) -> bool {
+    self.root_nodes.contains_key(&retrieval_location.set_root())
+  }
+
+  /// Retrieves the (root) locations of the documents in the cache
+  ///
+  pub fn get_root_locations(&self) -> impl Iterator<Item = &NodeLocation> {
+    self.root_nodes.keys()
+  }
+
   /// Retrieves all locations in the cache
   ///
   pub fn get_locations(&self) -> impl Iterator<Item = NodeLocation> + '_ {
@@ -76,8 +118,7 @@ impl NodeCache {
       /*
       retrieve the document
       */
-      let data = fetch_text(&entry.key().to_fetch_string()).await?;
-      let root_node = serde_yaml::from_str(&data)?;
+      let root_node = Self::fetch_node(self.fetcher.as_ref(), entry.key()).await?;
 
       /*
       populate the cache with this document
@@ -88,25 +129,67 @@ impl NodeCache {
     Ok(())
   }
 
+  /// Fetch and parse the document at the location via the fetcher, the
+  /// document is not added to the cache.
+  ///
+  pub async fn fetch_node(
+    fetcher: &dyn Fetcher,
+    retrieval_location: &NodeLocation,
+  ) -> Result<serde_json::Value, NodeCacheError> {
+    let data = fetcher.fetch_text(&retrieval_location.set_root()).await?;
+    let root_node = parse_yaml(&data)?;
+
+    Ok(root_node)
+  }
+
   pub fn load_from_node(
     &mut self,
     retrieval_location: &NodeLocation,
     node: serde_json::Value,
   ) -> Result<(), NodeCacheError> {
-    /*
-    If the document is not in the cache
-    */
-    if let btree_map::Entry::Vacant(entry) = self.root_nodes.entry(retrieval_location.clone()) {
+    match self.root_nodes.entry(retrieval_location.clone()) {
       /*
-      populate the cache with this document
+      If the document is not in the cache, populate the cache with this
+      document
+      */
+      btree_map::Entry::Vacant(entry) => {
+        entry.insert(node);
+        Ok(())
+      }
+      /*
+      The same document may be loaded more than once
       */
-      entry.insert(node);
-      Ok(())
-    } else {
-      Err(NodeCacheError::Conflict)
+      btree_map::Entry::Occupied(entry) if *entry.get() == node => Ok(()),
+      btree_map::Entry::Occupied(_) => Err(NodeCacheError::Conflict),
     }
   }
 
+  /// Parse the text and add it to the cache, just like `load_from_node`.
+  ///
+  pub fn load_from_text(
+    &mut self,
+    retrieval_location: &NodeLocation,
+    text: &str,
+    format: TextFormat,
+  ) -> Result<(), NodeCacheError> {
+    let node = match format {
+      TextFormat::Json => serde_json::from_str(text)?,
+      TextFormat::Yaml => parse_yaml(text)?,
+    };
+
+    self.load_from_node(retrieval_location, node)
+  }
+
+  /// Remove the document at the location from the cache so it can be loaded
+  /// again. Returns false if the document was not in the cache.
+  ///
+  pub fn invalidate(&mut self, retrieval_location: &NodeLocation) -> bool {
+    self
+      .root_nodes
+      .remove(&retrieval_location.set_root())
+      .is_some()
+  }
+
   fn get_node_path_with_member(
     &self,
     root_location: &NodeLocation,
@@ -163,11 +246,23 @@ impl NodeCache {
   }
 }
 
+/// The format of the text of a document. Every json document is also a valid
+/// yaml document, so yaml may be used for both.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[wasm_bindgen]
+pub enum TextFormat {
+  Json,
+  Yaml,
+}
+
 #[derive(Debug)]
 pub enum NodeCacheError {
   SerializationError,
   Conflict,
   FetchError,
+  IntegrityError,
+  YamlError(YamlError),
 }
 
 impl From<FetchTextError> for NodeCacheError {
@@ -175,20 +270,33 @@ impl From<FetchTextError> for NodeCacheError {
     match value {
       FetchTextError::IoError => Self::FetchError,
       FetchTextError::HttpError => Self::FetchError,
+      FetchTextError::HttpStatus(_) => Self::FetchError,
+      FetchTextError::UnexpectedContentType => Self::FetchError,
+      FetchTextError::NotFound => Self::FetchError,
+      FetchTextError::IntegrityError => Self::IntegrityError,
+      FetchTextError::UnsupportedOption => Self::FetchError,
+      FetchTextError::SerializationError => Self::SerializationError,
     }
   }
 }
 
-impl From<serde_yaml::Error> for NodeCacheError {
-  fn from(_value: serde_yaml::Error) -> Self {
+impl From<serde_json::Error> for NodeCacheError {
+  fn from(_value: serde_json::Error) -> Self {
     Self::SerializationError
   }
 }
 
+impl From<YamlError> for NodeCacheError {
+  fn from(value: YamlError) -> Self {
+    Self::YamlError(value)
+  }
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 #[cfg(test)]
 mod tests {
   use super::*;
+  use crate::utils::MemoryFetcher;
 
   #[tokio::test]
   async fn test_load_from_location() {
@@ -220,4 +328,22 @@ mod tests {
       .unwrap();
     assert_eq!(*node, serde_json::Value::String("object".into()));
   }
+
+  #[tokio::test]
+  async fn test_load_from_location_with_fetcher() {
+    let location: NodeLocation = "memory:/api.yaml".parse().unwrap();
+    let mut fetcher = MemoryFetcher::new();
+    fetcher.insert(&location, "openapi: 3.0.3\ninfo:\n  title: test\n");
+
+    let mut cache = NodeCache::new_with_fetcher(fetcher);
+    cache.load_from_location(&location).await.unwrap();
+
+    let node = cache
+      .get_node(&location.set_pointer(vec!["info".into(), "title".into()]))
+      .unwrap();
+    assert_eq!(*node, serde_json::Value::String("test".into()));
+
+    let location: NodeLocation = "memory:/other.yaml".parse().unwrap();
+    assert!(cache.load_from_location(&location).await.is_err());
+  }
 }
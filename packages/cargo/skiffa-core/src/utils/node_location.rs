@@ -2,14 +2,28 @@ use once_cell::sync::Lazy;
 use regex::{Regex, RegexBuilder};
 use std::{error::Error, fmt, hash::Hash, iter, str::FromStr};
 
+/// Splits a location in origin (scheme and / or authority), path, query and
+/// hash. This is the regex from RFC 3986 appendix B, with the scheme and
+/// authority taken together.
+///
 pub static URL_REGEX: Lazy<Regex> = Lazy::new(|| {
-  RegexBuilder::new(r"^([a-z]+\:(?:\/\/)?[^\/]*)?([^\?\#]*?)?(\?.*?)?(\#.*?)?$")
+  RegexBuilder::new(r"^((?:[a-z][a-z0-9\+\-\.]*\:)(?:\/\/[^\/\?\#]*)?|\/\/[^\/\?\#]*)?([^\?\#]*)(\?[^\#]*)?(\#.*)?$")
     .unicode(true)
     .case_insensitive(true)
     .build()
     .unwrap()
 });
 
+/// Windows paths start with a drive letter, we handle the drive as if it was a
+/// scheme, this makes reference resolution work as expected.
+///
+pub static DRIVE_REGEX: Lazy<Regex> = Lazy::new(|| {
+  RegexBuilder::new(r"^[a-z]\:(?:\/|$)")
+    .case_insensitive(true)
+    .build()
+    .unwrap()
+});
+
 #[derive(
   Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
 )]
@@ -25,9 +39,9 @@ pub struct NodeLocation {
 impl NodeLocation {
   fn new(origin: String, path: Vec<String>, query: String, hash: Vec<String>) -> Self {
     Self {
-      origin,
+      origin: normalize_origin(origin),
       path: normalize_path(path),
-      query,
+      query: normalize_percent_encoding(query),
       hash: normalize_hash(hash),
     }
   }
@@ -48,6 +62,10 @@ impl NodeLocation {
     }
   }
 
+  pub fn get_origin(&self) -> String {
+    self.origin.clone()
+  }
+
   pub fn get_path(&self) -> Vec<String> {
     self.path.to_vec()
   }
@@ -60,6 +78,14 @@ impl NodeLocation {
     self.hash.is_empty()
   }
 
+  /*
+  Is this a location on the local file system? That is a location without a
+  scheme, a location with a (windows) drive letter or a file uri.
+  */
+  pub fn is_file(&self) -> bool {
+    self.origin.is_empty() || self.is_drive() || self.origin == "file://"
+  }
+
   /*
   Set the anchor of this location, replacing the pointer.
   */
@@ -120,28 +146,52 @@ impl NodeLocation {
 
   /**
   Get the part of the location before the hash. This could be used to get data from a server
-  or file system.
+  or file system. For file locations this is a (decoded) file system path.
   */
   pub fn to_fetch_string(&self) -> String {
-    let origin = &self.origin;
-    let path = self
-      .path
-      .iter()
-      .map(|part| urlencoding::encode(part))
-      .collect::<Vec<_>>()
-      .join("/");
-    let query = &self.query;
+    if self.is_file() {
+      let path = self.path.join("/");
+      if self.is_drive() {
+        return self.origin.clone() + path.as_str();
+      }
+      if self.origin.is_empty() {
+        return if path.is_empty() {
+          "./".to_owned()
+        } else {
+          path
+        };
+      }
+      // a file uri, it may still contain a drive letter
+      if DRIVE_REGEX.is_match(path.trim_start_matches('/')) {
+        return path.trim_start_matches('/').to_owned();
+      }
+      return path;
+    }
 
-    return origin.to_string() + path.as_str() + query.as_str();
+    self.origin.clone() + encode_path(&self.origin, &self.path).as_str() + self.query.as_str()
   }
 
+  /**
+  Resolve other against this location, as described in RFC 3986 section 5.2.2. Dot segments
+  are removed from the resolved path.
+  */
   pub fn join(&self, other: &NodeLocation) -> Self {
     if !other.origin.is_empty() {
-      return other.clone();
+      if other.has_scheme() {
+        return other.clone();
+      }
+
+      // network path reference, we only take the scheme from this location
+      return NodeLocation::new(
+        self.get_scheme().to_owned() + other.origin.as_str(),
+        other.path.clone(),
+        other.query.clone(),
+        other.hash.clone(),
+      );
     }
 
     if !other.path.is_empty() {
-      if other.path.first().unwrap().is_empty() {
+      if other.is_absolute_path() {
         return NodeLocation::new(
           self.origin.clone(),
           other.path.clone(),
@@ -149,14 +199,21 @@ impl NodeLocation {
           other.hash.clone(),
         );
       } else {
-        return NodeLocation::new(
-          self.origin.clone(),
+        let base_path: Vec<_> = if self.path.is_empty() && self.has_authority() {
+          vec![String::new()]
+        } else {
           self
             .path
             .iter()
-            .take(self.path.len() - 1)
-            .chain(other.path.iter())
+            .take(self.path.len().saturating_sub(1))
             .cloned()
+            .collect()
+        };
+        return NodeLocation::new(
+          self.origin.clone(),
+          base_path
+            .into_iter()
+            .chain(other.path.iter().cloned())
             .collect(),
           other.query.clone(),
           other.hash.clone(),
@@ -180,6 +237,31 @@ impl NodeLocation {
       other.hash.clone(),
     )
   }
+
+  fn get_scheme(&self) -> &str {
+    if self.has_scheme() {
+      let index = self.origin.find(':').unwrap();
+      &self.origin[..index + 1]
+    } else {
+      ""
+    }
+  }
+
+  fn has_scheme(&self) -> bool {
+    !self.origin.is_empty() && !self.origin.starts_with("//")
+  }
+
+  fn has_authority(&self) -> bool {
+    self.origin.contains("//")
+  }
+
+  fn is_drive(&self) -> bool {
+    self.origin.len() == 2 && DRIVE_REGEX.is_match(&self.origin)
+  }
+
+  fn is_absolute_path(&self) -> bool {
+    self.path.len() > 1 && self.path[0].is_empty()
+  }
 }
 
 impl TryFrom<&str> for NodeLocation {
@@ -211,7 +293,7 @@ impl TryFrom<&str> for NodeLocation {
       path
         .split('/')
         .map(|part| urlencoding::decode(part).map_err(|_error| ParseLocationError::DecodeError))
-        .map(|part| part.map(unescape_hash))
+        .map(|part| part.map(|part| part.into_owned()))
         .collect::<Result<_, _>>()?
     };
 
@@ -237,12 +319,7 @@ impl TryFrom<&str> for NodeLocation {
 impl From<&NodeLocation> for String {
   fn from(value: &NodeLocation) -> Self {
     let origin = &value.origin;
-    let path = value
-      .path
-      .iter()
-      .map(|part| urlencoding::encode(part))
-      .collect::<Vec<_>>()
-      .join("/");
+    let path = encode_path(origin, &value.path);
     let query = &value.query;
 
     let hash = "#".to_string()
@@ -254,7 +331,7 @@ impl From<&NodeLocation> for String {
         .collect::<Vec<_>>()
         .join("/");
 
-    return origin.to_string() + path.as_str() + query.as_str() + hash.as_str();
+    origin.to_string() + path.as_str() + query.as_str() + hash.as_str()
   }
 }
 
@@ -304,40 +381,153 @@ fn unescape_hash(input: impl AsRef<str>) -> String {
   input.as_ref().replace("~1", "/").replace("~0", "~")
 }
 
-fn normalize_path(path: impl IntoIterator<Item = impl ToString>) -> Vec<String> {
-  let mut path: Vec<_> = path.into_iter().map(|part| part.to_string()).collect();
+fn is_unreserved(byte: u8) -> bool {
+  byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~')
+}
 
-  let mut path_index = 0;
+fn is_path_character(byte: u8) -> bool {
+  is_unreserved(byte)
+    || matches!(
+      byte,
+      b'!' | b'$' | b'&' | b'\'' | b'(' | b')' | b'*' | b'+' | b',' | b';' | b'=' | b':' | b'@'
+    )
+}
 
-  while path_index < path.len() {
-    // first or last parts may be empty
-    if (path_index == 0 || path_index == path.len() - 1) && path[path_index].is_empty() {
-      path_index += 1;
-      continue;
-    }
+/*
+Encode a path, every part is percent encoded except for the characters that are allowed in a
+path segment. If there is no origin and the first part has a colon, then we prefix the path
+with a dot segment so the colon is not mistaken for a scheme.
+*/
+fn encode_path(origin: &str, path: &[String]) -> String {
+  let path = path
+    .iter()
+    .map(|part| {
+      part
+        .bytes()
+        .map(|byte| {
+          if is_path_character(byte) {
+            (byte as char).to_string()
+          } else {
+            format!("%{:02X}", byte)
+          }
+        })
+        .collect::<String>()
+    })
+    .collect::<Vec<_>>();
+
+  if origin.is_empty() && path.len() == 1 && path[0].is_empty() {
+    return "./".to_owned();
+  }
 
-    // empty parts, or paths that are a dot are removed
-    if path[path_index].is_empty() || path[path_index] == "." {
-      path.remove(path_index);
-      continue;
-    }
+  if origin.is_empty() && path.first().is_some_and(|part| part.contains(':')) {
+    return "./".to_owned() + path.join("/").as_str();
+  }
+
+  path.join("/")
+}
 
-    if path[path_index] == ".." && path_index > 0 && path[path_index - 1].is_empty() {
-      path.remove(path_index);
-      continue;
+/*
+Percent encoded unreserved characters are decoded, all other percent encodings are made
+uppercase.
+*/
+fn normalize_percent_encoding(input: impl AsRef<str>) -> String {
+  let input = input.as_ref().as_bytes();
+  let mut output = Vec::with_capacity(input.len());
+
+  let mut index = 0;
+  while index < input.len() {
+    if input[index] == b'%' && index + 2 < input.len() {
+      let hex = std::str::from_utf8(&input[index + 1..index + 3]).unwrap_or_default();
+      if let Ok(byte) = u8::from_str_radix(hex, 16) {
+        if is_unreserved(byte) {
+          output.push(byte);
+        } else {
+          output.extend(format!("%{:02X}", byte).bytes());
+        }
+        index += 3;
+        continue;
+      }
     }
 
-    if path[path_index] == ".." && path_index > 0 && path[path_index - 1] != ".." {
-      path.remove(path_index);
-      path_index -= 1;
-      path.remove(path_index);
-      continue;
+    output.push(input[index]);
+    index += 1;
+  }
+
+  String::from_utf8(output).unwrap()
+}
+
+/*
+Scheme and host are case insensitive and made lowercase, default ports are removed.
+*/
+fn normalize_origin(origin: impl AsRef<str>) -> String {
+  let origin = origin.as_ref();
+
+  let (scheme, authority) = if let Some(authority) = origin.strip_prefix("//") {
+    ("", Some(authority))
+  } else if let Some(index) = origin.find(':') {
+    let scheme = &origin[..index + 1];
+    let rest = &origin[index + 1..];
+    (scheme, rest.strip_prefix("//"))
+  } else {
+    ("", None)
+  };
+  let scheme = scheme.to_lowercase();
+
+  let Some(authority) = authority else {
+    return scheme;
+  };
+
+  let (user_information, host) = match authority.rfind('@') {
+    Some(index) => (&authority[..index + 1], &authority[index + 1..]),
+    None => ("", authority),
+  };
+  let host = normalize_percent_encoding(host.to_lowercase());
+  let host = match scheme.as_str() {
+    "http:" | "ws:" => host.strip_suffix(":80").unwrap_or(&host),
+    "https:" | "wss:" => host.strip_suffix(":443").unwrap_or(&host),
+    _ => &host,
+  };
+
+  scheme + "//" + user_information + host
+}
+
+/*
+Removes dot segments from the path as described in RFC 3986 section 5.2.4, with the exception
+that relative paths keep leading double dot segments. This way we can resolve relative paths
+against other relative paths.
+*/
+fn normalize_path(path: impl IntoIterator<Item = impl ToString>) -> Vec<String> {
+  let path: Vec<_> = path.into_iter().map(|part| part.to_string()).collect();
+  let mut result: Vec<String> = Vec::new();
+
+  for (index, part) in path.iter().enumerate() {
+    let last = index == path.len() - 1;
+
+    match part.as_str() {
+      "." => {}
+      ".." => match result.last() {
+        // relative path that goes above the base
+        None => result.push(part.clone()),
+        Some(previous) if previous == ".." => result.push(part.clone()),
+        // the root of an absolute path
+        Some(previous) if previous.is_empty() && result.len() == 1 => {}
+        Some(_) => {
+          result.pop();
+        }
+      },
+      _ => {
+        result.push(part.clone());
+        continue;
+      }
     }
 
-    path_index += 1;
+    // a path that ends in a dot segment is a directory
+    if last {
+      result.push(String::new());
+    }
   }
 
-  path
+  result
 }
 
 fn normalize_hash(hash: impl IntoIterator<Item = impl ToString>) -> Vec<String> {
@@ -544,4 +734,206 @@ mod tests {
       assert_eq!(actual, expected);
     }
   }
+
+  #[test]
+  fn node_location_join_rfc3986() {
+    // RFC 3986 section 5.4.1
+    do_test("g:h", "g:h");
+    do_test("g", "http://a/b/c/g");
+    do_test("./g", "http://a/b/c/g");
+    do_test("g/", "http://a/b/c/g/");
+    do_test("/g", "http://a/g");
+    do_test("//g", "http://g");
+    do_test("?y", "http://a/b/c/d;p?y");
+    do_test("g?y", "http://a/b/c/g?y");
+    do_test("#s", "http://a/b/c/d;p?q#s");
+    do_test("g#s", "http://a/b/c/g#s");
+    do_test("g?y#s", "http://a/b/c/g?y#s");
+    do_test(";x", "http://a/b/c/;x");
+    do_test("g;x", "http://a/b/c/g;x");
+    do_test("g;x?y#s", "http://a/b/c/g;x?y#s");
+    do_test("", "http://a/b/c/d;p?q");
+    do_test(".", "http://a/b/c/");
+    do_test("./", "http://a/b/c/");
+    do_test("..", "http://a/b/");
+    do_test("../", "http://a/b/");
+    do_test("../g", "http://a/b/g");
+    do_test("../..", "http://a/");
+    do_test("../../", "http://a/");
+    do_test("../../g", "http://a/g");
+
+    // RFC 3986 section 5.4.2
+    do_test("../../../g", "http://a/g");
+    do_test("../../../../g", "http://a/g");
+    do_test("/./g", "http://a/g");
+    do_test("/../g", "http://a/g");
+    do_test("g.", "http://a/b/c/g.");
+    do_test(".g", "http://a/b/c/.g");
+    do_test("g..", "http://a/b/c/g..");
+    do_test("..g", "http://a/b/c/..g");
+    do_test("./../g", "http://a/b/g");
+    do_test("./g/.", "http://a/b/c/g/");
+    do_test("g/./h", "http://a/b/c/g/h");
+    do_test("g/../h", "http://a/b/c/h");
+    do_test("g;x=1/./y", "http://a/b/c/g;x=1/y");
+    do_test("g;x=1/../y", "http://a/b/c/y");
+    do_test("g?y/./x", "http://a/b/c/g?y/./x");
+    do_test("g?y/../x", "http://a/b/c/g?y/../x");
+    do_test("http:g", "http:g");
+
+    fn do_test(other: &str, expected: &str) {
+      let actual = "http://a/b/c/d;p?q"
+        .parse::<NodeLocation>()
+        .unwrap()
+        .join(&other.parse().unwrap());
+      let expected = expected.parse::<NodeLocation>().unwrap();
+      assert_eq!(actual, expected);
+    }
+  }
+
+  #[test]
+  fn node_location_normalize() {
+    do_test("HTTP://Example.COM:80/a", "http://example.com/a");
+    do_test("https://example.com:443/a", "https://example.com/a");
+    do_test("https://example.com:8443/a", "https://example.com:8443/a");
+    do_test("http://example.com/%7Ea%2db", "http://example.com/~a-b");
+    do_test("http://example.com/a?%7e%2f", "http://example.com/a?~%2F");
+    do_test("a/../b.yaml", "b.yaml");
+    do_test("./a/./b.yaml", "a/b.yaml");
+    do_test("C:\\a\\..\\b.yaml", "c:/b.yaml");
+    do_test("file:///a/../b.yaml", "file:///b.yaml");
+
+    fn do_test(actual: &str, expected: &str) {
+      let actual = actual.parse::<NodeLocation>().unwrap();
+      let expected = expected.parse::<NodeLocation>().unwrap();
+      assert_eq!(actual, expected);
+    }
+  }
+
+  #[test]
+  fn node_location_fetch_string() {
+    do_test(
+      "http://example.com/a%20b?c=d#/e",
+      "http://example.com/a%20b?c=d",
+    );
+    do_test("a/b%20c.yaml#/d", "a/b c.yaml");
+    do_test("../../a.yaml", "../../a.yaml");
+    do_test("c:\\a\\b.yaml", "c:/a/b.yaml");
+    do_test("file:///a/b%20c.yaml", "/a/b c.yaml");
+    do_test("file:///c:/a/b.yaml", "c:/a/b.yaml");
+
+    fn do_test(actual: &str, expected: &str) {
+      let actual = actual.parse::<NodeLocation>().unwrap().to_fetch_string();
+      assert_eq!(actual, expected);
+    }
+  }
+
+  mod properties {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn origin_strategy() -> impl Strategy<Value = String> {
+      prop_oneof![
+        Just(String::new()),
+        Just("c:".to_owned()),
+        Just("file://".to_owned()),
+        "(http|https)://[a-z]{1,8}\\.(com|org)(:[0-9]{2,4})?",
+      ]
+    }
+
+    fn segment_strategy() -> impl Strategy<Value = String> {
+      prop_oneof![
+        Just(".".to_owned()),
+        Just("..".to_owned()),
+        "[a-zA-Z0-9 _~%:;@!-]{1,8}",
+      ]
+    }
+
+    fn location_strategy() -> impl Strategy<Value = String> {
+      (
+        origin_strategy(),
+        prop::collection::vec(segment_strategy(), 0..6),
+        prop::option::of("[a-z0-9=&]{0,8}"),
+        prop::collection::vec("[a-z0-9~]{1,4}", 0..4),
+      )
+        .prop_map(|(origin, path, query, pointer)| {
+          let path = path
+            .iter()
+            .map(|part| urlencoding::encode(part).into_owned())
+            .collect::<Vec<_>>()
+            .join("/");
+          let path = if origin.is_empty() || path.is_empty() {
+            path
+          } else {
+            "/".to_owned() + path.as_str()
+          };
+          let query = query.map(|query| "?".to_owned() + query.as_str());
+          origin + path.as_str() + query.unwrap_or_default().as_str() + "#/" + &pointer.join("/")
+        })
+    }
+
+    proptest! {
+      #[test]
+      fn parse_display_round_trip(input in location_strategy()) {
+        let location: NodeLocation = input.parse().unwrap();
+        let reparsed: NodeLocation = location.to_string().parse().unwrap();
+        prop_assert_eq!(reparsed, location);
+      }
+
+      #[test]
+      fn normalize_is_idempotent(path in prop::collection::vec(segment_strategy(), 0..8)) {
+        let normalized = normalize_path(path);
+        prop_assert_eq!(normalize_path(normalized.clone()), normalized);
+      }
+
+      #[test]
+      fn normalized_path_has_no_dot_segments(
+        base in location_strategy(),
+        reference in location_strategy(),
+      ) {
+        let base: NodeLocation = base.parse().unwrap();
+        let reference: NodeLocation = reference.parse().unwrap();
+        let location = base.join(&reference);
+
+        prop_assert!(!location.path.iter().any(|part| part == "."));
+        let leading = location.path.iter().take_while(|part| *part == "..").count();
+        prop_assert!(!location.path.iter().skip(leading).any(|part| part == ".."));
+      }
+
+      #[test]
+      fn join_dot_segments_are_equivalent(
+        base in location_strategy(),
+        directory in "[a-z]{1,8}",
+        file in "[a-z]{1,8}\\.yaml",
+      ) {
+        let base: NodeLocation = base.parse().unwrap();
+        let actual = base.join(&format!("{}/../{}", directory, file).parse().unwrap());
+        let expected = base.join(&file.parse().unwrap());
+        prop_assert_eq!(actual, expected);
+      }
+
+      #[test]
+      fn join_absolute_is_identity(
+        base in location_strategy(),
+        reference in "(http|https)://[a-z]{1,8}\\.com(/[a-z]{1,4}){0,4}",
+      ) {
+        let base: NodeLocation = base.parse().unwrap();
+        let reference: NodeLocation = reference.parse().unwrap();
+        prop_assert_eq!(base.join(&reference), reference);
+      }
+
+      #[test]
+      fn percent_encoding_is_equivalent(path in prop::collection::vec("[a-zA-Z0-9._~-]{1,8}", 1..4)) {
+        let plain = path.join("/");
+        let encoded = path
+          .iter()
+          .map(|part| part.bytes().map(|byte| format!("%{:02x}", byte)).collect::<String>())
+          .collect::<Vec<_>>()
+          .join("/");
+        let plain: NodeLocation = ("http://example.com/".to_owned() + plain.as_str()).parse().unwrap();
+        let encoded: NodeLocation = ("http://example.com/".to_owned() + encoded.as_str()).parse().unwrap();
+        prop_assert_eq!(plain, encoded);
+      }
+    }
+  }
 }
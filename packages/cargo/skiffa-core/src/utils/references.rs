@@ -0,0 +1,35 @@
+use super::NodeLocation;
+
+/// Collect the `$ref` members in the node, with the location of the object
+/// that contains the reference.
+///
+pub fn collect_references(
+  location: &NodeLocation,
+  node: &serde_json::Value,
+  references: &mut Vec<(NodeLocation, String)>,
+) {
+  match node {
+    serde_json::Value::Array(items) => {
+      for (index, item) in items.iter().enumerate() {
+        collect_references(
+          &location.push_pointer(vec![index.to_string()]),
+          item,
+          references,
+        );
+      }
+    }
+    serde_json::Value::Object(members) => {
+      if let Some(reference) = members.get("$ref").and_then(|value| value.as_str()) {
+        references.push((location.clone(), reference.to_owned()));
+      }
+      for (member, value) in members {
+        collect_references(
+          &location.push_pointer(vec![member.clone()]),
+          value,
+          references,
+        );
+      }
+    }
+    _ => {}
+  }
+}
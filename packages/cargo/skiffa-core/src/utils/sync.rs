@@ -0,0 +1,186 @@
+//! Shared ownership and interior mutability that is thread-safe (`Send` and
+//! `Sync`) in native builds and single-threaded in wasm builds. Use these in
+//! place of `std::rc::Rc` and `std::cell::RefCell`.
+
+#[cfg(target_os = "unknown")]
+pub use std::cell::RefCell as Lock;
+#[cfg(target_os = "unknown")]
+pub use std::rc::{Rc as Shared, Weak};
+
+#[cfg(not(target_os = "unknown"))]
+pub use std::sync::{Arc as Shared, Weak};
+
+/// `Send` and `Sync` in native builds, implemented by everything in wasm
+/// builds.
+///
+#[cfg(not(target_os = "unknown"))]
+pub trait MaybeSendSync: Send + Sync {}
+#[cfg(not(target_os = "unknown"))]
+impl<T: Send + Sync + ?Sized> MaybeSendSync for T {}
+
+/// `Send` and `Sync` in native builds, implemented by everything in wasm
+/// builds.
+///
+#[cfg(target_os = "unknown")]
+pub trait MaybeSendSync {}
+#[cfg(target_os = "unknown")]
+impl<T: ?Sized> MaybeSendSync for T {}
+
+#[cfg(not(target_os = "unknown"))]
+pub use native::*;
+
+#[cfg(not(target_os = "unknown"))]
+mod native {
+  use std::cell::RefCell;
+  use std::collections::BTreeMap;
+  use std::ops::{Deref, DerefMut};
+
+  thread_local! {
+    /**
+     * borrow state of every lock that is borrowed on this thread, by the
+     * address of the lock. A positive number counts the shared borrows, -1 is
+     * an exclusive borrow.
+     */
+    static BORROWS: RefCell<BTreeMap<usize, isize>> = Default::default();
+  }
+
+  /// A `RefCell` like lock that may be shared between threads. Borrowing
+  /// blocks until other threads release their borrows. Like a `RefCell`,
+  /// borrowing panics when it conflicts with a borrow on the same thread,
+  /// instead of waiting forever.
+  ///
+  #[derive(Debug, Default)]
+  pub struct Lock<T>(parking_lot::RwLock<T>);
+
+  impl<T> Lock<T> {
+    pub fn new(value: T) -> Self {
+      Self(parking_lot::RwLock::new(value))
+    }
+
+    pub fn borrow(&self) -> LockReadGuard<'_, T> {
+      let borrow = Borrow::shared(self.key());
+      // a recursive read does not wait for writers that are waiting, so a
+      // thread that already has a shared borrow can not deadlock
+      LockReadGuard {
+        guard: self.0.read_recursive(),
+        _borrow: borrow,
+      }
+    }
+
+    pub fn borrow_mut(&self) -> LockWriteGuard<'_, T> {
+      let borrow = Borrow::exclusive(self.key());
+      LockWriteGuard {
+        guard: self.0.write(),
+        _borrow: borrow,
+      }
+    }
+
+    fn key(&self) -> usize {
+      self as *const Self as usize
+    }
+  }
+
+  /// Records a borrow in the borrow state of this thread until dropped.
+  ///
+  struct Borrow(usize);
+
+  impl Borrow {
+    fn shared(key: usize) -> Self {
+      BORROWS.with_borrow_mut(|borrows| {
+        let state = borrows.entry(key).or_default();
+        assert!(*state >= 0, "already mutably borrowed");
+        *state += 1;
+      });
+      Self(key)
+    }
+
+    fn exclusive(key: usize) -> Self {
+      BORROWS.with_borrow_mut(|borrows| {
+        let state = borrows.entry(key).or_default();
+        assert!(*state == 0, "already borrowed");
+        *state = -1;
+      });
+      Self(key)
+    }
+  }
+
+  impl Drop for Borrow {
+    fn drop(&mut self) {
+      // the state may already be gone when the thread is shutting down
+      let _ = BORROWS.try_with(|borrows| {
+        let mut borrows = borrows.borrow_mut();
+        let state = borrows.get_mut(&self.0).unwrap();
+        if *state > 1 {
+          *state -= 1;
+        } else {
+          borrows.remove(&self.0);
+        }
+      });
+    }
+  }
+
+  pub struct LockReadGuard<'a, T> {
+    guard: parking_lot::RwLockReadGuard<'a, T>,
+    _borrow: Borrow,
+  }
+
+  impl<T> Deref for LockReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+      &self.guard
+    }
+  }
+
+  pub struct LockWriteGuard<'a, T> {
+    guard: parking_lot::RwLockWriteGuard<'a, T>,
+    _borrow: Borrow,
+  }
+
+  impl<T> Deref for LockWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+      &self.guard
+    }
+  }
+
+  impl<T> DerefMut for LockWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+      &mut self.guard
+    }
+  }
+
+  #[cfg(test)]
+  mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock() {
+      let lock = Lock::new(1);
+      {
+        let first = lock.borrow();
+        let second = lock.borrow();
+        assert_eq!(*first + *second, 2);
+      }
+      *lock.borrow_mut() += 1;
+      assert_eq!(*lock.borrow(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "already borrowed")]
+    fn test_lock_reentrant_borrow_mut() {
+      let lock = Lock::new(1);
+      let _first = lock.borrow();
+      let _second = lock.borrow_mut();
+    }
+
+    #[test]
+    #[should_panic(expected = "already mutably borrowed")]
+    fn test_lock_reentrant_borrow() {
+      let lock = Lock::new(1);
+      let _first = lock.borrow_mut();
+      let _second = lock.borrow();
+    }
+  }
+}
@@ -0,0 +1,283 @@
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum YamlErrorKind {
+  /// The text is not valid yaml
+  Syntax,
+  /// A key occurs more than once in a mapping, possibly after converting it
+  /// to a string
+  DuplicateKey,
+  /// A key that can not be converted to a string, like a sequence
+  InvalidKey,
+  /// A merge key (`<<`) with something other than a mapping or a sequence of
+  /// mappings
+  InvalidMerge,
+  /// A number that can not be represented in json, like `.inf`
+  InvalidNumber,
+}
+
+/// Diagnostic for yaml that can not be converted to the json data model.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct YamlError {
+  pub kind: YamlErrorKind,
+  pub message: String,
+  /// Line and column (both one based) in the text, if known
+  pub position: Option<(usize, usize)>,
+  /// Pointer to the node, if known
+  pub pointer: Option<Vec<String>>,
+}
+
+impl YamlError {
+  fn new(kind: YamlErrorKind, message: impl Into<String>, pointer: &[String]) -> Self {
+    Self {
+      kind,
+      message: message.into(),
+      position: None,
+      pointer: Some(pointer.to_vec()),
+    }
+  }
+}
+
+impl std::error::Error for YamlError {}
+
+impl Display for YamlError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.message)?;
+    if let Some(pointer) = &self.pointer {
+      write!(f, " at #/{}", pointer.join("/"))?;
+    }
+    Ok(())
+  }
+}
+
+impl From<serde_yaml::Error> for YamlError {
+  fn from(value: serde_yaml::Error) -> Self {
+    let message = value.to_string();
+    // serde_yaml reports duplicate keys in a mapping as a generic error
+    let kind = if message.contains("duplicate entry") {
+      YamlErrorKind::DuplicateKey
+    } else {
+      YamlErrorKind::Syntax
+    };
+
+    Self {
+      kind,
+      message,
+      position: value
+        .location()
+        .map(|location| (location.line(), location.column())),
+      pointer: None,
+    }
+  }
+}
+
+/// Parse yaml into the json data model. Aliases are expanded, merge keys
+/// (`<<`) are applied, tags are ignored and scalar keys are converted to
+/// strings, so `200:` becomes `"200"`. Duplicate keys are an error.
+///
+pub fn parse_yaml(text: &str) -> Result<serde_json::Value, YamlError> {
+  let node: serde_yaml::Value = serde_yaml::from_str(text)?;
+  convert_node(node, &mut Vec::new())
+}
+
+fn convert_node(
+  node: serde_yaml::Value,
+  pointer: &mut Vec<String>,
+) -> Result<serde_json::Value, YamlError> {
+  Ok(match node {
+    serde_yaml::Value::Null => serde_json::Value::Null,
+    serde_yaml::Value::Bool(value) => serde_json::Value::Bool(value),
+    serde_yaml::Value::Number(value) => serde_json::Value::Number(convert_number(value, pointer)?),
+    serde_yaml::Value::String(value) => serde_json::Value::String(value),
+    serde_yaml::Value::Sequence(items) => {
+      let mut result = Vec::with_capacity(items.len());
+      for (index, item) in items.into_iter().enumerate() {
+        pointer.push(index.to_string());
+        result.push(convert_node(item, pointer)?);
+        pointer.pop();
+      }
+      serde_json::Value::Array(result)
+    }
+    serde_yaml::Value::Mapping(mapping) => {
+      serde_json::Value::Object(convert_mapping(mapping, pointer)?)
+    }
+    serde_yaml::Value::Tagged(tagged) => convert_node(tagged.value, pointer)?,
+  })
+}
+
+fn convert_mapping(
+  mapping: serde_yaml::Mapping,
+  pointer: &mut Vec<String>,
+) -> Result<serde_json::Map<String, serde_json::Value>, YamlError> {
+  let mut result = serde_json::Map::new();
+  let mut merge = None;
+
+  for (key, value) in mapping {
+    if key.as_str() == Some("<<") {
+      merge = Some(value);
+      continue;
+    }
+
+    let key = convert_key(key, pointer)?;
+    pointer.push(key.clone());
+    let value = convert_node(value, pointer)?;
+    pointer.pop();
+
+    if result.contains_key(&key) {
+      return Err(YamlError::new(
+        YamlErrorKind::DuplicateKey,
+        format!("duplicate key {:?}", key),
+        pointer,
+      ));
+    }
+    result.insert(key, value);
+  }
+
+  /*
+  keys in the mapping itself take precedence over merged keys, and keys in
+  earlier merged mappings take precedence over keys in later ones. Merged
+  mappings are converted first, so merges in them are applied too.
+  */
+  if let Some(merge) = merge {
+    pointer.push("<<".to_owned());
+    let merge = convert_node(merge, pointer)?;
+    let sources = match merge {
+      serde_json::Value::Object(source) => vec![source],
+      serde_json::Value::Array(items) => items
+        .into_iter()
+        .map(|item| match item {
+          serde_json::Value::Object(source) => Ok(source),
+          _ => Err(YamlError::new(
+            YamlErrorKind::InvalidMerge,
+            "merge sequence may only contain mappings",
+            pointer,
+          )),
+        })
+        .collect::<Result<_, _>>()?,
+      _ => {
+        return Err(YamlError::new(
+          YamlErrorKind::InvalidMerge,
+          "merge value must be a mapping or a sequence of mappings",
+          pointer,
+        ))
+      }
+    };
+    pointer.pop();
+
+    for source in sources {
+      for (key, value) in source {
+        result.entry(key).or_insert(value);
+      }
+    }
+  }
+
+  Ok(result)
+}
+
+fn convert_key(key: serde_yaml::Value, pointer: &[String]) -> Result<String, YamlError> {
+  match key {
+    serde_yaml::Value::String(value) => Ok(value),
+    serde_yaml::Value::Number(value) => Ok(value.to_string()),
+    serde_yaml::Value::Bool(value) => Ok(value.to_string()),
+    serde_yaml::Value::Null => Ok("null".to_owned()),
+    serde_yaml::Value::Tagged(tagged) => convert_key(tagged.value, pointer),
+    serde_yaml::Value::Sequence(_) | serde_yaml::Value::Mapping(_) => Err(YamlError::new(
+      YamlErrorKind::InvalidKey,
+      "only scalars may be used as keys",
+      pointer,
+    )),
+  }
+}
+
+fn convert_number(
+  value: serde_yaml::Number,
+  pointer: &[String],
+) -> Result<serde_json::Number, YamlError> {
+  if let Some(value) = value.as_u64() {
+    return Ok(value.into());
+  }
+  if let Some(value) = value.as_i64() {
+    return Ok(value.into());
+  }
+
+  value
+    .as_f64()
+    .and_then(serde_json::Number::from_f64)
+    .ok_or_else(|| {
+      YamlError::new(
+        YamlErrorKind::InvalidNumber,
+        format!("invalid number {}", value),
+        pointer,
+      )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn test_parse_yaml() {
+    do_test(
+      "responses: { 200: { description: ok }, default: { description: error } }",
+      json!({
+        "responses": {
+          "200": { "description": "ok" },
+          "default": { "description": "error" }
+        }
+      }),
+    );
+
+    do_test(
+      "{ 1.5: a, true: b, ~: c, !!str 4: d }",
+      json!({ "1.5": "a", "true": "b", "null": "c", "4": "d" }),
+    );
+
+    do_test(
+      r#"
+base: &base
+  a: 1
+  b: 2
+extended: &extended
+  <<: *base
+  b: 3
+  c: 4
+chained:
+  <<: [*extended, { d: 5, a: 6 }]
+  e: 7
+"#,
+      json!({
+        "base": { "a": 1, "b": 2 },
+        "extended": { "a": 1, "b": 3, "c": 4 },
+        "chained": { "a": 1, "b": 3, "c": 4, "d": 5, "e": 7 }
+      }),
+    );
+
+    fn do_test(text: &str, expected: serde_json::Value) {
+      let actual = parse_yaml(text).unwrap();
+      assert_eq!(actual, expected);
+    }
+  }
+
+  #[test]
+  fn test_parse_yaml_errors() {
+    let error = do_test("a: 1\nb:\n  c: 1\n  c: 2\n", YamlErrorKind::DuplicateKey);
+    assert!(error.position.is_some());
+
+    let error = do_test("a:\n  200: x\n  '200': y\n", YamlErrorKind::DuplicateKey);
+    assert_eq!(error.pointer, Some(vec!["a".to_owned()]));
+
+    do_test("? [a]\n: b\n", YamlErrorKind::InvalidKey);
+    do_test("a:\n  <<: 1\n", YamlErrorKind::InvalidMerge);
+    do_test("a: .inf\n", YamlErrorKind::InvalidNumber);
+    do_test("a: [\n", YamlErrorKind::Syntax);
+
+    fn do_test(text: &str, expected: YamlErrorKind) -> YamlError {
+      let actual = parse_yaml(text).unwrap_err();
+      assert_eq!(actual.kind, expected);
+      actual
+    }
+  }
+}